@@ -375,7 +375,7 @@ const LEGENDARY_ITEMS=[
   {name:"Fenrir's Fang",icon:'🐺',slot:'weapon',base:{ATK:20,Speed:10},passive:'+20% speed, attacks apply slow',passiveId:'fenrirFang'},
   {name:"Odin's Eye",icon:'👁️',slot:'accessory',base:{ATK:8},passive:'Always see enemy HP, +15% crit chance',passiveId:'odinsEye'},
   {name:'Mjolnir Shard',icon:'🔨',slot:'weapon',base:{ATK:25},passive:'Attacks chain lightning to 2 nearby enemies for 30% dmg',passiveId:'mjolnirShard'},
-  {name:'Valkyrie Aegis',icon:'🛡️',slot:'armor',base:{DEF:18,HP:15},passive:'20% chance to negate damage completely',passiveId:'valkyrieAegis'},
+  {name:'Valkyrie Aegis',icon:'🛡️',slot:'offhand',base:{DEF:18,HP:15},passive:'20% chance to negate damage completely',passiveId:'valkyrieAegis'},
   {name:'Loki\'s Trinket',icon:'🔮',slot:'accessory',base:{Speed:12},passive:'Dash cooldown reduced 50%, leave damaging trail',passiveId:'lokiTrinket'},
   {name:'Hel\'s Embrace',icon:'👘',slot:'armor',base:{HP:50},passive:'Gain 3% max HP on kill',passiveId:'helsEmbrace'},
   {name:'Gungnir Tip',icon:'🗡️',slot:'weapon',base:{ATK:22},passive:'First hit on each enemy deals 2x damage',passiveId:'gungnirTip'},
@@ -448,6 +448,31 @@ function generateGear(slot,depth,isBoss){
     id:Math.random().toString(36).substr(2,9)};
 }
 
+// Weapons that need both hands; they can't be worn with an off-hand (matches the server)
+const TWO_HANDED_TYPES=['Hammer','Staff','Mjolnir Shard','Necro Lord Staff','Staff of Renewal'];
+function isTwoHanded(item){return !!item&&item.slot==='weapon'&&TWO_HANDED_TYPES.some(t=>(item.baseType||item.name).includes(t));}
+
+// Server `Item` rows use enum tags; the client gear model uses the legacy JSON keys
+const SERVER_STAT_KEYS={Atk:'ATK',Def:'DEF',Hp:'HP',Speed:'Speed',Lifesteal:'lifesteal',Reflect:'reflect',
+  DropRate:'dropRate',GoldBonus:'goldBonus',Crit:'crit',AttackSpeed:'attackSpeed'};
+
+/** Convert a typed server item into the client gear model */
+export function gearFromServerItem(item:any){
+  const rarity=RARITIES.find(r=>r.name===item.rarity.tag.toLowerCase())||RARITIES[0];
+  const stats:Record<string,number>={};
+  if(item.atk)stats.ATK=item.atk;
+  if(item.def)stats.DEF=item.def;
+  if(item.hp)stats.HP=item.hp;
+  if(item.speed)stats.Speed=item.speed;
+  const affixes=item.affixes.map((a:any)=>({name:a.name,stat:SERVER_STAT_KEYS[a.stat.tag],value:a.value,
+    pct:a.isPercent,flat:!a.isPercent,type:a.isPrefix?'prefix':'suffix'}));
+  const leg=item.passiveId?LEGENDARY_ITEMS.find(l=>l.passiveId===item.passiveId):null;
+  return{slot:item.slot.tag.toLowerCase(),baseType:item.baseType,name:item.name,icon:item.icon,rarity:rarity.name,rarityColor:rarity.color,
+    stats,affixes,passive:leg?leg.passive:null,passiveId:item.passiveId??null,classReq:item.classReq??undefined,
+    ilvl:item.itemLevel,cardSlot:item.card?item.card.cardType:null,source:item.source,isNew:true,
+    id:Math.random().toString(36).substr(2,9)};
+}
+
 // ─── CARD SYSTEM (Ragnarok-style) ───
 const CARD_DEFS={
   slime:{name:'Slime Card',icon:'🟢',bonus:{HP:5},bonusType:'pct',desc:'+5% HP'},
@@ -571,7 +596,7 @@ function getComputedStats(item){
 let gold=0,dungeonDepth=1;
 let playerLevel=1,playerXP=0;
 let baseMaxHp=100,baseAtk=0,baseDef=0,baseSpeed=0;
-let equipped={weapon:null,armor:null,accessory:null};
+let equipped={weapon:null,offhand:null,armor:null,accessory:null};
 let backpack=[]; // max 16
 
 // ─── LIFETIME STATS ───
@@ -760,7 +785,21 @@ const EMOTE_HOLD_THRESHOLD = 300; // ms to trigger emote wheel
 // Chat input state
 let chatInputOpen = false;
 let serverEnemyIds: bigint[] = []; // maps local enemy index → server enemy ID
-let serverLootMap: Map<string, {id: bigint, x: number, y: number, itemDataJson: string, rarity: string}> = new Map();
+let serverLootMap: Map<string, {id: bigint, x: number, y: number, item: any, rarity: string}> = new Map();
+// Telegraphed danger zones from the server, keyed by zone ID
+interface TelegraphZoneState {
+  shape: string; // 'Circle' | 'Cone' | 'Line'
+  x: number;
+  y: number;
+  radius: number; // Circle/cone radius, line length
+  angle: number;
+  arc: number;    // Cone half-angle
+  width: number;  // Line width
+  duration: number; // ms from placement to resolve
+  receivedAt: number;
+  shared: boolean;  // Stack zone: damage split between everyone inside
+}
+let telegraphZones: Map<string, TelegraphZoneState> = new Map();
 
 // ─── SERVER-AUTHORITATIVE ENEMY STATE (for interpolation) ───
 const SERVER_TICK_MS = 50; // Server runs at 20Hz
@@ -1073,44 +1112,23 @@ export function syncEnemyHp(updates: Array<{id: bigint, hp: number, isAlive: boo
     }
   }
 }
-export function addServerLoot(loot: {id: bigint, x: number, y: number, itemDataJson: string, rarity: string}) {
-  console.log('[Game] addServerLoot called:', { id: loot.id.toString(), x: loot.x, y: loot.y, rarity: loot.rarity, itemDataJson: loot.itemDataJson });
+export function addServerLoot(loot: {id: bigint, x: number, y: number, item: any, rarity: string}) {
+  console.log('[Game] addServerLoot called:', { id: loot.id.toString(), x: loot.x, y: loot.y, rarity: loot.rarity });
   const key = loot.id.toString();
   if (serverLootMap.has(key)) return;
+  if (!loot.item) {
+    console.warn('[Game] Skipping loot drop without a typed item:', key);
+    return;
+  }
   serverLootMap.set(key, loot);
 
   // Scale server coordinates to client coordinates
   const clientX = serverToClientX(loot.x);
   const clientY = serverToClientY(loot.y);
 
-  // Parse item data to get enemy source type
-  let enemyType = 'slime'; // Default
-  try {
-    const itemData = JSON.parse(loot.itemDataJson);
-    if (itemData.source) {
-      enemyType = itemData.source;
-    }
-  } catch (e) {
-    console.warn('[Game] Failed to parse loot itemDataJson:', e);
-  }
-
-  // Get drop table for this enemy type
-  const table = DROP_TABLES[enemyType] || DROP_TABLES.slime;
-  const slot = pickSlotFromTable(table);
-
-  // Generate proper gear based on rarity
-  const isBoss = enemyType === 'boss';
-  const gear = generateGear(slot, dungeonDepth, isBoss);
-  console.log('[Game] Generated gear:', { enemyType, slot, gearName: gear.name, gearRarity: gear.rarity, icon: gear.icon });
-
-  // Override rarity if server specifies it
-  if (loot.rarity) {
-    gear.rarity = loot.rarity;
-    gear.rarityColor = loot.rarity === 'rare' ? '#3b82f6' :
-                       loot.rarity === 'uncommon' ? '#22c55e' :
-                       loot.rarity === 'epic' ? '#a855f7' :
-                       loot.rarity === 'legendary' ? '#f97316' : '#fff';
-  }
+  // The server rolled the item; show exactly what the player will pick up
+  const gear = gearFromServerItem(loot.item);
+  console.log('[Game] Server gear:', { source: gear.source, slot: gear.slot, gearName: gear.name, gearRarity: gear.rarity, icon: gear.icon });
 
   // Add to lootDrops for rendering with bounce animation (juice effect)
   const scatterX = (Math.random() - 0.5) * 30;
@@ -1144,9 +1162,34 @@ export function removeServerLoot(id: bigint) {
   serverLootMap.delete(key);
   lootDrops = lootDrops.filter(l => !l._serverLootId || l._serverLootId.toString() !== key);
 }
+// Add or move a server telegraph zone (follow zones move with their player)
+export function syncTelegraphZone(zone: {
+  id: bigint, roomIndex: number, shape: string, x: number, y: number, radius: number,
+  angle: number, arc: number, width: number, createdAt: number, resolveAt: number, shared: boolean
+}) {
+  if (currentRoom !== undefined && zone.roomIndex !== currentRoom) return;
+  const key = zone.id.toString();
+  const existing = telegraphZones.get(key);
+  telegraphZones.set(key, {
+    shape: zone.shape,
+    x: serverToClientX(zone.x),
+    y: serverToClientY(zone.y),
+    radius: zone.radius,
+    angle: zone.angle,
+    arc: zone.arc,
+    width: zone.width,
+    duration: Math.max(1, zone.resolveAt - zone.createdAt),
+    receivedAt: existing ? existing.receivedAt : performance.now(),
+    shared: zone.shared,
+  });
+}
+export function removeTelegraphZone(id: bigint) {
+  telegraphZones.delete(id.toString());
+}
 export function syncRoom(roomIndex: number) {
   if (roomIndex === currentRoom) return;
   serverLootMap.clear();  // Clear stale loot from previous room
+  telegraphZones.clear();
   serverEnemyStates.clear();  // Clear stale enemies from previous room
   serverEnemyIds = [];
   goToRoom(roomIndex, roomIndex > currentRoom ? 'top' : 'bottom');
@@ -1405,6 +1448,34 @@ function addDoor(r,side){
   if(side==='bottom'){r[ROOM_H-1][7]=2;}
 }
 
+// Server room layouts for the active dungeon, keyed by room index. Tile codes:
+// 0 floor, 1 wall/pillar, 2 door, 3 pit (blocks walking but not projectiles)
+let serverRoomTiles: Map<number, number[][]> = new Map();
+const SERVER_TILE_CODES = { Floor: 0, Wall: 1, Pillar: 1, Door: 2, Pit: 3 };
+
+/** Store a server room layout and swap it in if that room is already built */
+export function syncRoomTiles(layout: { roomIndex: number, width: number, height: number, tiles: string[] }) {
+  const grid: number[][] = [];
+  for (let y = 0; y < ROOM_H; y++) {
+    const row: number[] = [];
+    for (let x = 0; x < ROOM_W; x++) {
+      // The client room is one row taller than the server's; rows past it stay wall
+      const inside = x < layout.width && y < layout.height;
+      row.push(inside ? SERVER_TILE_CODES[layout.tiles[y * layout.width + x]] ?? 0 : 1);
+    }
+    grid.push(row);
+  }
+  serverRoomTiles.set(layout.roomIndex, grid);
+  if (rooms && layout.roomIndex < rooms.length && activeGameMode !== 'open_world') {
+    rooms[layout.roomIndex] = grid;
+  }
+}
+
+/** Forget the previous dungeon's layouts */
+export function clearServerRoomTiles() {
+  serverRoomTiles.clear();
+}
+
 // ─── RANDOM DUNGEON GENERATION ───
 let dungeonRooms=[];
 
@@ -1679,7 +1750,7 @@ function showHub(){
   // Gear slots
   const gearDiv=document.getElementById('hub-gear-slots');
   gearDiv.innerHTML='';
-  ['weapon','armor','accessory'].forEach(slot=>{
+  ['weapon','offhand','armor','accessory'].forEach(slot=>{
     const item=equipped[slot];
     const el=document.createElement('div');
     el.className='hub-gear-slot';
@@ -2297,6 +2368,7 @@ function resetGame(){
   yggdrasilUsed=false;
   dungeonRooms=generateDungeon(dungeonDepth);
   rooms=dungeonRooms.map(d=>{const m=makeRoom();d.doors.forEach(s=>addDoor(m,s));return m;});
+  serverRoomTiles.forEach((grid,idx)=>{if(idx<rooms.length)rooms[idx]=grid;});
   // Enemies come from server - don't spawn locally
   showRoomLabel(dungeonRooms[0].name);
   document.getElementById('depth-display').textContent='Run '+dungeonDepth;
@@ -2390,7 +2462,7 @@ let invSortMode='default'; // default, rarity, slot
 function renderInventory(){
   const eqDiv=document.getElementById('equipped-slots');
   eqDiv.innerHTML='';
-  ['weapon','armor','accessory'].forEach(slot=>{
+  ['weapon','offhand','armor','accessory'].forEach(slot=>{
     const item=equipped[slot];
     const el=document.createElement('div');
     el.className='inv-slot'+(item?' rarity-'+item.rarity:' empty');
@@ -2623,6 +2695,8 @@ function equipFromBackpack(idx){
   const item=backpack[idx];
   if(!item)return;
   const slot=item.slot;
+  if(isTwoHanded(item)&&equipped.offhand){showPickup(item.name+' is two-handed; unequip '+equipped.offhand.name+' first','#ef4444');return;}
+  if(slot==='offhand'&&isTwoHanded(equipped.weapon)){showPickup('Cannot use an off-hand item with two-handed '+equipped.weapon.name,'#ef4444');return;}
   const old=equipped[slot];
   equipped[slot]=item;
   backpack.splice(idx,1);
@@ -3091,7 +3165,8 @@ function canMove(x,y,r){
   const corners=[[x-margin,y-margin],[x+margin,y-margin],[x-margin,y+margin],[x+margin,y+margin]];
   for(const[cx,cy]of corners){
     const tx=Math.floor(cx/TILE),ty=Math.floor(cy/TILE);
-    if(tileAt(tx,ty)===1)return false;
+    const t=tileAt(tx,ty);
+    if(t===1||t===3)return false;
   }
   return true;
 }
@@ -3548,7 +3623,7 @@ function update(dt){
       l.picked=true;
       const lootJson=l.type==='gear'?JSON.stringify(l.gear):l.type==='card'?JSON.stringify({cardType:l.cardType}):JSON.stringify({type:'gold',amount:l.amount});
       const lootRarity=l.type==='gear'?(l.gear.rarity||'common'):l.type==='card'?'rare':'common';
-      callbacks.onPickupLoot?.(lootDrops.indexOf(l),lootJson,lootRarity);
+      callbacks.onPickupLoot?.(lootDrops.indexOf(l),lootJson,lootRarity,l._serverLootId);
     }
   });
   lootDrops=lootDrops.filter(l=>!l.picked);
@@ -3618,6 +3693,28 @@ function cdEdge(deg){
 }
 
 // ─── DRAW ───
+// Outline the full zone and grow a fill toward the edge as it counts down to resolve
+function drawTelegraphZones(){
+  const now=performance.now();
+  telegraphZones.forEach(z=>{
+    const t=Math.min(1,(now-z.receivedAt)/z.duration);
+    const rgb=z.shared?'251,191,36':'239,68,68';
+    const path=(scale:number)=>{
+      ctx.beginPath();
+      if(z.shape==='Cone'){ctx.moveTo(0,0);ctx.arc(0,0,z.radius*scale,-z.arc,z.arc);ctx.closePath();}
+      else if(z.shape==='Line'){ctx.rect(0,-z.width/2,z.radius*scale,z.width);}
+      else{ctx.arc(0,0,z.radius*scale,0,Math.PI*2);}
+    };
+    ctx.save();
+    ctx.translate(z.x,z.y);
+    ctx.rotate(z.angle);
+    ctx.fillStyle=`rgba(${rgb},0.15)`;path(1);ctx.fill();
+    ctx.strokeStyle=`rgba(${rgb},0.9)`;ctx.lineWidth=2;path(1);ctx.stroke();
+    ctx.fillStyle=`rgba(${rgb},0.35)`;path(t);ctx.fill();
+    ctx.restore();
+  });
+}
+
 function draw(){
   const dpr=window.devicePixelRatio||1;
 
@@ -3652,6 +3749,9 @@ function draw(){
       ctx.fillStyle=cleared?'#00aa66':'#aa0044';
       ctx.fillRect(px,py,TILE,TILE);
       if(cleared){ctx.fillStyle=THEME.lootUncommon;ctx.fillRect(px+8,py+2,TILE-16,TILE-4);}
+    }else if(t===3){
+      ctx.fillStyle='#05050a';ctx.fillRect(px,py,TILE,TILE);
+      ctx.strokeStyle='rgba(120,60,200,0.35)';ctx.strokeRect(px+2,py+2,TILE-4,TILE-4);
     }else{
       ctx.fillStyle=(x+y)%2===0?THEME.tileA:THEME.tileB;
       ctx.fillRect(px,py,TILE,TILE);
@@ -3747,6 +3847,9 @@ function draw(){
     ctx.restore();
   });
 
+  // telegraphed danger zones (under enemies)
+  drawTelegraphZones();
+
   // enemies
   enemies.forEach(e=>{
     if(e.hp<=0)return;
@@ -4352,7 +4455,7 @@ export function restoreFromServer(data: {
   xp?: number;
   dungeonDepth?: number;
   playerClass?: string;
-  inventory?: Array<{ itemDataJson: string; equippedSlot?: string | null; cardDataJson?: string | null }>;
+  inventory?: Array<{ item: any; equippedSlot?: string | null }>;
 }) {
   if (data.gold != null) gold = data.gold;
   if (data.level != null) playerLevel = data.level;
@@ -4367,17 +4470,16 @@ export function restoreFromServer(data: {
   }
   if (data.inventory) {
     backpack = [];
-    equipped = { weapon: null, armor: null, accessory: null };
+    equipped = { weapon: null, offhand: null, armor: null, accessory: null };
     data.inventory.forEach(item => {
-      try {
-        const parsed = JSON.parse(item.itemDataJson);
-        if (item.equippedSlot && (item.equippedSlot === 'weapon' || item.equippedSlot === 'armor' || item.equippedSlot === 'accessory')) {
-          equipped[item.equippedSlot] = parsed;
-        } else {
-          backpack.push(parsed);
-        }
-      } catch (e) {
-        console.warn('[Game] Failed to parse inventory item:', e);
+      // Rows still waiting on `migrate_item_json` have no typed item yet
+      if (!item.item) return;
+      const gear = gearFromServerItem(item.item);
+      gear.isNew = false;
+      if (item.equippedSlot && item.equippedSlot in equipped) {
+        equipped[item.equippedSlot] = gear;
+      } else {
+        backpack.push(gear);
       }
     });
   }
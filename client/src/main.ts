@@ -1,7 +1,7 @@
 // main.ts — Entry point
 // Server-authoritative multiplayer with client interpolation
 
-import { initGame, setGameMode, setCallbacks, restoreFromServer, updateOtherPlayer, removeOtherPlayer, syncEnemyFromServer, removeServerEnemy, addServerLoot, removeServerLoot, syncRoom, getCurrentRoom, initServerEnemies, getServerEnemyIds, syncPlayerStats, clientToServerX, clientToServerY, getEquippedIcons, receiveMessage, setPlayerClass, getPlayerClass, returnToHub, onMatchFound, getActiveGameMode, getOpenWorldRoom, pauseGame, initOpenWorldServerEnemies, syncOpenWorldEnemyFromServer, removeOpenWorldServerEnemy, setOpenWorldInstanceId, clearOpenWorldServerEnemies } from './game';
+import { initGame, setGameMode, setCallbacks, restoreFromServer, updateOtherPlayer, removeOtherPlayer, syncEnemyFromServer, removeServerEnemy, addServerLoot, removeServerLoot, syncTelegraphZone, removeTelegraphZone, syncRoomTiles, clearServerRoomTiles, syncRoom, getCurrentRoom, initServerEnemies, getServerEnemyIds, syncPlayerStats, clientToServerX, clientToServerY, getEquippedIcons, receiveMessage, setPlayerClass, getPlayerClass, returnToHub, onMatchFound, getActiveGameMode, getOpenWorldRoom, pauseGame, initOpenWorldServerEnemies, syncOpenWorldEnemyFromServer, removeOpenWorldServerEnemy, setOpenWorldInstanceId, clearOpenWorldServerEnemies } from './game';
 import { spacetimeClient } from './spacetime';
 import type { PlayerClass, ActiveGameMode } from './types';
 import { CLASS_STATS } from './types';
@@ -18,6 +18,12 @@ let activeDungeonId: bigint | null = null;
 let activeRaidId: bigint | null = null;
 let openWorldInstanceId: bigint | null = null;
 
+// Swap in the room layouts the server already generated for a newly active dungeon
+function loadRoomTiles(dungeonId: bigint) {
+  clearServerRoomTiles();
+  spacetimeClient.getRoomTileMaps(dungeonId).forEach(syncRoomTiles);
+}
+
 // Class selection UI
 function showClassSelection() {
   const overlay = document.createElement('div');
@@ -136,6 +142,23 @@ async function main() {
       }
     });
 
+    // Server room layouts: walls, pillars and pits the server collides against
+    spacetimeClient.onRoomTileMapChange((layout) => {
+      if (activeDungeonId == null) return;
+      if (layout.dungeonId.toString() !== activeDungeonId.toString()) return;
+      syncRoomTiles(layout);
+    });
+
+    // Server telegraph zones: draw the danger areas the server will resolve
+    spacetimeClient.onTelegraphZoneChange((zone) => {
+      if (activeDungeonId == null) return;
+      // Use string comparison for bigint
+      if (zone.dungeonId.toString() !== activeDungeonId.toString()) return;
+      syncTelegraphZone(zone);
+    }, (id) => {
+      removeTelegraphZone(id);
+    });
+
     // Co-op: listen for room transitions from other player
     spacetimeClient.onDungeonUpdate((dungeon) => {
       // Use string comparison for bigint
@@ -214,6 +237,7 @@ async function main() {
           const d = spacetimeClient.getActiveDungeon();
           if (d) {
             activeDungeonId = d.id;
+            loadRoomTiles(activeDungeonId);
             console.log('[Main] Resolved active dungeon ID (poll):', activeDungeonId, 'server room:', d.currentRoom);
             // Sync to server's room if different from client
             const clientRoom = getCurrentRoom();
@@ -269,9 +293,10 @@ async function main() {
           spacetimeClient.useDash(activeDungeonId, dirX, dirY);
         }
       },
-      onPickupLoot: (_lootIdx, itemDataJson, rarity) => {
-        if (itemDataJson && rarity) {
-          spacetimeClient.addInventoryItem(itemDataJson, rarity);
+      onPickupLoot: (_lootIdx, _itemDataJson, _rarity, serverLootId) => {
+        // Server drops carry their rolled item; the server moves it into the inventory
+        if (serverLootId != null) {
+          spacetimeClient.pickupLoot(serverLootId);
         }
       },
       onCompleteDungeon: () => {
@@ -359,6 +384,7 @@ async function main() {
         spacetimeClient.startDungeonSolo(tier, difficulty).then(dungeonId => {
           if (dungeonId) {
             activeDungeonId = dungeonId;
+            loadRoomTiles(dungeonId);
             console.log('[Main] Started solo dungeon:', dungeonId);
             // Initialize enemies
             setTimeout(() => {
@@ -17,4 +17,9 @@ export default __t.row({
   currentRoom: __t.u32().name("current_room"),
   totalRooms: __t.u32().name("total_rooms"),
   seed: __t.u64(),
+  tier: __t.u32(),
+  difficulty: __t.u32(),
+  startedAt: __t.u64().name("started_at"),
+  rngState: __t.u64().name("rng_state"),
+  raidId: __t.option(__t.u64()).name("raid_id"),
 });
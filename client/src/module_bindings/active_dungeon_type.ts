@@ -17,6 +17,11 @@ export default __t.object("ActiveDungeon", {
   currentRoom: __t.u32(),
   totalRooms: __t.u32(),
   seed: __t.u64(),
+  tier: __t.u32(),
+  difficulty: __t.u32(),
+  startedAt: __t.u64(),
+  rngState: __t.u64(),
+  raidId: __t.option(__t.u64()),
 });
 
 
@@ -12,5 +12,4 @@ import {
 
 export default {
   itemDataJson: __t.string(),
-  rarity: __t.string(),
 };
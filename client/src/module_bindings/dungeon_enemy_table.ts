@@ -33,4 +33,7 @@ export default __t.row({
   tauntTimer: __t.f32().name("taunt_timer"),
   isBoss: __t.bool().name("is_boss"),
   bossPhase: __t.u32().name("boss_phase"),
+  summonerId: __t.option(__t.u64()).name("summoner_id"),
+  def: __t.i32(),
+  isPriority: __t.bool().name("is_priority"),
 });
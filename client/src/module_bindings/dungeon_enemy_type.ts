@@ -33,6 +33,9 @@ export default __t.object("DungeonEnemy", {
   tauntTimer: __t.f32(),
   isBoss: __t.bool(),
   bossPhase: __t.u32(),
+  summonerId: __t.option(__t.u64()),
+  def: __t.i32(),
+  isPriority: __t.bool(),
 });
 
 
@@ -42,6 +42,8 @@ import CancelQueueReducer from "./cancel_queue_reducer";
 export { CancelQueueReducer };
 import CompleteDungeonReducer from "./complete_dungeon_reducer";
 export { CompleteDungeonReducer };
+import DeleteEnemyDefinitionReducer from "./delete_enemy_definition_reducer";
+export { DeleteEnemyDefinitionReducer };
 import DiscardItemReducer from "./discard_item_reducer";
 export { DiscardItemReducer };
 import EnterOpenWorldReducer from "./enter_open_world_reducer";
@@ -50,10 +52,14 @@ import EnterRoomReducer from "./enter_room_reducer";
 export { EnterRoomReducer };
 import EquipItemReducer from "./equip_item_reducer";
 export { EquipItemReducer };
+import GrantAdminReducer from "./grant_admin_reducer";
+export { GrantAdminReducer };
 import LeaveOpenWorldReducer from "./leave_open_world_reducer";
 export { LeaveOpenWorldReducer };
 import LoginReducer from "./login_reducer";
 export { LoginReducer };
+import MigrateItemJsonReducer from "./migrate_item_json_reducer";
+export { MigrateItemJsonReducer };
 import PickupLootReducer from "./pickup_loot_reducer";
 export { PickupLootReducer };
 import PlaceHealingZoneReducer from "./place_healing_zone_reducer";
@@ -64,12 +70,26 @@ import QueueRaidReducer from "./queue_raid_reducer";
 export { QueueRaidReducer };
 import RegisterPlayerReducer from "./register_player_reducer";
 export { RegisterPlayerReducer };
+import ResetRaidLockoutsReducer from "./reset_raid_lockouts_reducer";
+export { ResetRaidLockoutsReducer };
+import RevivePlayerReducer from "./revive_player_reducer";
+export { RevivePlayerReducer };
+import SeedEnemyDefinitionsReducer from "./seed_enemy_definitions_reducer";
+export { SeedEnemyDefinitionsReducer };
 import SendChatReducer from "./send_chat_reducer";
 export { SendChatReducer };
 import SendEmoteReducer from "./send_emote_reducer";
 export { SendEmoteReducer };
+import SetEnemyCombatRollsReducer from "./set_enemy_combat_rolls_reducer";
+export { SetEnemyCombatRollsReducer };
+import SetEnemyDefinitionReducer from "./set_enemy_definition_reducer";
+export { SetEnemyDefinitionReducer };
+import SetEnemyOnHitReducer from "./set_enemy_on_hit_reducer";
+export { SetEnemyOnHitReducer };
 import SetGameModeReducer from "./set_game_mode_reducer";
 export { SetGameModeReducer };
+import SocketCardReducer from "./socket_card_reducer";
+export { SocketCardReducer };
 import StartDungeonReducer from "./start_dungeon_reducer";
 export { StartDungeonReducer };
 import StartDungeonSoloReducer from "./start_dungeon_solo_reducer";
@@ -82,6 +102,8 @@ import TickOpenWorldReducer from "./tick_open_world_reducer";
 export { TickOpenWorldReducer };
 import UnequipItemReducer from "./unequip_item_reducer";
 export { UnequipItemReducer };
+import UnsocketCardReducer from "./unsocket_card_reducer";
+export { UnsocketCardReducer };
 import UpdateOpenWorldPositionReducer from "./update_open_world_position_reducer";
 export { UpdateOpenWorldPositionReducer };
 import UpdatePositionReducer from "./update_position_reducer";
@@ -100,14 +122,26 @@ import ActiveDungeonRow from "./active_dungeon_table";
 export { ActiveDungeonRow };
 import ActiveHealingZoneRow from "./active_healing_zone_table";
 export { ActiveHealingZoneRow };
+import AdminRow from "./admin_table";
+export { AdminRow };
+import BossMechanicTimerRow from "./boss_mechanic_timer_table";
+export { BossMechanicTimerRow };
+import CombatEventRow from "./combat_event_table";
+export { CombatEventRow };
 import DailyRaidClearRow from "./daily_raid_clear_table";
 export { DailyRaidClearRow };
+import DownedPlayerRow from "./downed_player_table";
+export { DownedPlayerRow };
 import DungeonEnemyRow from "./dungeon_enemy_table";
 export { DungeonEnemyRow };
 import DungeonParticipantRow from "./dungeon_participant_table";
 export { DungeonParticipantRow };
 import DungeonQueueRow from "./dungeon_queue_table";
 export { DungeonQueueRow };
+import DungeonRoomRow from "./dungeon_room_table";
+export { DungeonRoomRow };
+import EnemyDefinitionRow from "./enemy_definition_table";
+export { EnemyDefinitionRow };
 import EnemyTickScheduleRow from "./enemy_tick_schedule_table";
 export { EnemyTickScheduleRow };
 import InventoryItemRow from "./inventory_item_table";
@@ -116,6 +150,8 @@ import LootDropRow from "./loot_drop_table";
 export { LootDropRow };
 import MatchmakingTickScheduleRow from "./matchmaking_tick_schedule_table";
 export { MatchmakingTickScheduleRow };
+import MovementStateRow from "./movement_state_table";
+export { MovementStateRow };
 import OpenWorldEnemyRow from "./open_world_enemy_table";
 export { OpenWorldEnemyRow };
 import OpenWorldInstanceRow from "./open_world_instance_table";
@@ -128,20 +164,42 @@ import PlayerRow from "./player_table";
 export { PlayerRow };
 import PlayerAbilityStateRow from "./player_ability_state_table";
 export { PlayerAbilityStateRow };
+import PlayerCardRow from "./player_card_table";
+export { PlayerCardRow };
 import PlayerGameModeRow from "./player_game_mode_table";
 export { PlayerGameModeRow };
 import PlayerMessageRow from "./player_message_table";
 export { PlayerMessageRow };
 import PlayerPositionRow from "./player_position_table";
 export { PlayerPositionRow };
+import ProjectileRow from "./projectile_table";
+export { ProjectileRow };
 import RaidCooldownRow from "./raid_cooldown_table";
 export { RaidCooldownRow };
+import RaidEncounterRow from "./raid_encounter_table";
+export { RaidEncounterRow };
 import RaidInstanceRow from "./raid_instance_table";
 export { RaidInstanceRow };
+import RaidLockoutRow from "./raid_lockout_table";
+export { RaidLockoutRow };
+import RaidMechanicRow from "./raid_mechanic_table";
+export { RaidMechanicRow };
 import RaidParticipantRow from "./raid_participant_table";
 export { RaidParticipantRow };
 import RaidQueueRow from "./raid_queue_table";
 export { RaidQueueRow };
+import RaidResetScheduleRow from "./raid_reset_schedule_table";
+export { RaidResetScheduleRow };
+import RoomConnectionRow from "./room_connection_table";
+export { RoomConnectionRow };
+import RoomTileMapRow from "./room_tile_map_table";
+export { RoomTileMapRow };
+import StatusEffectRow from "./status_effect_table";
+export { StatusEffectRow };
+import SuspiciousActivityRow from "./suspicious_activity_table";
+export { SuspiciousActivityRow };
+import TelegraphZoneRow from "./telegraph_zone_table";
+export { TelegraphZoneRow };
 import ThreatEntryRow from "./threat_entry_table";
 export { ThreatEntryRow };
 
@@ -150,22 +208,56 @@ import ActiveDungeon from "./active_dungeon_type";
 export { ActiveDungeon };
 import ActiveHealingZone from "./active_healing_zone_type";
 export { ActiveHealingZone };
+import Admin from "./admin_type";
+export { Admin };
+import Affix from "./affix_type";
+export { Affix };
+import AiArchetype from "./ai_archetype_type";
+export { AiArchetype };
+import BossMechanicTimer from "./boss_mechanic_timer_type";
+export { BossMechanicTimer };
+import CardSocket from "./card_socket_type";
+export { CardSocket };
+import CombatEvent from "./combat_event_type";
+export { CombatEvent };
+import Combatant from "./combatant_type";
+export { Combatant };
 import DailyRaidClear from "./daily_raid_clear_type";
 export { DailyRaidClear };
+import DamageType from "./damage_type_type";
+export { DamageType };
+import DownedPlayer from "./downed_player_type";
+export { DownedPlayer };
 import DungeonEnemy from "./dungeon_enemy_type";
 export { DungeonEnemy };
 import DungeonParticipant from "./dungeon_participant_type";
 export { DungeonParticipant };
 import DungeonQueue from "./dungeon_queue_type";
 export { DungeonQueue };
+import DungeonRoom from "./dungeon_room_type";
+export { DungeonRoom };
+import EnemyDefinition from "./enemy_definition_type";
+export { EnemyDefinition };
 import EnemyTickSchedule from "./enemy_tick_schedule_type";
 export { EnemyTickSchedule };
+import HitOutcome from "./hit_outcome_type";
+export { HitOutcome };
 import InventoryItem from "./inventory_item_type";
 export { InventoryItem };
+import Item from "./item_type";
+export { Item };
+import ItemSlot from "./item_slot_type";
+export { ItemSlot };
 import LootDrop from "./loot_drop_type";
 export { LootDrop };
 import MatchmakingTickSchedule from "./matchmaking_tick_schedule_type";
 export { MatchmakingTickSchedule };
+import MechanicKind from "./mechanic_kind_type";
+export { MechanicKind };
+import MovementState from "./movement_state_type";
+export { MovementState };
+import OnHitStatus from "./on_hit_status_type";
+export { OnHitStatus };
 import OpenWorldEnemy from "./open_world_enemy_type";
 export { OpenWorldEnemy };
 import OpenWorldInstance from "./open_world_instance_type";
@@ -178,22 +270,60 @@ import Player from "./player_type";
 export { Player };
 import PlayerAbilityState from "./player_ability_state_type";
 export { PlayerAbilityState };
+import PlayerCard from "./player_card_type";
+export { PlayerCard };
 import PlayerGameMode from "./player_game_mode_type";
 export { PlayerGameMode };
 import PlayerMessage from "./player_message_type";
 export { PlayerMessage };
 import PlayerPosition from "./player_position_type";
 export { PlayerPosition };
+import Projectile from "./projectile_type";
+export { Projectile };
 import RaidCooldown from "./raid_cooldown_type";
 export { RaidCooldown };
+import RaidEncounter from "./raid_encounter_type";
+export { RaidEncounter };
 import RaidInstance from "./raid_instance_type";
 export { RaidInstance };
+import RaidLockout from "./raid_lockout_type";
+export { RaidLockout };
+import RaidMechanic from "./raid_mechanic_type";
+export { RaidMechanic };
 import RaidParticipant from "./raid_participant_type";
 export { RaidParticipant };
 import RaidQueue from "./raid_queue_type";
 export { RaidQueue };
+import RaidResetSchedule from "./raid_reset_schedule_type";
+export { RaidResetSchedule };
+import Rarity from "./rarity_type";
+export { Rarity };
+import Resistances from "./resistances_type";
+export { Resistances };
+import RoomConnection from "./room_connection_type";
+export { RoomConnection };
+import RoomKind from "./room_kind_type";
+export { RoomKind };
+import RoomTileMap from "./room_tile_map_type";
+export { RoomTileMap };
+import Side from "./side_type";
+export { Side };
+import StatKind from "./stat_kind_type";
+export { StatKind };
+import StatusEffect from "./status_effect_type";
+export { StatusEffect };
+import StatusKind from "./status_kind_type";
+export { StatusKind };
+import SuspiciousActivity from "./suspicious_activity_type";
+export { SuspiciousActivity };
+import TelegraphZone from "./telegraph_zone_type";
+export { TelegraphZone };
 import ThreatEntry from "./threat_entry_type";
 export { ThreatEntry };
+import Tile from "./tile_type";
+export { Tile };
+import ZoneShape from "./zone_shape_type";
+export { ZoneShape };
 
 /** The schema information for all tables in this module. This is defined the same was as the tables would have been defined in the server. */
 const tablesSchema = __schema(
@@ -219,6 +349,39 @@ const tablesSchema = __schema(
       { name: 'active_healing_zone_id_key', constraint: 'unique', columns: ['id'] },
     ],
   }, ActiveHealingZoneRow),
+  __table({
+    name: 'admin',
+    indexes: [
+      { name: 'identity', algorithm: 'btree', columns: [
+        'identity',
+      ] },
+    ],
+    constraints: [
+      { name: 'admin_identity_key', constraint: 'unique', columns: ['identity'] },
+    ],
+  }, AdminRow),
+  __table({
+    name: 'boss_mechanic_timer',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'boss_mechanic_timer_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, BossMechanicTimerRow),
+  __table({
+    name: 'combat_event',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'combat_event_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, CombatEventRow),
   __table({
     name: 'daily_raid_clear',
     indexes: [
@@ -230,6 +393,17 @@ const tablesSchema = __schema(
       { name: 'daily_raid_clear_identity_key', constraint: 'unique', columns: ['identity'] },
     ],
   }, DailyRaidClearRow),
+  __table({
+    name: 'downed_player',
+    indexes: [
+      { name: 'identity', algorithm: 'btree', columns: [
+        'identity',
+      ] },
+    ],
+    constraints: [
+      { name: 'downed_player_identity_key', constraint: 'unique', columns: ['identity'] },
+    ],
+  }, DownedPlayerRow),
   __table({
     name: 'dungeon_enemy',
     indexes: [
@@ -263,6 +437,28 @@ const tablesSchema = __schema(
       { name: 'dungeon_queue_identity_key', constraint: 'unique', columns: ['identity'] },
     ],
   }, DungeonQueueRow),
+  __table({
+    name: 'dungeon_room',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'dungeon_room_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, DungeonRoomRow),
+  __table({
+    name: 'enemy_definition',
+    indexes: [
+      { name: 'enemy_type', algorithm: 'btree', columns: [
+        'enemyType',
+      ] },
+    ],
+    constraints: [
+      { name: 'enemy_definition_enemy_type_key', constraint: 'unique', columns: ['enemyType'] },
+    ],
+  }, EnemyDefinitionRow),
   __table({
     name: 'enemy_tick_schedule',
     indexes: [
@@ -280,6 +476,9 @@ const tablesSchema = __schema(
       { name: 'id', algorithm: 'btree', columns: [
         'id',
       ] },
+      { name: 'owner_identity', algorithm: 'btree', columns: [
+        'ownerIdentity',
+      ] },
     ],
     constraints: [
       { name: 'inventory_item_id_key', constraint: 'unique', columns: ['id'] },
@@ -307,6 +506,17 @@ const tablesSchema = __schema(
       { name: 'matchmaking_tick_schedule_scheduled_id_key', constraint: 'unique', columns: ['scheduledId'] },
     ],
   }, MatchmakingTickScheduleRow),
+  __table({
+    name: 'movement_state',
+    indexes: [
+      { name: 'identity', algorithm: 'btree', columns: [
+        'identity',
+      ] },
+    ],
+    constraints: [
+      { name: 'movement_state_identity_key', constraint: 'unique', columns: ['identity'] },
+    ],
+  }, MovementStateRow),
   __table({
     name: 'open_world_enemy',
     indexes: [
@@ -373,6 +583,17 @@ const tablesSchema = __schema(
       { name: 'player_ability_state_identity_key', constraint: 'unique', columns: ['identity'] },
     ],
   }, PlayerAbilityStateRow),
+  __table({
+    name: 'player_card',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'player_card_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, PlayerCardRow),
   __table({
     name: 'player_game_mode',
     indexes: [
@@ -406,6 +627,17 @@ const tablesSchema = __schema(
       { name: 'player_position_identity_key', constraint: 'unique', columns: ['identity'] },
     ],
   }, PlayerPositionRow),
+  __table({
+    name: 'projectile',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'projectile_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, ProjectileRow),
   __table({
     name: 'raid_cooldown',
     indexes: [
@@ -417,6 +649,17 @@ const tablesSchema = __schema(
       { name: 'raid_cooldown_identity_key', constraint: 'unique', columns: ['identity'] },
     ],
   }, RaidCooldownRow),
+  __table({
+    name: 'raid_encounter',
+    indexes: [
+      { name: 'name', algorithm: 'btree', columns: [
+        'name',
+      ] },
+    ],
+    constraints: [
+      { name: 'raid_encounter_name_key', constraint: 'unique', columns: ['name'] },
+    ],
+  }, RaidEncounterRow),
   __table({
     name: 'raid_instance',
     indexes: [
@@ -428,6 +671,28 @@ const tablesSchema = __schema(
       { name: 'raid_instance_id_key', constraint: 'unique', columns: ['id'] },
     ],
   }, RaidInstanceRow),
+  __table({
+    name: 'raid_lockout',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'raid_lockout_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, RaidLockoutRow),
+  __table({
+    name: 'raid_mechanic',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'raid_mechanic_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, RaidMechanicRow),
   __table({
     name: 'raid_participant',
     indexes: [
@@ -450,6 +715,72 @@ const tablesSchema = __schema(
       { name: 'raid_queue_identity_key', constraint: 'unique', columns: ['identity'] },
     ],
   }, RaidQueueRow),
+  __table({
+    name: 'raid_reset_schedule',
+    indexes: [
+      { name: 'scheduled_id', algorithm: 'btree', columns: [
+        'scheduledId',
+      ] },
+    ],
+    constraints: [
+      { name: 'raid_reset_schedule_scheduled_id_key', constraint: 'unique', columns: ['scheduledId'] },
+    ],
+  }, RaidResetScheduleRow),
+  __table({
+    name: 'room_connection',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'room_connection_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, RoomConnectionRow),
+  __table({
+    name: 'room_tile_map',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'room_tile_map_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, RoomTileMapRow),
+  __table({
+    name: 'status_effect',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'status_effect_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, StatusEffectRow),
+  __table({
+    name: 'suspicious_activity',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'suspicious_activity_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, SuspiciousActivityRow),
+  __table({
+    name: 'telegraph_zone',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'telegraph_zone_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, TelegraphZoneRow),
   __table({
     name: 'threat_entry',
     indexes: [
@@ -470,26 +801,37 @@ const reducersSchema = __reducers(
   __reducerSchema("attack_open_world", AttackOpenWorldReducer),
   __reducerSchema("cancel_queue", CancelQueueReducer),
   __reducerSchema("complete_dungeon", CompleteDungeonReducer),
+  __reducerSchema("delete_enemy_definition", DeleteEnemyDefinitionReducer),
   __reducerSchema("discard_item", DiscardItemReducer),
   __reducerSchema("enter_open_world", EnterOpenWorldReducer),
   __reducerSchema("enter_room", EnterRoomReducer),
   __reducerSchema("equip_item", EquipItemReducer),
+  __reducerSchema("grant_admin", GrantAdminReducer),
   __reducerSchema("leave_open_world", LeaveOpenWorldReducer),
   __reducerSchema("login", LoginReducer),
+  __reducerSchema("migrate_item_json", MigrateItemJsonReducer),
   __reducerSchema("pickup_loot", PickupLootReducer),
   __reducerSchema("place_healing_zone", PlaceHealingZoneReducer),
   __reducerSchema("queue_dungeon", QueueDungeonReducer),
   __reducerSchema("queue_raid", QueueRaidReducer),
   __reducerSchema("register_player", RegisterPlayerReducer),
+  __reducerSchema("reset_raid_lockouts", ResetRaidLockoutsReducer),
+  __reducerSchema("revive_player", RevivePlayerReducer),
+  __reducerSchema("seed_enemy_definitions", SeedEnemyDefinitionsReducer),
   __reducerSchema("send_chat", SendChatReducer),
   __reducerSchema("send_emote", SendEmoteReducer),
+  __reducerSchema("set_enemy_combat_rolls", SetEnemyCombatRollsReducer),
+  __reducerSchema("set_enemy_definition", SetEnemyDefinitionReducer),
+  __reducerSchema("set_enemy_on_hit", SetEnemyOnHitReducer),
   __reducerSchema("set_game_mode", SetGameModeReducer),
+  __reducerSchema("socket_card", SocketCardReducer),
   __reducerSchema("start_dungeon", StartDungeonReducer),
   __reducerSchema("start_dungeon_solo", StartDungeonSoloReducer),
   __reducerSchema("tick_enemies", TickEnemiesReducer),
   __reducerSchema("tick_matchmaking", TickMatchmakingReducer),
   __reducerSchema("tick_open_world", TickOpenWorldReducer),
   __reducerSchema("unequip_item", UnequipItemReducer),
+  __reducerSchema("unsocket_card", UnsocketCardReducer),
   __reducerSchema("update_open_world_position", UpdateOpenWorldPositionReducer),
   __reducerSchema("update_position", UpdatePositionReducer),
   __reducerSchema("use_dash", UseDashReducer),
@@ -10,10 +10,15 @@ import {
   type Infer as __Infer,
 } from "spacetimedb";
 
+import Item from "./item_type";
+
 export default __t.row({
   id: __t.u64().primaryKey(),
   ownerIdentity: __t.identity().name("owner_identity"),
   itemDataJson: __t.string().name("item_data_json"),
   equippedSlot: __t.option(__t.string()).name("equipped_slot"),
   cardDataJson: __t.option(__t.string()).name("card_data_json"),
+  get item() {
+    return __t.option(Item);
+  },
 });
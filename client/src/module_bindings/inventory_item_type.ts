@@ -10,12 +10,17 @@ import {
   type Infer as __Infer,
 } from "spacetimedb";
 
+import Item from "./item_type";
+
 export default __t.object("InventoryItem", {
   id: __t.u64(),
   ownerIdentity: __t.identity(),
   itemDataJson: __t.string(),
   equippedSlot: __t.option(__t.string()),
   cardDataJson: __t.option(__t.string()),
+  get item() {
+    return __t.option(Item);
+  },
 });
 
 
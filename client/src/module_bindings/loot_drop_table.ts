@@ -10,6 +10,8 @@ import {
   type Infer as __Infer,
 } from "spacetimedb";
 
+import Item from "./item_type";
+
 export default __t.row({
   id: __t.u64().primaryKey(),
   dungeonId: __t.u64().name("dungeon_id"),
@@ -19,4 +21,7 @@ export default __t.row({
   itemDataJson: __t.string().name("item_data_json"),
   rarity: __t.string(),
   pickedUp: __t.bool().name("picked_up"),
+  get item() {
+    return __t.option(Item);
+  },
 });
@@ -10,6 +10,8 @@ import {
   type Infer as __Infer,
 } from "spacetimedb";
 
+import Item from "./item_type";
+
 export default __t.object("LootDrop", {
   id: __t.u64(),
   dungeonId: __t.u64(),
@@ -19,6 +21,9 @@ export default __t.object("LootDrop", {
   itemDataJson: __t.string(),
   rarity: __t.string(),
   pickedUp: __t.bool(),
+  get item() {
+    return __t.option(Item);
+  },
 });
 
 
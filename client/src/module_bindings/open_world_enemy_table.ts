@@ -29,4 +29,5 @@ export default __t.row({
   targetX: __t.f32().name("target_x"),
   targetY: __t.f32().name("target_y"),
   facingAngle: __t.f32().name("facing_angle"),
+  def: __t.i32(),
 });
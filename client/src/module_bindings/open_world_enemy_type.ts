@@ -29,6 +29,7 @@ export default __t.object("OpenWorldEnemy", {
   targetX: __t.f32(),
   targetY: __t.f32(),
   facingAngle: __t.f32(),
+  def: __t.i32(),
 });
 
 
@@ -14,4 +14,5 @@ export default __t.row({
   id: __t.u64().primaryKey(),
   createdAt: __t.u64().name("created_at"),
   playerCount: __t.u32().name("player_count"),
+  rngState: __t.u64().name("rng_state"),
 });
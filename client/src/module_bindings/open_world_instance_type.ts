@@ -14,6 +14,7 @@ export default __t.object("OpenWorldInstance", {
   id: __t.u64(),
   createdAt: __t.u64(),
   playerCount: __t.u32(),
+  rngState: __t.u64(),
 });
 
 
@@ -18,4 +18,6 @@ export default __t.row({
   healingZoneCd: __t.f32().name("healing_zone_cd"),
   dashCd: __t.f32().name("dash_cd"),
   postDashBonusTimer: __t.f32().name("post_dash_bonus_timer"),
+  iframeTimer: __t.f32().name("iframe_timer"),
+  nextAttackAt: __t.u64().name("next_attack_at"),
 });
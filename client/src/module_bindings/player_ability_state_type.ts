@@ -18,6 +18,8 @@ export default __t.object("PlayerAbilityState", {
   healingZoneCd: __t.f32(),
   dashCd: __t.f32(),
   postDashBonusTimer: __t.f32(),
+  iframeTimer: __t.f32(),
+  nextAttackAt: __t.u64(),
 });
 
 
@@ -23,4 +23,8 @@ export default __t.row({
   speed: __t.i32(),
   gold: __t.u64(),
   dungeonsCleared: __t.u32().name("dungeons_cleared"),
+  critChance: __t.i32().name("crit_chance"),
+  critDamage: __t.i32().name("crit_damage"),
+  dodge: __t.i32(),
+  block: __t.i32(),
 });
@@ -23,6 +23,10 @@ export default __t.object("Player", {
   speed: __t.i32(),
   gold: __t.u64(),
   dungeonsCleared: __t.u32(),
+  critChance: __t.i32(),
+  critDamage: __t.i32(),
+  dodge: __t.i32(),
+  block: __t.i32(),
 });
 
 
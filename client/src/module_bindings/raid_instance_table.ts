@@ -17,4 +17,6 @@ export default __t.row({
   bossMaxHp: __t.i32().name("boss_max_hp"),
   bossPhase: __t.u32().name("boss_phase"),
   wipeCount: __t.u32().name("wipe_count"),
+  endedAt: __t.u64().name("ended_at"),
+  bossIndex: __t.u32().name("boss_index"),
 });
@@ -17,6 +17,8 @@ export default __t.object("RaidInstance", {
   bossMaxHp: __t.i32(),
   bossPhase: __t.u32(),
   wipeCount: __t.u32(),
+  endedAt: __t.u64(),
+  bossIndex: __t.u32(),
 });
 
 
@@ -16,4 +16,5 @@ export default __t.row({
   playerIdentity: __t.identity().name("player_identity"),
   playerClass: __t.string().name("player_class"),
   disconnectedAt: __t.option(__t.u64()).name("disconnected_at"),
+  slot: __t.u32(),
 });
@@ -16,6 +16,7 @@ export default __t.object("RaidParticipant", {
   playerIdentity: __t.identity(),
   playerClass: __t.string(),
   disconnectedAt: __t.option(__t.u64()),
+  slot: __t.u32(),
 });
 
 
@@ -1,7 +1,29 @@
 // SpacetimeDB connection layer
-import { DbConnection, DbConnectionBuilder } from './module_bindings';
+import { DbConnection, DbConnectionBuilder, Item as ItemSchema, TelegraphZone as TelegraphZoneSchema, RoomTileMap as RoomTileMapSchema } from './module_bindings';
+import type { Infer } from 'spacetimedb';
 import type { ConnectionState } from './types';
 
+type Item = Infer<typeof ItemSchema>;
+type TelegraphZone = Infer<typeof TelegraphZoneSchema>;
+type RoomTileMap = Infer<typeof RoomTileMapSchema>;
+
+/** Room tile grid with tiles as tag names ('Floor', 'Wall', ...), row-major */
+export interface RoomLayout {
+  dungeonId: bigint;
+  roomIndex: number;
+  width: number;
+  height: number;
+  tiles: string[];
+}
+
+const mapRoomLayout = (row: RoomTileMap): RoomLayout => ({
+  dungeonId: row.dungeonId,
+  roomIndex: row.roomIndex,
+  width: row.width,
+  height: row.height,
+  tiles: row.tiles.map(t => t.tag),
+});
+
 // Use local server for development, maincloud for production
 const IS_LOCAL_DEV = window.location.hostname === 'localhost' || window.location.hostname === '127.0.0.1';
 const SPACETIMEDB_URI = IS_LOCAL_DEV ? 'ws://localhost:3000' : 'wss://maincloud.spacetimedb.com';
@@ -113,6 +135,8 @@ class SpacetimeClient {
           'SELECT * FROM threat_entry',
           'SELECT * FROM player_ability_state',
           'SELECT * FROM active_healing_zone',
+          'SELECT * FROM telegraph_zone',
+          'SELECT * FROM room_tile_map',
           // Game mode tables
           'SELECT * FROM player_game_mode',
           'SELECT * FROM open_world_instance',
@@ -162,9 +186,9 @@ class SpacetimeClient {
     this.conn.reducers.pickupLoot({ lootId });
   }
 
-  addInventoryItem(itemDataJson: string, rarity: string) {
+  addInventoryItem(itemDataJson: string) {
     if (!this.conn) return;
-    this.conn.reducers.addInventoryItem({ itemDataJson, rarity });
+    this.conn.reducers.addInventoryItem({ itemDataJson });
   }
 
   completeDungeon(dungeonId: bigint, clientGold?: bigint, clientXp?: bigint) {
@@ -351,17 +375,16 @@ class SpacetimeClient {
   }
 
   /** Read inventory items for current player */
-  getInventoryItems(): Array<{ itemDataJson: string; equippedSlot: string | null; cardDataJson: string | null }> {
+  getInventoryItems(): Array<{ item: Item | null; equippedSlot: string | null }> {
     if (!this.conn || !this._state.identity) return [];
     try {
-      const items: Array<{ itemDataJson: string; equippedSlot: string | null; cardDataJson: string | null }> = [];
+      const items: Array<{ item: Item | null; equippedSlot: string | null }> = [];
       const iter = (this.conn.db as any).inventoryItem.iter();
       for (const item of iter) {
         if (item.ownerIdentity.toHexString() === this._state.identity) {
           items.push({
-            itemDataJson: item.itemDataJson,
+            item: item.item ?? null,
             equippedSlot: item.equippedSlot ?? null,
-            cardDataJson: item.cardDataJson ?? null,
           });
         }
       }
@@ -515,7 +538,7 @@ class SpacetimeClient {
   }
 
   /** Listen for loot drop inserts and updates */
-  onLootDropChange(cb: (loot: { id: bigint, dungeonId: bigint, roomIndex: number, x: number, y: number, itemDataJson: string, rarity: string, pickedUp: boolean }) => void) {
+  onLootDropChange(cb: (loot: { id: bigint, dungeonId: bigint, roomIndex: number, x: number, y: number, item: Item | null, rarity: string, pickedUp: boolean }) => void) {
     if (!this.conn) return;
     try {
       const mapRow = (row: any) => ({
@@ -524,7 +547,7 @@ class SpacetimeClient {
         roomIndex: row.roomIndex,
         x: row.x,
         y: row.y,
-        itemDataJson: row.itemDataJson,
+        item: row.item ?? null,
         rarity: row.rarity,
         pickedUp: row.pickedUp,
       });
@@ -539,6 +562,72 @@ class SpacetimeClient {
     }
   }
 
+  /** Listen for telegraphed danger zones (enemy attacks the server resolves when they run out) */
+  onTelegraphZoneChange(cb: (zone: {
+    id: bigint,
+    dungeonId: bigint,
+    roomIndex: number,
+    shape: string,
+    x: number,
+    y: number,
+    radius: number,
+    angle: number,
+    arc: number,
+    width: number,
+    createdAt: number,
+    resolveAt: number,
+    shared: boolean,
+  }) => void, onDelete?: (id: bigint) => void) {
+    if (!this.conn) return;
+    const mapRow = (row: TelegraphZone) => ({
+      id: row.id,
+      dungeonId: row.dungeonId,
+      roomIndex: row.roomIndex,
+      shape: row.shape.tag,
+      x: row.x,
+      y: row.y,
+      radius: row.radius,
+      angle: row.angle,
+      arc: row.arc,
+      width: row.width,
+      createdAt: Number(row.createdAt),
+      resolveAt: Number(row.resolveAt),
+      shared: row.shared,
+    });
+    this.conn.db.telegraphZone.onInsert((_ctx, row) => {
+      cb(mapRow(row));
+    });
+    this.conn.db.telegraphZone.onUpdate((_ctx, _old, row) => {
+      cb(mapRow(row));
+    });
+    if (onDelete) {
+      this.conn.db.telegraphZone.onDelete((_ctx, row) => {
+        onDelete(row.id);
+      });
+    }
+  }
+
+  /** Listen for room tile layouts (generated when a room is first entered) */
+  onRoomTileMapChange(cb: (layout: RoomLayout) => void) {
+    if (!this.conn) return;
+    this.conn.db.roomTileMap.onInsert((_ctx, row) => {
+      cb(mapRoomLayout(row));
+    });
+    this.conn.db.roomTileMap.onUpdate((_ctx, _old, row) => {
+      cb(mapRoomLayout(row));
+    });
+  }
+
+  /** Read every room layout already generated for a dungeon */
+  getRoomTileMaps(dungeonId: bigint): RoomLayout[] {
+    if (!this.conn) return [];
+    const layouts: RoomLayout[] = [];
+    for (const row of this.conn.db.roomTileMap.iter()) {
+      if (row.dungeonId === dungeonId) layouts.push(mapRoomLayout(row));
+    }
+    return layouts;
+  }
+
   /** Listen for player stats updates (HP, XP, level changes from server) */
   onPlayerUpdate(cb: (player: { hp: number, maxHp: number, xp: number, level: number }) => void) {
     if (!this.conn || !this._state.identity) return;
@@ -73,8 +73,8 @@ export interface GameCallbacks {
   onAttack?: (targetEnemyId: number) => void;
   // Called by game when player dashes
   onDash?: (dirX: number, dirY: number) => void;
-  // Called by game when player picks up loot (passes serialized item JSON and rarity)
-  onPickupLoot?: (lootId: number, itemDataJson?: string, rarity?: string) => void;
+  // Called by game when player picks up loot (passes serialized item JSON, rarity and the server loot ID if any)
+  onPickupLoot?: (lootId: number, itemDataJson?: string, rarity?: string, serverLootId?: bigint) => void;
   // Called by game when entering a room
   onEnterRoom?: (roomIndex: number) => void;
   // Called by game when starting a dungeon
@@ -0,0 +1,268 @@
+//! Damage resolution shared by player attacks, enemy attacks, abilities and card procs:
+//! dodge, flat DEF mitigation, per-type resistances, blocks and crits.
+//! Every roll comes from the caller's RNG (the dungeon stream inside dungeons).
+
+use spacetimedb::{Identity, SpacetimeType};
+
+use crate::enemy_defs::Resistances;
+use crate::rng::Rng;
+
+/// New variants go at the end so stored rows keep their tags.
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DamageType {
+    Physical,
+    Fire,
+    Frost,
+    Poison,
+    Holy,
+}
+
+/// How a hit landed, for floating combat text.
+/// New variants go at the end so stored rows keep their tags.
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HitOutcome {
+    Hit,
+    Crit,
+    Dodge,
+    Block,
+}
+
+/// Something that deals or takes damage
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Combatant {
+    Player(Identity),
+    Enemy(u64),
+    OpenWorldEnemy(u64),
+}
+
+/// Timed effect kinds stored in the `status_effect` table.
+/// New variants go at the end so stored rows keep their tags.
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatusKind {
+    Stun,   // No actions
+    Slow,   // Magnitude: percent of movement speed removed
+    Burn,   // Magnitude: damage per second
+    Poison,
+    Bleed,
+    Shield, // Magnitude: damage left to absorb
+    Vulnerable, // Magnitude: percent extra damage taken (stacks from boss busters)
+}
+
+impl StatusKind {
+    /// Damage type dealt by damage-over-time effects
+    pub fn dot_damage_type(self) -> Option<DamageType> {
+        match self {
+            StatusKind::Burn => Some(DamageType::Fire),
+            StatusKind::Poison => Some(DamageType::Poison),
+            StatusKind::Bleed => Some(DamageType::Physical),
+            StatusKind::Stun | StatusKind::Slow | StatusKind::Shield | StatusKind::Vulnerable => None,
+        }
+    }
+}
+
+/// A status an attack can inflict on hit. For damage over time, `magnitude` is a
+/// percent of the triggering hit dealt per second; otherwise it is the raw magnitude.
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub struct OnHitStatus {
+    pub kind: StatusKind,
+    pub chance: f32,
+    pub magnitude: i32,
+    pub duration: f32,
+}
+
+impl OnHitStatus {
+    /// Stored magnitude for a hit that dealt `damage`
+    pub fn magnitude_for(&self, damage: i32) -> i32 {
+        if self.kind.dot_damage_type().is_some() {
+            (damage * self.magnitude / 100).max(1)
+        } else {
+            self.magnitude
+        }
+    }
+}
+
+/// Shape of a telegraphed danger zone.
+/// New variants go at the end so stored rows keep their tags.
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ZoneShape {
+    Circle, // `radius` around the origin
+    Cone,   // `radius` deep, `arc` radians either side of `angle`
+    Line,   // `radius` long toward `angle`, `width` wide
+}
+
+impl ZoneShape {
+    /// True if `point` is inside the zone; `pad` grows it by the target's hit radius
+    #[allow(clippy::too_many_arguments)]
+    pub fn contains(self, origin: (f32, f32), radius: f32, angle: f32, arc: f32, width: f32, point: (f32, f32), pad: f32) -> bool {
+        let (dx, dy) = (point.0 - origin.0, point.1 - origin.1);
+        let dist = (dx * dx + dy * dy).sqrt();
+        match self {
+            ZoneShape::Circle => dist <= radius + pad,
+            ZoneShape::Cone => dist <= radius + pad && (dist <= pad || angle_off_facing(angle, origin, point) <= arc),
+            ZoneShape::Line => {
+                let along = dx * angle.cos() + dy * angle.sin();
+                let across = (dy * angle.cos() - dx * angle.sin()).abs();
+                along >= -pad && along <= radius + pad && across <= width / 2.0 + pad
+            }
+        }
+    }
+}
+
+/// Resistances are capped so nothing becomes immune
+const MAX_RESISTANCE: i32 = 90;
+/// Half-angle of the cone a raised shield covers
+const SHIELD_BLOCK_ARC: f32 = std::f32::consts::PI / 3.0;
+/// Damage removed by a block (frontal shield or block roll)
+const SHIELD_BLOCK_PCT: i32 = 80;
+/// Dodge and block chances are capped so hits always have a chance to land
+const MAX_AVOIDANCE: i32 = 75;
+
+/// What the attacker brings to a hit
+pub struct Attacker {
+    pub x: f32,
+    pub y: f32,
+    pub crit_chance: i32, // Percent
+    pub crit_damage: i32, // Percent of normal damage on a crit
+}
+
+/// What the target brings to a hit
+pub struct Defender<'a> {
+    pub def: i32,
+    pub resistances: &'a Resistances,
+    pub x: f32,
+    pub y: f32,
+    pub facing_angle: f32,
+    pub shield_raised: bool, // Blocks hits from the front
+    pub dodge: i32,          // Percent
+    pub block: i32,          // Percent
+}
+
+pub struct HitResult {
+    pub amount: i32,
+    pub outcome: HitOutcome,
+}
+
+impl Resistances {
+    /// Percent reduction against a damage type
+    pub fn against(&self, damage_type: DamageType) -> i32 {
+        let pct = match damage_type {
+            DamageType::Physical => self.physical,
+            DamageType::Fire => self.fire,
+            DamageType::Frost => self.frost,
+            DamageType::Poison => self.poison,
+            DamageType::Holy => self.holy,
+        };
+        pct.min(MAX_RESISTANCE)
+    }
+}
+
+/// Angle (0..π) between where the target faces and the direction to the attacker.
+/// 0 means the attacker is straight in front, π means directly behind.
+pub fn angle_off_facing(facing_angle: f32, target: (f32, f32), attacker: (f32, f32)) -> f32 {
+    let attack_angle = (attacker.1 - target.1).atan2(attacker.0 - target.0);
+    let mut diff = (attack_angle - facing_angle).abs() % std::f32::consts::TAU;
+    if diff > std::f32::consts::PI {
+        diff = std::f32::consts::TAU - diff;
+    }
+    diff
+}
+
+/// Roll and resolve a hit: dodge, then DEF and resistance, then block or crit.
+/// Landed hits deal at least 1 damage; dodged hits deal none.
+pub fn resolve_damage(rng: &mut Rng, raw: i32, damage_type: DamageType, attacker: &Attacker, target: &Defender) -> HitResult {
+    if rng.chance(target.dodge.clamp(0, MAX_AVOIDANCE) as f32 / 100.0) {
+        return HitResult { amount: 0, outcome: HitOutcome::Dodge };
+    }
+
+    let mut damage = (raw - target.def / 2).max(1);
+    damage = damage * (100 - target.resistances.against(damage_type)) / 100;
+
+    let frontal = target.shield_raised
+        && angle_off_facing(target.facing_angle, (target.x, target.y), (attacker.x, attacker.y)) <= SHIELD_BLOCK_ARC;
+    let outcome = if frontal || rng.chance(target.block.clamp(0, MAX_AVOIDANCE) as f32 / 100.0) {
+        damage = damage * (100 - SHIELD_BLOCK_PCT) / 100;
+        HitOutcome::Block
+    } else if rng.chance(attacker.crit_chance.clamp(0, 100) as f32 / 100.0) {
+        damage = damage * attacker.crit_damage.max(100) / 100;
+        HitOutcome::Crit
+    } else {
+        HitOutcome::Hit
+    };
+    HitResult { amount: damage.max(1), outcome }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NO_RESIST: Resistances = Resistances { physical: 0, fire: 0, frost: 0, poison: 0, holy: 0 };
+
+    fn attacker(x: f32, crit_chance: i32) -> Attacker {
+        Attacker { x, y: 0.0, crit_chance, crit_damage: 200 }
+    }
+
+    fn defender(resistances: &Resistances) -> Defender<'_> {
+        Defender { def: 0, resistances, x: 0.0, y: 0.0, facing_angle: 0.0, shield_raised: false, dodge: 0, block: 0 }
+    }
+
+    #[test]
+    fn resistance_is_capped() {
+        let resistances = Resistances { fire: 150, ..NO_RESIST };
+        assert_eq!(resistances.against(DamageType::Fire), MAX_RESISTANCE);
+        let hit = resolve_damage(&mut Rng::new(1), 100, DamageType::Fire, &attacker(10.0, 0), &defender(&resistances));
+        assert_eq!(hit.amount, 100 * (100 - MAX_RESISTANCE) / 100);
+    }
+
+    #[test]
+    fn weaknesses_add_damage() {
+        let resistances = Resistances { holy: -25, ..NO_RESIST };
+        let hit = resolve_damage(&mut Rng::new(1), 100, DamageType::Holy, &attacker(10.0, 0), &defender(&resistances));
+        assert_eq!(hit.amount, 125);
+    }
+
+    #[test]
+    fn landed_hits_deal_at_least_one() {
+        let resistances = Resistances { physical: 90, ..NO_RESIST };
+        let target = Defender { def: 50, ..defender(&resistances) };
+        let hit = resolve_damage(&mut Rng::new(1), 1, DamageType::Physical, &attacker(10.0, 0), &target);
+        assert_eq!(hit.outcome, HitOutcome::Hit);
+        assert_eq!(hit.amount, 1);
+    }
+
+    #[test]
+    fn dodge_and_block_are_capped() {
+        let mut rng = Rng::new(9);
+        let dodgy = Defender { dodge: 100, ..defender(&NO_RESIST) };
+        let blocky = Defender { block: 100, ..defender(&NO_RESIST) };
+        let rolls = 2000;
+        let dodged = (0..rolls)
+            .filter(|_| resolve_damage(&mut rng, 10, DamageType::Physical, &attacker(10.0, 0), &dodgy).outcome == HitOutcome::Dodge)
+            .count();
+        let blocked = (0..rolls)
+            .filter(|_| resolve_damage(&mut rng, 10, DamageType::Physical, &attacker(10.0, 0), &blocky).outcome == HitOutcome::Block)
+            .count();
+        let cap = rolls * MAX_AVOIDANCE as usize / 100;
+        for count in [dodged, blocked] {
+            assert!(count < rolls, "avoidance was not capped");
+            assert!(count.abs_diff(cap) < rolls / 20, "{count} avoided, expected about {cap}");
+        }
+    }
+
+    #[test]
+    fn raised_shield_blocks_frontal_hits_only() {
+        let shield = Defender { shield_raised: true, ..defender(&NO_RESIST) };
+        let front = resolve_damage(&mut Rng::new(1), 100, DamageType::Physical, &attacker(10.0, 100), &shield);
+        assert_eq!(front.outcome, HitOutcome::Block);
+        assert_eq!(front.amount, 100 * (100 - SHIELD_BLOCK_PCT) / 100);
+
+        let behind = resolve_damage(&mut Rng::new(1), 100, DamageType::Physical, &attacker(-10.0, 100), &shield);
+        assert_eq!(behind.outcome, HitOutcome::Crit);
+        assert_eq!(behind.amount, 200);
+    }
+
+    #[test]
+    fn angle_off_facing_wraps() {
+        let angle = angle_off_facing(std::f32::consts::PI - 0.1, (0.0, 0.0), (-10.0, -0.5));
+        assert!(angle < 0.2);
+    }
+}
@@ -0,0 +1,222 @@
+//! Seeded dungeon room graphs: a main path from the start room to the boss room,
+//! with side branches that dead-end in treasure rooms and optional elite rooms.
+//! Room count and room composition scale with dungeon depth.
+
+use spacetimedb::SpacetimeType;
+
+use crate::rng::Rng;
+use crate::room_layout::Side;
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoomKind {
+    Start,
+    Combat,
+    Treasure,
+    Elite,
+    Boss,
+}
+
+/// Door between two rooms; `from_side` is the door in `from`, `to_side` the door in `to`
+pub struct RoomLink {
+    pub from: u32,
+    pub to: u32,
+    pub from_side: Side,
+    pub to_side: Side,
+}
+
+pub struct DungeonPlan {
+    pub rooms: Vec<RoomKind>,
+    pub links: Vec<RoomLink>,
+}
+
+/// Enemy types that can appear in combat rooms: (type, first depth, weight)
+const ENEMY_POOL: &[(&str, u32, f32)] = &[
+    ("slime", 1, 30.0),
+    ("skeleton", 1, 30.0),
+    ("bat", 1, 20.0),
+    ("archer", 2, 20.0),
+    ("charger", 2, 15.0),
+    ("wolf", 3, 15.0),
+    ("bomber", 3, 10.0),
+    ("necromancer", 4, 8.0),
+];
+
+const MAX_MAIN_PATH: u32 = 6;
+const MAX_BRANCHES: u32 = 3;
+const MAX_COMBAT_ENEMIES: u32 = 8;
+
+// ─── Graph ─────────────────────────────────────────────────────────────────────
+
+pub fn generate(rng: &mut Rng, depth: u32) -> DungeonPlan {
+    let depth = depth.max(1);
+    let mut rooms = vec![RoomKind::Start];
+    let mut links = Vec::new();
+
+    // Main path: start → combat rooms → boss, going north through each room
+    let main_len = (2 + depth / 2).min(MAX_MAIN_PATH);
+    let elite_chance = (0.1 + 0.05 * depth as f32).min(0.5);
+    for i in 1..=main_len {
+        // The first room is always a plain fight
+        let kind = if i > 1 && rng.chance(elite_chance) { RoomKind::Elite } else { RoomKind::Combat };
+        rooms.push(kind);
+        links.push(RoomLink { from: i - 1, to: i, from_side: Side::North, to_side: Side::South });
+    }
+    let boss = rooms.len() as u32;
+    rooms.push(RoomKind::Boss);
+    links.push(RoomLink { from: boss - 1, to: boss, from_side: Side::North, to_side: Side::South });
+
+    // Branches hang off the east/west doors of main path rooms
+    let mut free_sides: Vec<(u32, Side)> = (1..=main_len)
+        .flat_map(|i| [(i, Side::East), (i, Side::West)])
+        .collect();
+    let branches = (1 + depth / 3).min(MAX_BRANCHES);
+    for _ in 0..branches {
+        if free_sides.is_empty() {
+            break;
+        }
+        let (mut parent, side) = free_sides.swap_remove(rng.index(free_sides.len()));
+        let len = if depth >= 3 { rng.range_u32(1, 2) } else { 1 };
+        for step in 1..=len {
+            let room = rooms.len() as u32;
+            rooms.push(if step == len { RoomKind::Treasure } else { RoomKind::Combat });
+            links.push(RoomLink { from: parent, to: room, from_side: side, to_side: side.opposite() });
+            parent = room;
+        }
+    }
+
+    DungeonPlan { rooms, links }
+}
+
+// ─── Composition ───────────────────────────────────────────────────────────────
+
+/// Roll the enemy types for a room. Start and treasure rooms are empty.
+pub fn roll_room_enemies(rng: &mut Rng, kind: RoomKind, depth: u32) -> Vec<&'static str> {
+    let depth = depth.max(1);
+    match kind {
+        RoomKind::Start | RoomKind::Treasure => Vec::new(),
+        RoomKind::Boss => vec!["raid_boss"],
+        RoomKind::Combat => roll_pack(rng, depth, (3 + depth / 2).min(MAX_COMBAT_ENEMIES)),
+        RoomKind::Elite => {
+            // Shield knight mini-boss with a smaller escort
+            let mut enemies = vec!["shield_knight"];
+            enemies.extend(roll_pack(rng, depth, (2 + depth / 3).min(MAX_COMBAT_ENEMIES - 1)));
+            enemies
+        }
+    }
+}
+
+fn roll_pack(rng: &mut Rng, depth: u32, count: u32) -> Vec<&'static str> {
+    let pool: Vec<&(&str, u32, f32)> = ENEMY_POOL.iter().filter(|(_, min_depth, _)| depth >= *min_depth).collect();
+    let weights: Vec<f32> = pool.iter().map(|(_, _, w)| *w).collect();
+    let mut enemies = Vec::new();
+    while (enemies.len() as u32) < count {
+        let enemy_type = pool[rng.weighted_index(&weights)].0;
+        enemies.push(enemy_type);
+        // Wolves hunt in pairs
+        if enemy_type == "wolf" && (enemies.len() as u32) < count {
+            enemies.push("wolf");
+        }
+    }
+    enemies
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rooms reachable from the start room through the links
+    fn reachable(plan: &DungeonPlan) -> Vec<bool> {
+        let mut seen = vec![false; plan.rooms.len()];
+        let mut stack = vec![0u32];
+        seen[0] = true;
+        while let Some(room) = stack.pop() {
+            for link in &plan.links {
+                let next = if link.from == room { link.to } else if link.to == room { link.from } else { continue };
+                if !seen[next as usize] {
+                    seen[next as usize] = true;
+                    stack.push(next);
+                }
+            }
+        }
+        seen
+    }
+
+    #[test]
+    fn boss_room_exists_and_is_reachable() {
+        for depth in 1..=20 {
+            for seed in 0..50 {
+                let plan = generate(&mut Rng::new(seed), depth);
+                assert_eq!(plan.rooms[0], RoomKind::Start);
+                let bosses: Vec<usize> = (0..plan.rooms.len()).filter(|&i| plan.rooms[i] == RoomKind::Boss).collect();
+                assert_eq!(bosses.len(), 1, "depth {depth} seed {seed}");
+                assert!(reachable(&plan)[bosses[0]], "depth {depth} seed {seed}: boss cut off");
+            }
+        }
+    }
+
+    #[test]
+    fn every_room_is_connected() {
+        for depth in 1..=20 {
+            for seed in 0..50 {
+                let plan = generate(&mut Rng::new(seed), depth);
+                assert!(reachable(&plan).iter().all(|&r| r), "depth {depth} seed {seed}");
+                assert!(plan.links.iter().all(|l| (l.from as usize) < plan.rooms.len() && (l.to as usize) < plan.rooms.len()));
+            }
+        }
+    }
+
+    #[test]
+    fn doors_line_up_and_never_share_a_wall() {
+        for depth in 1..=20 {
+            for seed in 0..50 {
+                let plan = generate(&mut Rng::new(seed), depth);
+                let mut doors: Vec<(u32, Side)> = Vec::new();
+                for link in &plan.links {
+                    assert_eq!(link.to_side, link.from_side.opposite());
+                    doors.push((link.from, link.from_side));
+                    doors.push((link.to, link.to_side));
+                }
+                let count = doors.len();
+                doors.sort_by_key(|&(room, side)| (room, side as u8));
+                doors.dedup();
+                assert_eq!(doors.len(), count, "depth {depth} seed {seed}: two doors on one wall");
+            }
+        }
+    }
+
+    #[test]
+    fn treasure_rooms_are_dead_ends() {
+        for seed in 0..50 {
+            let plan = generate(&mut Rng::new(seed), 9);
+            for (i, kind) in plan.rooms.iter().enumerate() {
+                if *kind == RoomKind::Treasure {
+                    let degree = plan.links.iter().filter(|l| l.from == i as u32 || l.to == i as u32).count();
+                    assert_eq!(degree, 1);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn same_seed_gives_same_plan() {
+        let a = generate(&mut Rng::new(77), 6);
+        let b = generate(&mut Rng::new(77), 6);
+        assert_eq!(a.rooms, b.rooms);
+        let ends = |p: &DungeonPlan| p.links.iter().map(|l| (l.from, l.to)).collect::<Vec<_>>();
+        assert_eq!(ends(&a), ends(&b));
+    }
+
+    #[test]
+    fn room_enemies_follow_the_room_kind() {
+        let mut rng = Rng::new(3);
+        assert!(roll_room_enemies(&mut rng, RoomKind::Start, 5).is_empty());
+        assert!(roll_room_enemies(&mut rng, RoomKind::Treasure, 5).is_empty());
+        assert_eq!(roll_room_enemies(&mut rng, RoomKind::Boss, 5), vec!["raid_boss"]);
+        assert_eq!(roll_room_enemies(&mut rng, RoomKind::Elite, 5)[0], "shield_knight");
+        for _ in 0..50 {
+            let pack = roll_room_enemies(&mut rng, RoomKind::Combat, 1);
+            assert!(pack.len() as u32 <= MAX_COMBAT_ENEMIES);
+            assert!(pack.iter().all(|t| ["slime", "skeleton", "bat"].contains(t)));
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Built-in enemy definitions. They seed the `enemy_definition` table on init; after
+//! that the table is the source of truth and admins tune or add monsters at runtime.
+
+use spacetimedb::SpacetimeType;
+
+use crate::combat::{OnHitStatus, StatusKind};
+
+/// Behavior routine an enemy runs in `tick_enemies`.
+/// New variants go at the end so stored rows keep their tags.
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AiArchetype {
+    BasicMelee,
+    Charger,
+    Wolf,
+    Necromancer,
+    Bomber,
+    ShieldKnight,
+    Archer,
+    RaidBoss,
+}
+
+impl AiArchetype {
+    /// AI state an enemy of this archetype spawns in
+    pub fn initial_state(self) -> &'static str {
+        match self {
+            AiArchetype::Charger => "idle",
+            AiArchetype::Wolf => "orbit",
+            AiArchetype::Necromancer => "flee",
+            AiArchetype::ShieldKnight => "advance",
+            AiArchetype::Archer => "kite",
+            AiArchetype::BasicMelee | AiArchetype::Bomber | AiArchetype::RaidBoss => "chase",
+        }
+    }
+}
+
+/// Percent damage reduction per damage type (negative values are weaknesses)
+#[derive(SpacetimeType, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Resistances {
+    pub physical: i32,
+    pub fire: i32,
+    pub frost: i32,
+    pub poison: i32,
+    pub holy: i32,
+}
+
+/// Percent chances for the combat rolls in `combat::resolve_damage`
+#[derive(Clone, Copy)]
+pub struct CombatRolls {
+    pub crit_chance: i32,
+    pub crit_damage: i32,
+    pub dodge: i32,
+    pub block: i32,
+}
+
+/// Compile-time enemy stats; the loot table defaults to the enemy type
+pub struct EnemyTemplate {
+    pub enemy_type: &'static str,
+    pub hp: i32,
+    pub atk: i32,
+    pub def: i32,
+    pub speed: f32, // Multiple of ENEMY_MOVE_SPEED
+    pub xp: u64,
+    pub ai: AiArchetype,
+    pub resistances: Resistances,
+    pub rolls: CombatRolls,
+    pub on_hit: Option<OnHitStatus>, // Status the enemy's attacks can inflict
+}
+
+const NONE: Resistances = Resistances { physical: 0, fire: 0, frost: 0, poison: 0, holy: 0 };
+const UNDEAD: Resistances = Resistances { physical: 0, fire: 0, frost: 0, poison: 50, holy: -25 };
+
+const ROLLS: CombatRolls = CombatRolls { crit_chance: 5, crit_damage: 150, dodge: 0, block: 0 };
+
+pub const DEFAULTS: &[EnemyTemplate] = &[
+    EnemyTemplate { enemy_type: "skeleton", hp: 60, atk: 12, def: 3, speed: 1.0, xp: 15, ai: AiArchetype::BasicMelee, resistances: UNDEAD, rolls: ROLLS, on_hit: None },
+    EnemyTemplate { enemy_type: "slime", hp: 40, atk: 8, def: 0, speed: 1.0, xp: 10, ai: AiArchetype::BasicMelee, resistances: Resistances { poison: 50, frost: -25, ..NONE }, rolls: ROLLS, on_hit: Some(OnHitStatus { kind: StatusKind::Slow, chance: 0.25, magnitude: 30, duration: 1.5 }) },
+    EnemyTemplate { enemy_type: "bat", hp: 15, atk: 6, def: 0, speed: 1.5, xp: 8, ai: AiArchetype::BasicMelee, resistances: NONE, rolls: CombatRolls { dodge: 20, ..ROLLS }, on_hit: None },
+    EnemyTemplate { enemy_type: "charger", hp: 40, atk: 20, def: 4, speed: 2.5, xp: 25, ai: AiArchetype::Charger, resistances: Resistances { physical: 10, ..NONE }, rolls: CombatRolls { crit_chance: 15, ..ROLLS }, on_hit: None },
+    EnemyTemplate { enemy_type: "wolf", hp: 20, atk: 8, def: 1, speed: 1.8, xp: 12, ai: AiArchetype::Wolf, resistances: Resistances { frost: 25, fire: -15, ..NONE }, rolls: CombatRolls { dodge: 10, crit_chance: 10, ..ROLLS }, on_hit: Some(OnHitStatus { kind: StatusKind::Bleed, chance: 0.2, magnitude: 25, duration: 3.0 }) },
+    EnemyTemplate { enemy_type: "necromancer", hp: 60, atk: 5, def: 2, speed: 0.5, xp: 50, ai: AiArchetype::Necromancer, resistances: UNDEAD, rolls: CombatRolls { dodge: 5, ..ROLLS }, on_hit: None },
+    EnemyTemplate { enemy_type: "bomber", hp: 25, atk: 30, def: 0, speed: 0.8, xp: 20, ai: AiArchetype::Bomber, resistances: Resistances { fire: 50, frost: -25, ..NONE }, rolls: CombatRolls { crit_chance: 0, ..ROLLS }, on_hit: Some(OnHitStatus { kind: StatusKind::Burn, chance: 1.0, magnitude: 20, duration: 3.0 }) },
+    EnemyTemplate { enemy_type: "shield_knight", hp: 70, atk: 12, def: 8, speed: 0.7, xp: 35, ai: AiArchetype::ShieldKnight, resistances: Resistances { physical: 20, ..NONE }, rolls: CombatRolls { block: 15, ..ROLLS }, on_hit: None },
+    EnemyTemplate { enemy_type: "archer", hp: 35, atk: 10, def: 1, speed: 0.6, xp: 18, ai: AiArchetype::Archer, resistances: NONE, rolls: CombatRolls { crit_chance: 10, crit_damage: 175, dodge: 10, ..ROLLS }, on_hit: None },
+    EnemyTemplate { enemy_type: "boss", hp: 300, atk: 18, def: 6, speed: 1.0, xp: 100, ai: AiArchetype::BasicMelee, resistances: Resistances { physical: 10, ..NONE }, rolls: CombatRolls { crit_chance: 10, ..ROLLS }, on_hit: None },
+    EnemyTemplate { enemy_type: "bone_colossus", hp: 600, atk: 20, def: 12, speed: 0.8, xp: 200, ai: AiArchetype::RaidBoss, resistances: Resistances { physical: 20, holy: -15, ..UNDEAD }, rolls: CombatRolls { block: 10, ..ROLLS }, on_hit: None },
+    EnemyTemplate { enemy_type: "raid_boss", hp: 800, atk: 25, def: 10, speed: 1.0, xp: 250, ai: AiArchetype::RaidBoss, resistances: Resistances { physical: 10, fire: 10, frost: 10, poison: 10, holy: 10 }, rolls: CombatRolls { crit_chance: 10, crit_damage: 175, ..ROLLS }, on_hit: Some(OnHitStatus { kind: StatusKind::Burn, chance: 0.15, magnitude: 15, duration: 3.0 }) },
+];
+
+/// Stats used for enemy types with no definition row
+pub const FALLBACK: EnemyTemplate = EnemyTemplate {
+    enemy_type: "",
+    hp: 20,
+    atk: 5,
+    def: 0,
+    speed: 1.0,
+    xp: 10,
+    ai: AiArchetype::BasicMelee,
+    resistances: NONE,
+    rolls: ROLLS,
+    on_hit: None,
+};
+
+pub fn template(enemy_type: &str) -> &'static EnemyTemplate {
+    DEFAULTS.iter().find(|t| t.enemy_type == enemy_type).unwrap_or(&FALLBACK)
+}
@@ -0,0 +1,956 @@
+//! Server-side item generation: base types, rarity, prefix/suffix affixes and legendaries.
+//! Ported from the client loot roller so every inventory item is rolled by the server.
+
+use spacetimedb::SpacetimeType;
+
+use crate::combat::{DamageType, OnHitStatus, StatusKind};
+use crate::rng::Rng;
+
+// ─── Item Schema ───────────────────────────────────────────────────────────────
+
+/// Equipment slot an item fits into.
+/// New variants go at the end so stored rows keep their tags.
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ItemSlot {
+    Weapon,
+    Armor,
+    Accessory,
+    OffHand,
+}
+
+impl ItemSlot {
+    pub const ALL: [ItemSlot; 4] = [ItemSlot::Weapon, ItemSlot::OffHand, ItemSlot::Armor, ItemSlot::Accessory];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ItemSlot::Weapon => "weapon",
+            ItemSlot::Armor => "armor",
+            ItemSlot::Accessory => "accessory",
+            ItemSlot::OffHand => "offhand",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        ItemSlot::ALL.iter().copied().find(|slot| slot.as_str() == s)
+    }
+}
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rarity {
+    Common,
+    Uncommon,
+    Rare,
+    Epic,
+    Legendary,
+}
+
+impl Rarity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Rarity::Common => "common",
+            Rarity::Uncommon => "uncommon",
+            Rarity::Rare => "rare",
+            Rarity::Epic => "epic",
+            Rarity::Legendary => "legendary",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        RARITIES.iter().copied().find(|r| r.as_str() == s)
+    }
+
+    /// Number of affixes rolled for this rarity
+    fn affix_count(self, rng: &mut Rng) -> usize {
+        match self {
+            Rarity::Common => 0,
+            Rarity::Uncommon => 1,
+            Rarity::Rare => 2,
+            Rarity::Epic => 2 + rng.index(2), // 2-3
+            Rarity::Legendary => 3,
+        }
+    }
+
+    /// Most affixes `affix_count` can roll for this rarity
+    fn max_affixes(self) -> usize {
+        match self {
+            Rarity::Common => 0,
+            Rarity::Uncommon => 1,
+            Rarity::Rare => 2,
+            Rarity::Epic | Rarity::Legendary => 3,
+        }
+    }
+}
+
+/// Stat an item or affix modifies
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatKind {
+    Atk,
+    Def,
+    Hp,
+    Speed,
+    Lifesteal,
+    Reflect,
+    DropRate,
+    GoldBonus,
+    Crit,
+    AttackSpeed, // Percent faster swings
+}
+
+impl StatKind {
+    /// Parse the client's stat keys ("ATK", "HP", "lifesteal", ...)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "ATK" => Some(StatKind::Atk),
+            "DEF" => Some(StatKind::Def),
+            "HP" => Some(StatKind::Hp),
+            "Speed" => Some(StatKind::Speed),
+            "lifesteal" => Some(StatKind::Lifesteal),
+            "reflect" => Some(StatKind::Reflect),
+            "dropRate" => Some(StatKind::DropRate),
+            "goldBonus" => Some(StatKind::GoldBonus),
+            "crit" => Some(StatKind::Crit),
+            "attackSpeed" => Some(StatKind::AttackSpeed),
+            _ => None,
+        }
+    }
+}
+
+/// A rolled prefix or suffix
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct Affix {
+    pub name: String,
+    pub stat: StatKind,
+    pub value: i32,
+    pub is_percent: bool,
+    pub is_prefix: bool,
+}
+
+/// A card slotted into an item (Ragnarok-style)
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct CardSocket {
+    pub card_type: String, // Enemy type the card came from
+}
+
+/// A fully rolled item, stored as a column on inventory and loot rows
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct Item {
+    pub name: String,
+    pub icon: String,
+    pub base_type: String,       // Gear type ("Sword", "Robe", ...) or legendary name
+    pub slot: ItemSlot,
+    pub rarity: Rarity,
+    pub item_level: u32,
+    pub source: String,          // Enemy type that dropped it
+    pub atk: i32,
+    pub def: i32,
+    pub hp: i32,
+    pub speed: i32,
+    pub affixes: Vec<Affix>,
+    pub passive_id: Option<String>,
+    pub class_req: Option<String>,
+    pub card: Option<CardSocket>,
+}
+
+/// Summed core stat contributions of equipment (base stats, affixes and cards)
+#[derive(Clone, Copy, Default)]
+pub struct ItemStats {
+    pub atk: i32,
+    pub def: i32,
+    pub hp: i32,
+    pub speed: i32,
+    pub crit: i32,
+    pub attack_speed: i32,
+}
+
+impl ItemStats {
+    fn add(&mut self, stat: StatKind, value: i32) {
+        match stat {
+            StatKind::Atk => self.atk += value,
+            StatKind::Def => self.def += value,
+            StatKind::Hp => self.hp += value,
+            StatKind::Speed => self.speed += value,
+            StatKind::Crit => self.crit += value,
+            StatKind::AttackSpeed => self.attack_speed += value,
+            // Other secondary stats (lifesteal, ...) are not applied to the player row yet
+            _ => {}
+        }
+    }
+
+    pub fn merge(&mut self, other: &ItemStats) {
+        self.atk += other.atk;
+        self.def += other.def;
+        self.hp += other.hp;
+        self.speed += other.speed;
+        self.crit += other.crit;
+        self.attack_speed += other.attack_speed;
+    }
+}
+
+impl Item {
+    /// An item with no stats, affixes or card yet
+    fn new(name: &str, icon: &str, slot: ItemSlot, rarity: Rarity, item_level: u32, source: &str) -> Self {
+        Item {
+            name: name.to_string(),
+            icon: icon.to_string(),
+            base_type: name.to_string(),
+            slot,
+            rarity,
+            item_level,
+            source: source.to_string(),
+            atk: 0,
+            def: 0,
+            hp: 0,
+            speed: 0,
+            affixes: Vec::new(),
+            passive_id: None,
+            class_req: None,
+            card: None,
+        }
+    }
+
+    /// Two-handed weapons occupy the off-hand slot as well
+    pub fn is_two_handed(&self) -> bool {
+        self.slot == ItemSlot::Weapon && TWO_HANDED_TYPES.contains(&self.base_type.as_str())
+    }
+
+    /// Damage type dealt by a weapon (physical unless the weapon is elemental)
+    pub fn damage_type(&self) -> DamageType {
+        ELEMENTAL_WEAPONS.iter()
+            .find(|(name, _)| *name == self.base_type)
+            .map(|(_, t)| *t)
+            .unwrap_or(DamageType::Physical)
+    }
+
+    /// Status a weapon can inflict on hit
+    pub fn on_hit_status(&self) -> Option<OnHitStatus> {
+        if self.slot != ItemSlot::Weapon {
+            return None;
+        }
+        WEAPON_ON_HIT.iter()
+            .find(|(name, _)| *name == self.base_type)
+            .map(|(_, status)| *status)
+    }
+
+    /// Swing timing and cleave of a weapon
+    pub fn weapon_profile(&self) -> WeaponProfile {
+        if self.slot != ItemSlot::Weapon {
+            return UNARMED;
+        }
+        WEAPON_PROFILES.iter()
+            .find(|(name, _)| *name == self.base_type)
+            .map(|(_, profile)| *profile)
+            .unwrap_or(UNARMED)
+    }
+
+    /// Minimum player level needed to equip this item
+    pub fn level_req(&self) -> u32 {
+        if self.rarity == Rarity::Legendary {
+            self.item_level.max(1)
+        } else {
+            self.item_level.saturating_sub(LEVEL_REQ_GRACE).max(1)
+        }
+    }
+
+    /// Check class and level requirements for a player
+    pub fn check_requirements(&self, player_class: &str, player_level: u32) -> Result<(), String> {
+        if let Some(req) = &self.class_req {
+            if req != player_class {
+                return Err(format!("{} can only be equipped by the {} class", self.name, req));
+            }
+        }
+        let level_req = self.level_req();
+        if player_level < level_req {
+            return Err(format!("{} requires level {} (you are level {})", self.name, level_req, player_level));
+        }
+        Ok(())
+    }
+
+    /// Base value of a core stat on this item
+    pub fn base_stat(&self, stat: StatKind) -> i32 {
+        match stat {
+            StatKind::Atk => self.atk,
+            StatKind::Def => self.def,
+            StatKind::Hp => self.hp,
+            StatKind::Speed => self.speed,
+            _ => 0,
+        }
+    }
+
+    /// Total stats including affixes and the slotted card
+    pub fn computed_stats(&self) -> ItemStats {
+        let mut s = ItemStats {
+            atk: self.atk,
+            def: self.def,
+            hp: self.hp,
+            speed: self.speed,
+            crit: 0,
+            attack_speed: 0,
+        };
+
+        // Flat affixes add directly, percentage affixes scale the item's base stat
+        for a in &self.affixes {
+            if a.is_percent {
+                let base = self.base_stat(a.stat);
+                let bonus = if base > 0 { (base as f32 * a.value as f32 / 100.0).ceil() as i32 } else { a.value };
+                s.add(a.stat, bonus);
+            } else {
+                s.add(a.stat, a.value);
+            }
+        }
+
+        // Card bonuses are a percentage of the item's base stat (minimum base of 10)
+        if let Some(card) = &self.card {
+            for &(stat, pct) in card_stat_bonuses(&card.card_type) {
+                if stat == StatKind::Crit {
+                    s.add(stat, pct);
+                    continue;
+                }
+                let base = match self.base_stat(stat) { 0 => 10, b => b };
+                s.add(stat, (base as f32 * pct as f32 / 100.0).ceil() as i32);
+            }
+        }
+        s
+    }
+
+    fn add_base_stat(&mut self, stat: StatKind, value: i32) {
+        match stat {
+            StatKind::Atk => self.atk += value,
+            StatKind::Def => self.def += value,
+            StatKind::Hp => self.hp += value,
+            StatKind::Speed => self.speed += value,
+            _ => {}
+        }
+    }
+}
+
+// ─── Definitions ───────────────────────────────────────────────────────────────
+
+const RARITIES: [Rarity; 5] = [Rarity::Common, Rarity::Uncommon, Rarity::Rare, Rarity::Epic, Rarity::Legendary];
+
+/// Rarity weights by depth bracket (common, uncommon, rare, epic, legendary)
+const RARITY_WEIGHTS_BY_DEPTH: [[f32; 5]; 3] = [
+    [60.0, 30.0, 8.0, 1.5, 0.5],   // depth 1-2
+    [40.0, 35.0, 18.0, 5.0, 2.0],  // depth 3-5
+    [25.0, 30.0, 28.0, 12.0, 5.0], // depth 6+
+];
+
+/// Boss drops shift weight away from common/uncommon toward rare+
+const BOSS_RARITY_SHIFT: [f32; 5] = [0.3, 0.5, 1.5, 2.0, 2.5];
+
+/// Drop table slot order
+const SLOTS: [ItemSlot; 4] = ItemSlot::ALL;
+
+/// Weapon base types (and legendaries) that need both hands
+const TWO_HANDED_TYPES: &[&str] = &["Hammer", "Staff", "Mjolnir Shard", "Necro Lord Staff", "Staff of Renewal"];
+
+/// Weapon base types (and legendaries) that deal non-physical damage
+const ELEMENTAL_WEAPONS: &[(&str, DamageType)] = &[
+    ("Staff", DamageType::Frost),
+    ("Ragnarok Blade", DamageType::Fire),
+    ("Necro Lord Staff", DamageType::Poison),
+    ("Fenrir's Fang", DamageType::Frost),
+    ("Mjolnir Shard", DamageType::Holy),
+    ("Staff of Renewal", DamageType::Holy),
+];
+
+/// Weapon base types (and legendaries) that inflict a status on hit
+const WEAPON_ON_HIT: &[(&str, OnHitStatus)] = &[
+    ("Dagger", OnHitStatus { kind: StatusKind::Bleed, chance: 0.1, magnitude: 15, duration: 3.0 }),
+    ("Staff", OnHitStatus { kind: StatusKind::Slow, chance: 0.2, magnitude: 30, duration: 2.0 }),
+    ("Ragnarok Blade", OnHitStatus { kind: StatusKind::Burn, chance: 0.2, magnitude: 25, duration: 3.0 }),
+    ("Necro Lord Staff", OnHitStatus { kind: StatusKind::Poison, chance: 0.25, magnitude: 20, duration: 4.0 }),
+    ("Fenrir's Fang", OnHitStatus { kind: StatusKind::Bleed, chance: 0.2, magnitude: 20, duration: 3.0 }),
+    ("Mjolnir Shard", OnHitStatus { kind: StatusKind::Stun, chance: 0.1, magnitude: 0, duration: 0.6 }),
+];
+
+/// How a weapon swings: time between attacks and how many extra enemies a
+/// swing cleaves through
+#[derive(Clone, Copy)]
+pub struct WeaponProfile {
+    pub swing_time: f32,   // Seconds between attacks before attack speed
+    pub arc: f32,          // Half-angle of the cleave cone in radians (0 = single target)
+    pub cleave_targets: usize,
+    pub cleave_pct: i32,   // Damage to cleaved enemies, percent of the main hit
+}
+
+pub const UNARMED: WeaponProfile = WeaponProfile { swing_time: 0.5, arc: 0.0, cleave_targets: 0, cleave_pct: 0 };
+
+const SINGLE: WeaponProfile = UNARMED;
+const BLADE: WeaponProfile = WeaponProfile { swing_time: 0.5, arc: std::f32::consts::FRAC_PI_4, cleave_targets: 2, cleave_pct: 50 };
+const HEAVY: WeaponProfile = WeaponProfile { swing_time: 0.9, arc: std::f32::consts::FRAC_PI_2, cleave_targets: 4, cleave_pct: 70 };
+
+const WEAPON_PROFILES: &[(&str, WeaponProfile)] = &[
+    ("Sword", BLADE),
+    ("Axe", WeaponProfile { swing_time: 0.7, arc: std::f32::consts::FRAC_PI_3, cleave_targets: 3, cleave_pct: 60 }),
+    ("Dagger", WeaponProfile { swing_time: 0.3, ..SINGLE }),
+    ("Staff", WeaponProfile { swing_time: 0.6, ..SINGLE }),
+    ("Hammer", HEAVY),
+    ("Ragnarok Blade", BLADE),
+    ("Necro Lord Staff", WeaponProfile { swing_time: 0.6, ..SINGLE }),
+    ("Fenrir's Fang", WeaponProfile { swing_time: 0.3, ..SINGLE }),
+    ("Mjolnir Shard", HEAVY),
+    ("Gungnir Tip", WeaponProfile { swing_time: 0.45, ..SINGLE }),
+    ("Staff of Renewal", WeaponProfile { swing_time: 0.6, ..SINGLE }),
+];
+
+/// Non-legendary items can be worn this many levels below their item level
+const LEVEL_REQ_GRACE: u32 = 2;
+
+/// Base stat growth per item level and roll spread (min, width) for regular gear and legendaries
+const GEAR_GROWTH: f32 = 0.15;
+const GEAR_SPREAD: (f32, f32) = (0.8, 0.4);
+const LEGENDARY_GROWTH: f32 = 0.1;
+const LEGENDARY_SPREAD: (f32, f32) = (0.85, 0.3);
+
+/// Highest item level a legacy item is trusted with
+const LEGACY_MAX_ILVL: u32 = 50;
+
+struct GearType {
+    name: &'static str,
+    icon: &'static str,
+    slot: ItemSlot,
+    base: &'static [(StatKind, i32)],
+}
+
+const GEAR_TYPES: &[GearType] = &[
+    GearType { name: "Sword", icon: "⚔️", slot: ItemSlot::Weapon, base: &[(StatKind::Atk, 8)] },
+    GearType { name: "Axe", icon: "🪓", slot: ItemSlot::Weapon, base: &[(StatKind::Atk, 10)] },
+    GearType { name: "Dagger", icon: "🗡️", slot: ItemSlot::Weapon, base: &[(StatKind::Atk, 6), (StatKind::Speed, 5)] },
+    GearType { name: "Staff", icon: "🪄", slot: ItemSlot::Weapon, base: &[(StatKind::Atk, 5), (StatKind::Hp, 10)] },
+    GearType { name: "Hammer", icon: "🔨", slot: ItemSlot::Weapon, base: &[(StatKind::Atk, 12)] },
+    GearType { name: "Chestplate", icon: "🦺", slot: ItemSlot::Armor, base: &[(StatKind::Def, 8), (StatKind::Hp, 5)] },
+    GearType { name: "Helm", icon: "⛑️", slot: ItemSlot::Armor, base: &[(StatKind::Def, 4), (StatKind::Hp, 8)] },
+    GearType { name: "Robe", icon: "👘", slot: ItemSlot::Armor, base: &[(StatKind::Def, 3), (StatKind::Hp, 12)] },
+    GearType { name: "Ring", icon: "💍", slot: ItemSlot::Accessory, base: &[(StatKind::Atk, 3)] },
+    GearType { name: "Amulet", icon: "📿", slot: ItemSlot::Accessory, base: &[(StatKind::Hp, 15)] },
+    GearType { name: "Charm", icon: "🔮", slot: ItemSlot::Accessory, base: &[(StatKind::Def, 2), (StatKind::Atk, 2)] },
+    GearType { name: "Cape", icon: "🧣", slot: ItemSlot::Accessory, base: &[(StatKind::Speed, 8), (StatKind::Def, 2)] },
+    GearType { name: "Shield", icon: "🛡️", slot: ItemSlot::OffHand, base: &[(StatKind::Def, 6)] },
+    GearType { name: "Tome", icon: "📖", slot: ItemSlot::OffHand, base: &[(StatKind::Atk, 4), (StatKind::Hp, 6)] },
+];
+
+struct AffixDef {
+    name: &'static str,
+    stat: StatKind,
+    pct: bool,
+    base: i32,
+    scale: f32,
+}
+
+const PREFIXES: &[AffixDef] = &[
+    AffixDef { name: "Mighty", stat: StatKind::Atk, pct: false, base: 3, scale: 1.5 },
+    AffixDef { name: "Sturdy", stat: StatKind::Def, pct: false, base: 2, scale: 1.0 },
+    AffixDef { name: "Vital", stat: StatKind::Hp, pct: false, base: 8, scale: 4.0 },
+    AffixDef { name: "Swift", stat: StatKind::Speed, pct: false, base: 3, scale: 1.5 },
+    AffixDef { name: "Vampiric", stat: StatKind::Lifesteal, pct: true, base: 2, scale: 0.5 },
+    AffixDef { name: "Thorny", stat: StatKind::Reflect, pct: false, base: 2, scale: 1.0 },
+    AffixDef { name: "Lucky", stat: StatKind::DropRate, pct: true, base: 3, scale: 1.0 },
+];
+
+const SUFFIXES: &[AffixDef] = &[
+    AffixDef { name: "of Power", stat: StatKind::Atk, pct: true, base: 5, scale: 2.0 },
+    AffixDef { name: "of the Bear", stat: StatKind::Hp, pct: false, base: 10, scale: 5.0 },
+    AffixDef { name: "of Haste", stat: StatKind::Speed, pct: true, base: 5, scale: 2.0 },
+    AffixDef { name: "of the Leech", stat: StatKind::Lifesteal, pct: false, base: 1, scale: 0.5 },
+    AffixDef { name: "of Fortune", stat: StatKind::GoldBonus, pct: true, base: 5, scale: 2.0 },
+    AffixDef { name: "of Destruction", stat: StatKind::Crit, pct: true, base: 3, scale: 1.5 },
+    AffixDef { name: "of Protection", stat: StatKind::Def, pct: true, base: 5, scale: 2.0 },
+    AffixDef { name: "of Alacrity", stat: StatKind::AttackSpeed, pct: true, base: 4, scale: 1.0 },
+];
+
+struct LegendaryDef {
+    name: &'static str,
+    icon: &'static str,
+    slot: ItemSlot,
+    base: &'static [(StatKind, i32)],
+    passive_id: &'static str,
+    class_req: Option<&'static str>,
+}
+
+const LEGENDARY_ITEMS: &[LegendaryDef] = &[
+    LegendaryDef { name: "Ragnarok Blade", icon: "⚔️", slot: ItemSlot::Weapon, base: &[(StatKind::Atk, 30)], passive_id: "ragnarokBlade", class_req: None },
+    LegendaryDef { name: "Yggdrasil Leaf", icon: "🍃", slot: ItemSlot::Accessory, base: &[(StatKind::Hp, 40)], passive_id: "yggdrasilLeaf", class_req: None },
+    LegendaryDef { name: "Slime King's Crown", icon: "👑", slot: ItemSlot::Armor, base: &[(StatKind::Def, 12), (StatKind::Hp, 20)], passive_id: "slimeKingCrown", class_req: None },
+    LegendaryDef { name: "Bomber's Last Gift", icon: "💣", slot: ItemSlot::Accessory, base: &[(StatKind::Atk, 10)], passive_id: "bombersGift", class_req: None },
+    LegendaryDef { name: "Necro Lord Staff", icon: "🪄", slot: ItemSlot::Weapon, base: &[(StatKind::Atk, 18), (StatKind::Hp, 15)], passive_id: "necroStaff", class_req: None },
+    LegendaryDef { name: "Fenrir's Fang", icon: "🐺", slot: ItemSlot::Weapon, base: &[(StatKind::Atk, 20), (StatKind::Speed, 10)], passive_id: "fenrirFang", class_req: None },
+    LegendaryDef { name: "Odin's Eye", icon: "👁️", slot: ItemSlot::Accessory, base: &[(StatKind::Atk, 8)], passive_id: "odinsEye", class_req: None },
+    LegendaryDef { name: "Mjolnir Shard", icon: "🔨", slot: ItemSlot::Weapon, base: &[(StatKind::Atk, 25)], passive_id: "mjolnirShard", class_req: None },
+    LegendaryDef { name: "Valkyrie Aegis", icon: "🛡️", slot: ItemSlot::OffHand, base: &[(StatKind::Def, 18), (StatKind::Hp, 15)], passive_id: "valkyrieAegis", class_req: None },
+    LegendaryDef { name: "Loki's Trinket", icon: "🔮", slot: ItemSlot::Accessory, base: &[(StatKind::Speed, 12)], passive_id: "lokiTrinket", class_req: None },
+    LegendaryDef { name: "Hel's Embrace", icon: "👘", slot: ItemSlot::Armor, base: &[(StatKind::Hp, 50)], passive_id: "helsEmbrace", class_req: None },
+    LegendaryDef { name: "Gungnir Tip", icon: "🗡️", slot: ItemSlot::Weapon, base: &[(StatKind::Atk, 22)], passive_id: "gungnirTip", class_req: None },
+    LegendaryDef { name: "Bifrost Ring", icon: "💍", slot: ItemSlot::Accessory, base: &[(StatKind::Speed, 8), (StatKind::Def, 5)], passive_id: "bifrostRing", class_req: None },
+    LegendaryDef { name: "Surtr's Ember", icon: "🔥", slot: ItemSlot::Accessory, base: &[(StatKind::Atk, 15)], passive_id: "surtrEmber", class_req: None },
+    LegendaryDef { name: "Freya's Blessing", icon: "📿", slot: ItemSlot::Accessory, base: &[(StatKind::Hp, 25), (StatKind::Def, 8)], passive_id: "freyaBlessing", class_req: None },
+    // Class-specific legendaries
+    LegendaryDef { name: "Taunt Gauntlets", icon: "🧤", slot: ItemSlot::Armor, base: &[(StatKind::Def, 20), (StatKind::Hp, 30)], passive_id: "tauntGauntlets", class_req: Some("tank") },
+    LegendaryDef { name: "Aegis of Defiance", icon: "🔰", slot: ItemSlot::Accessory, base: &[(StatKind::Def, 15), (StatKind::Hp, 25)], passive_id: "aegisDefiance", class_req: Some("tank") },
+    LegendaryDef { name: "Healing Vestments", icon: "🥋", slot: ItemSlot::Armor, base: &[(StatKind::Hp, 40), (StatKind::Def, 10)], passive_id: "healingVestments", class_req: Some("healer") },
+    LegendaryDef { name: "Staff of Renewal", icon: "✨", slot: ItemSlot::Weapon, base: &[(StatKind::Atk, 12), (StatKind::Hp, 20)], passive_id: "staffRenewal", class_req: Some("healer") },
+    LegendaryDef { name: "Assassin's Cloak", icon: "🦇", slot: ItemSlot::Armor, base: &[(StatKind::Atk, 15), (StatKind::Speed, 10)], passive_id: "assassinCloak", class_req: Some("dps") },
+    LegendaryDef { name: "Shadowstep Boots", icon: "👢", slot: ItemSlot::Accessory, base: &[(StatKind::Speed, 15), (StatKind::Atk, 8)], passive_id: "shadowstepBoots", class_req: Some("dps") },
+];
+
+/// Stat bonuses granted by a card, keyed by the enemy type it drops from.
+/// Values are percent of the item's base stat, except crit which is flat.
+pub fn card_stat_bonuses(card_type: &str) -> &'static [(StatKind, i32)] {
+    match card_type {
+        "slime" => &[(StatKind::Hp, 5)],
+        "skeleton" => &[(StatKind::Atk, 10)],
+        "wolf" => &[(StatKind::Speed, 10)],
+        "archer" => &[(StatKind::Crit, 5)],
+        "charger" => &[(StatKind::Def, 8)],
+        "bomber" => &[(StatKind::Atk, 5)],
+        "necromancer" => &[(StatKind::Hp, 8), (StatKind::Atk, 3)],
+        "shield_knight" => &[(StatKind::Def, 12)],
+        "boss" | "raid_boss" | "bone_colossus" => &[(StatKind::Atk, 8), (StatKind::Hp, 5), (StatKind::Def, 5)],
+        _ => &[],
+    }
+}
+
+/// On-hit effect a socketed card can trigger
+#[derive(Clone, Copy)]
+pub enum CardProc {
+    /// Splash a percentage of the hit to other enemies within `radius`
+    Explode { chance: f32, pct: i32, radius: f32 },
+    /// Heal the attacker for a percentage of the hit
+    Drain { chance: f32, pct: i32 },
+    /// Inflict a status effect on the target
+    Inflict(OnHitStatus),
+}
+
+/// Combat effects of a card: bonus damage vs its own enemy type plus an optional proc
+pub struct CardEffect {
+    pub bonus_vs: &'static [&'static str],
+    pub bonus_pct: i32,
+    pub proc_effect: Option<CardProc>,
+}
+
+pub fn card_effect(card_type: &str) -> CardEffect {
+    let (bonus_vs, bonus_pct, proc_effect): (&'static [&'static str], i32, Option<CardProc>) = match card_type {
+        "slime" => (&["slime"], 15, Some(CardProc::Inflict(OnHitStatus { kind: StatusKind::Slow, chance: 0.15, magnitude: 30, duration: 2.0 }))),
+        "skeleton" => (&["skeleton"], 15, None),
+        "wolf" => (&["wolf"], 15, Some(CardProc::Inflict(OnHitStatus { kind: StatusKind::Bleed, chance: 0.1, magnitude: 20, duration: 3.0 }))),
+        "archer" => (&["archer"], 15, None),
+        "charger" => (&["charger"], 15, Some(CardProc::Inflict(OnHitStatus { kind: StatusKind::Stun, chance: 0.05, magnitude: 0, duration: 0.8 }))),
+        "bomber" => (&["bomber"], 15, Some(CardProc::Explode { chance: 0.1, pct: 50, radius: 60.0 })),
+        "necromancer" => (&["necromancer"], 15, Some(CardProc::Drain { chance: 0.15, pct: 20 })),
+        "shield_knight" => (&["shield_knight"], 15, None),
+        "boss" | "raid_boss" | "bone_colossus" => (&["boss", "raid_boss", "bone_colossus"], 20, None),
+        _ => (&[], 0, None),
+    };
+    CardEffect { bonus_vs, bonus_pct, proc_effect }
+}
+
+/// Chance that an enemy type drops its card
+pub fn card_drop_chance(enemy_type: &str) -> f32 {
+    match enemy_type {
+        "archer" | "bomber" => 0.015,
+        "necromancer" => 0.03,
+        "boss" | "raid_boss" | "bone_colossus" => 0.05,
+        "slime" | "skeleton" | "wolf" | "charger" | "shield_knight" => 0.02,
+        _ => 0.0,
+    }
+}
+
+/// Per-enemy drop table: chance to drop gear, a weight per slot (in `SLOTS` order), drop count
+pub struct DropTable {
+    pub gear_chance: f32,
+    pub slot_weights: [f32; 4], // Weapon, off-hand, armor, accessory
+    pub num_drops: (u32, u32),
+}
+
+pub fn get_drop_table(enemy_type: &str) -> DropTable {
+    let (gear_chance, slot_weights, num_drops) = match enemy_type {
+        "skeleton" => (0.3, [50.0, 15.0, 40.0, 10.0], (1, 1)),
+        "wolf" => (0.25, [20.0, 5.0, 40.0, 40.0], (1, 1)),
+        "archer" => (0.25, [40.0, 5.0, 20.0, 40.0], (1, 1)),
+        "charger" => (0.35, [50.0, 10.0, 30.0, 20.0], (1, 1)),
+        "bomber" => (0.3, [20.0, 5.0, 20.0, 60.0], (1, 1)),
+        "necromancer" => (0.45, [50.0, 25.0, 30.0, 20.0], (1, 1)),
+        "shield_knight" => (0.4, [20.0, 40.0, 40.0, 20.0], (1, 1)),
+        "boss" | "raid_boss" | "bone_colossus" => (1.0, [35.0, 20.0, 35.0, 30.0], (2, 4)),
+        "treasure" => (1.0, [35.0, 20.0, 35.0, 30.0], (1, 2)), // Treasure room chest
+        _ => (0.2, [20.0, 10.0, 50.0, 30.0], (1, 1)), // slime, bat
+    };
+    DropTable { gear_chance, slot_weights, num_drops }
+}
+
+// ─── Rolling ───────────────────────────────────────────────────────────────────
+
+fn pick_rarity(rng: &mut Rng, depth: u32, is_boss: bool) -> Rarity {
+    let bracket = if depth <= 2 { 0 } else if depth <= 5 { 1 } else { 2 };
+    let mut weights = RARITY_WEIGHTS_BY_DEPTH[bracket];
+    if is_boss {
+        for (w, shift) in weights.iter_mut().zip(BOSS_RARITY_SHIFT) {
+            *w *= shift;
+        }
+    }
+    RARITIES[rng.weighted_index(&weights)]
+}
+
+fn roll_affix_value(rng: &mut Rng, affix: &AffixDef, ilvl: u32) -> i32 {
+    let bonus = (rng.next_f32() * affix.scale * ilvl as f32) as i32;
+    (affix.base + bonus).max(1)
+}
+
+fn roll_affixes(rng: &mut Rng, rarity: Rarity, ilvl: u32) -> Vec<Affix> {
+    let count = rarity.affix_count(rng);
+    let mut affixes: Vec<Affix> = Vec::new();
+    let mut has_prefix = false;
+
+    for _ in 0..count {
+        // Alternate prefix/suffix, but allow doubles
+        let want_prefix = !has_prefix || rng.chance(0.5);
+        let pool = if want_prefix { PREFIXES } else { SUFFIXES };
+        let unused: Vec<&AffixDef> = pool.iter()
+            .filter(|d| !affixes.iter().any(|a| a.name == d.name))
+            .collect();
+        if unused.is_empty() {
+            continue;
+        }
+        let def = unused[rng.index(unused.len())];
+        affixes.push(Affix {
+            name: def.name.to_string(),
+            stat: def.stat,
+            value: roll_affix_value(rng, def, ilvl),
+            is_percent: def.pct,
+            is_prefix: want_prefix,
+        });
+        has_prefix |= want_prefix;
+    }
+    affixes
+}
+
+/// Start an item with rolled base stats
+#[allow(clippy::too_many_arguments)]
+fn roll_base_item(
+    rng: &mut Rng,
+    name: &str,
+    icon: &str,
+    slot: ItemSlot,
+    rarity: Rarity,
+    ilvl: u32,
+    source: &str,
+    base: &[(StatKind, i32)],
+    growth: f32,
+    spread: (f32, f32),
+) -> Item {
+    let mut item = Item::new(name, icon, slot, rarity, ilvl, source);
+    for &(stat, v) in base {
+        let roll = spread.0 + rng.next_f32() * spread.1;
+        item.add_base_stat(stat, scale_base_stat(v, ilvl, growth, roll));
+    }
+    item
+}
+
+fn scale_base_stat(v: i32, ilvl: u32, growth: f32, roll: f32) -> i32 {
+    (v as f32 * (1.0 + ilvl as f32 * growth) * roll).ceil() as i32
+}
+
+/// Highest base stat the generator can roll for a slot at an item level
+fn max_base_stat(slot: ItemSlot, ilvl: u32, stat: StatKind) -> i32 {
+    let gear = GEAR_TYPES.iter()
+        .filter(|g| g.slot == slot)
+        .flat_map(|g| g.base)
+        .filter(|(s, _)| *s == stat)
+        .map(|&(_, v)| scale_base_stat(v, ilvl, GEAR_GROWTH, GEAR_SPREAD.0 + GEAR_SPREAD.1));
+    let legendary = LEGENDARY_ITEMS.iter()
+        .filter(|l| l.slot == slot)
+        .flat_map(|l| l.base)
+        .filter(|(s, _)| *s == stat)
+        .map(|&(_, v)| scale_base_stat(v, ilvl, LEGENDARY_GROWTH, LEGENDARY_SPREAD.0 + LEGENDARY_SPREAD.1));
+    gear.chain(legendary).max().unwrap_or(0)
+}
+
+/// Highest value the generator can roll for an affix at an item level, or None if no
+/// affix rolls that stat that way
+fn max_affix_value(stat: StatKind, is_percent: bool, ilvl: u32) -> Option<i32> {
+    PREFIXES.iter()
+        .chain(SUFFIXES)
+        .filter(|d| d.stat == stat && d.pct == is_percent)
+        .map(|d| (d.base + (d.scale * ilvl as f32) as i32).max(1))
+        .max()
+}
+
+/// Pull an item from outside the generator back into what the generator could have
+/// rolled for its slot and level
+fn clamp_to_generator(item: &mut Item) {
+    item.item_level = item.item_level.clamp(1, LEGACY_MAX_ILVL);
+    let (slot, ilvl) = (item.slot, item.item_level);
+    item.atk = item.atk.clamp(0, max_base_stat(slot, ilvl, StatKind::Atk));
+    item.def = item.def.clamp(0, max_base_stat(slot, ilvl, StatKind::Def));
+    item.hp = item.hp.clamp(0, max_base_stat(slot, ilvl, StatKind::Hp));
+    item.speed = item.speed.clamp(0, max_base_stat(slot, ilvl, StatKind::Speed));
+
+    item.affixes.retain_mut(|a| match max_affix_value(a.stat, a.is_percent, ilvl) {
+        Some(max) => {
+            a.value = a.value.clamp(1, max);
+            true
+        }
+        None => false,
+    });
+    item.affixes.truncate(item.rarity.max_affixes());
+}
+
+/// Pick a slot from a drop table's weights
+pub fn pick_slot(rng: &mut Rng, table: &DropTable) -> ItemSlot {
+    SLOTS[rng.weighted_index(&table.slot_weights)]
+}
+
+/// Generate a legendary for a slot. `class_hint` biases toward class-specific items.
+pub fn generate_legendary(rng: &mut Rng, slot: ItemSlot, ilvl: u32, class_hint: Option<&str>, source: &str) -> Item {
+    let mut candidates: Vec<&LegendaryDef> = LEGENDARY_ITEMS.iter()
+        .filter(|l| l.slot == slot && (l.class_req.is_none() || l.class_req == class_hint))
+        .collect();
+
+    // 50% chance to prefer class-specific items when a class is known
+    if class_hint.is_some() && rng.chance(0.5) {
+        let class_items: Vec<&LegendaryDef> = candidates.iter()
+            .copied()
+            .filter(|l| l.class_req.is_some())
+            .collect();
+        if !class_items.is_empty() {
+            candidates = class_items;
+        }
+    }
+
+    let leg = candidates[rng.index(candidates.len())];
+    let mut item = roll_base_item(rng, leg.name, leg.icon, slot, Rarity::Legendary, ilvl, source, leg.base, LEGENDARY_GROWTH, LEGENDARY_SPREAD);
+    item.affixes = roll_affixes(rng, Rarity::Legendary, ilvl);
+    item.passive_id = Some(leg.passive_id.to_string());
+    item.class_req = leg.class_req.map(str::to_string);
+    item
+}
+
+/// Generate a piece of gear for a slot at the given item level
+pub fn generate_gear(rng: &mut Rng, slot: ItemSlot, ilvl: u32, is_boss: bool, class_hint: Option<&str>, source: &str) -> Item {
+    let ilvl = ilvl.max(1);
+    let rarity = pick_rarity(rng, ilvl, is_boss);
+    if rarity == Rarity::Legendary {
+        return generate_legendary(rng, slot, ilvl, class_hint, source);
+    }
+
+    let types: Vec<&GearType> = GEAR_TYPES.iter().filter(|t| t.slot == slot).collect();
+    let gear = types[rng.index(types.len())];
+    let mut item = roll_base_item(rng, gear.name, gear.icon, slot, rarity, ilvl, source, gear.base, GEAR_GROWTH, GEAR_SPREAD);
+    item.affixes = roll_affixes(rng, rarity, ilvl);
+
+    // Build name from first prefix/suffix
+    if let Some(p) = item.affixes.iter().find(|a| a.is_prefix) {
+        item.name = format!("{} {}", p.name, item.name);
+    }
+    if let Some(s) = item.affixes.iter().find(|a| !a.is_prefix) {
+        item.name = format!("{} {}", item.name, s.name);
+    }
+    item
+}
+
+/// Roll the drops for a killed enemy (may be empty)
+pub fn roll_enemy_drops(rng: &mut Rng, enemy_type: &str, depth: u32, class_hint: Option<&str>) -> Vec<Item> {
+    let table = get_drop_table(enemy_type);
+    if !rng.chance(table.gear_chance) {
+        return Vec::new();
+    }
+
+    // Mini-bosses roll on the boss rarity table too
+    let is_boss = matches!(enemy_type, "boss" | "raid_boss" | "bone_colossus" | "shield_knight");
+    let count = rng.range_u32(table.num_drops.0, table.num_drops.1);
+    (0..count)
+        .map(|_| {
+            let slot = pick_slot(rng, &table);
+            generate_gear(rng, slot, depth, is_boss, class_hint, enemy_type)
+        })
+        .collect()
+}
+
+// ─── Legacy JSON ───────────────────────────────────────────────────────────────
+
+/// Parse a card from a legacy `card_data_json` value: either a JSON object with a
+/// `type`/`cardType` key, a JSON string, or a bare enemy type.
+pub fn parse_legacy_card_json(json: &str) -> Option<CardSocket> {
+    let card_type = match serde_json::from_str::<serde_json::Value>(json) {
+        Ok(serde_json::Value::String(s)) => s,
+        Ok(serde_json::Value::Object(obj)) => obj.get("type")
+            .or_else(|| obj.get("cardType"))
+            .and_then(|v| v.as_str())?
+            .to_string(),
+        Ok(_) => return None,
+        Err(_) => json.trim().to_string(),
+    };
+    if card_type.is_empty() {
+        return None;
+    }
+    Some(CardSocket { card_type })
+}
+
+/// Parse a legacy `item_data_json` string into a typed item.
+/// Understands both the client item model and the old server drop format
+/// (`{"type":"drop","source":..,"atk_bonus":..,"def_bonus":..}`).
+/// Stats are clamped to what the generator rolls for the item's slot and level.
+pub fn parse_legacy_item_json(json: &str) -> Option<Item> {
+    let value: serde_json::Value = serde_json::from_str(json).ok()?;
+    let obj = value.as_object()?;
+    let get_str = |key: &str| obj.get(key).and_then(|v| v.as_str());
+    let get_i32 = |key: &str| obj.get(key).and_then(|v| v.as_f64()).map(|v| v as i32);
+
+    let rarity = get_str("rarity").and_then(Rarity::parse).unwrap_or(Rarity::Common);
+    let source = get_str("source").unwrap_or("unknown").to_string();
+    let class_req = get_str("classReq").map(str::to_string);
+
+    // Old server drops carried only flat bonuses and no slot — treat them as trophies
+    if get_str("type") == Some("drop") {
+        let mut item = Item::new("Trophy", "🏆", ItemSlot::Accessory, rarity, 1, &source);
+        item.name = format!("{} Trophy", source);
+        item.atk = get_i32("atk_bonus").unwrap_or(0);
+        item.def = get_i32("def_bonus").unwrap_or(0);
+        item.class_req = class_req;
+        clamp_to_generator(&mut item);
+        return Some(item);
+    }
+
+    let slot = ItemSlot::parse(get_str("slot")?)?;
+    let name = get_str("name").unwrap_or(slot.as_str());
+    let ilvl = obj.get("ilvl").and_then(|v| v.as_u64()).unwrap_or(1) as u32;
+    let mut item = Item::new(name, get_str("icon").unwrap_or(""), slot, rarity, ilvl, &source);
+
+    // Base type: the gear type contained in the name, or the name itself for legendaries
+    if let Some(gear) = GEAR_TYPES.iter().find(|g| g.slot == slot && name.contains(g.name)) {
+        item.base_type = gear.name.to_string();
+    }
+
+    if let Some(stats) = obj.get("stats").and_then(|v| v.as_object()) {
+        for (key, v) in stats {
+            if let (Some(stat), Some(v)) = (StatKind::parse(key), v.as_f64()) {
+                item.add_base_stat(stat, v as i32);
+            }
+        }
+    }
+
+    if let Some(affixes) = obj.get("affixes").and_then(|v| v.as_array()) {
+        for a in affixes {
+            let Some(stat) = a.get("stat").and_then(|v| v.as_str()).and_then(StatKind::parse) else {
+                continue;
+            };
+            item.affixes.push(Affix {
+                name: a.get("name").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+                stat,
+                value: a.get("value").and_then(|v| v.as_f64()).unwrap_or(0.0) as i32,
+                is_percent: a.get("pct").and_then(|v| v.as_bool()).unwrap_or(false),
+                is_prefix: a.get("type").and_then(|v| v.as_str()) == Some("prefix"),
+            });
+        }
+    }
+
+    item.passive_id = get_str("passiveId").map(str::to_string);
+    item.class_req = class_req;
+    item.card = get_str("cardSlot").map(|c| CardSocket { card_type: c.to_string() });
+    clamp_to_generator(&mut item);
+    Some(item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_json_without_a_slot_is_rejected() {
+        assert_eq!(parse_legacy_item_json("not json"), None);
+        assert_eq!(parse_legacy_item_json("[1, 2]"), None);
+        assert_eq!(parse_legacy_item_json(r#"{"name": "Sword"}"#), None);
+        assert_eq!(parse_legacy_item_json(r#"{"slot": "helmet"}"#), None);
+        assert_eq!(parse_legacy_item_json(r#"{"slot": 3}"#), None);
+    }
+
+    #[test]
+    fn legacy_json_defaults_missing_and_garbage_fields() {
+        let item = parse_legacy_item_json(r#"{
+            "slot": "armor", "rarity": 42, "ilvl": "high",
+            "stats": {"DEF": "lots", "HP": 5, "Mana": 9},
+            "affixes": [{"stat": "bogus", "value": 4}, {"value": 2}]
+        }"#).unwrap();
+        assert_eq!(item.slot, ItemSlot::Armor);
+        assert_eq!(item.rarity, Rarity::Common);
+        assert_eq!(item.item_level, 1);
+        assert_eq!(item.name, "armor");
+        assert_eq!(item.source, "unknown");
+        assert_eq!((item.atk, item.def, item.hp, item.speed), (0, 0, 5, 0));
+        assert!(item.affixes.is_empty());
+        assert_eq!(item.card, None);
+    }
+
+    #[test]
+    fn legacy_json_stats_are_clamped_to_the_generator() {
+        let item = parse_legacy_item_json(r#"{
+            "slot": "weapon", "name": "Sword of Cheating", "rarity": "rare", "ilvl": 9999,
+            "stats": {"ATK": 100000, "DEF": 40, "Speed": -7},
+            "affixes": [
+                {"name": "Mighty", "stat": "ATK", "value": 99999, "type": "prefix"},
+                {"name": "Thorny", "stat": "reflect", "value": 10, "pct": true},
+                {"name": "of Power", "stat": "ATK", "value": -3, "pct": true},
+                {"name": "Vital", "stat": "HP", "value": 5}
+            ]
+        }"#).unwrap();
+        assert_eq!(item.item_level, LEGACY_MAX_ILVL);
+        assert_eq!(item.base_type, "Sword");
+        assert_eq!(item.atk, max_base_stat(ItemSlot::Weapon, LEGACY_MAX_ILVL, StatKind::Atk));
+        assert_eq!(item.def, 0, "no weapon rolls DEF");
+        assert_eq!(item.speed, 0);
+
+        // Percent reflect never rolls, and a rare keeps at most two affixes
+        assert_eq!(item.affixes.len(), 2);
+        assert_eq!(item.affixes[0].value, max_affix_value(StatKind::Atk, false, LEGACY_MAX_ILVL).unwrap());
+        assert_eq!((item.affixes[1].name.as_str(), item.affixes[1].value), ("of Power", 1));
+    }
+
+    #[test]
+    fn legacy_drops_become_clamped_trophies() {
+        let item = parse_legacy_item_json(r#"{"type": "drop", "source": "wolf", "atk_bonus": 500, "def_bonus": 1}"#).unwrap();
+        assert_eq!(item.name, "wolf Trophy");
+        assert_eq!(item.slot, ItemSlot::Accessory);
+        assert_eq!(item.atk, max_base_stat(ItemSlot::Accessory, 1, StatKind::Atk));
+        assert_eq!(item.def, 1);
+    }
+
+    #[test]
+    fn generated_items_pass_the_clamp_unchanged() {
+        for seed in 0..300 {
+            let mut rng = Rng::new(seed);
+            let slot = SLOTS[seed as usize % SLOTS.len()];
+            let item = generate_gear(&mut rng, slot, 1 + seed as u32 % 20, seed % 3 == 0, None, "test");
+            let mut clamped = item.clone();
+            clamp_to_generator(&mut clamped);
+            assert_eq!(clamped, item, "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn legendaries_respect_the_class_hint() {
+        for seed in 0..200 {
+            for slot in SLOTS {
+                let item = generate_legendary(&mut Rng::new(seed), slot, 10, Some("tank"), "test");
+                assert_eq!(item.rarity, Rarity::Legendary);
+                assert_eq!(item.slot, slot);
+                assert!(item.passive_id.is_some());
+                assert!(matches!(item.class_req.as_deref(), None | Some("tank")), "{} for a tank", item.name);
+
+                let classless = generate_legendary(&mut Rng::new(seed), slot, 10, None, "test");
+                assert_eq!(classless.class_req, None, "{} without a class", classless.name);
+            }
+        }
+    }
+
+    #[test]
+    fn same_seed_gives_same_item() {
+        for seed in 0..50 {
+            let a = generate_gear(&mut Rng::new(seed), ItemSlot::Weapon, 12, true, Some("dps"), "boss");
+            let b = generate_gear(&mut Rng::new(seed), ItemSlot::Weapon, 12, true, Some("dps"), "boss");
+            assert_eq!(a, b);
+        }
+        let a = roll_enemy_drops(&mut Rng::new(8), "raid_boss", 5, None);
+        let b = roll_enemy_drops(&mut Rng::new(8), "raid_boss", 5, None);
+        assert!(!a.is_empty());
+        assert_eq!(a, b);
+    }
+}
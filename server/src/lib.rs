@@ -1,7 +1,25 @@
 //! SpacetimeDB server module for Dungeon Crawler
 //! Handles server-authoritative game state: players, dungeons, enemies, loot, inventory.
 
-use spacetimedb::{table, reducer, Table, ReducerContext, Identity, ScheduleAt, TimeDuration};
+use spacetimedb::{table, reducer, Table, ReducerContext, Identity, ScheduleAt, TimeDuration, Timestamp};
+
+mod combat;
+mod dungeon_graph;
+mod enemy_defs;
+mod items;
+mod pathfinding;
+mod raid_encounters;
+mod rng;
+mod room_layout;
+
+use combat::{Attacker, Combatant, DamageType, Defender, HitOutcome, HitResult, OnHitStatus, StatusKind, ZoneShape};
+use dungeon_graph::RoomKind;
+use enemy_defs::{AiArchetype, EnemyTemplate, Resistances};
+use items::{CardProc, CardSocket, Item, ItemSlot, WeaponProfile};
+use pathfinding::FlowField;
+use raid_encounters::{MechanicKind, MechanicTemplate};
+use rng::Rng;
+use room_layout::{Side, Tile};
 
 // ─── Tables ────────────────────────────────────────────────────────────────────
 
@@ -21,6 +39,15 @@ pub struct Player {
     speed: i32,
     gold: u64,
     dungeons_cleared: u32,
+    // Combat rolls (percent), derived from class and gear
+    #[default(0i32)]
+    crit_chance: i32,
+    #[default(150i32)]
+    crit_damage: i32,        // Percent of normal damage on a crit
+    #[default(0i32)]
+    dodge: i32,
+    #[default(0i32)]
+    block: i32,
 }
 
 /// An active dungeon instance
@@ -34,6 +61,16 @@ pub struct ActiveDungeon {
     current_room: u32,
     total_rooms: u32,
     seed: u64,
+    #[default(0u32)]
+    tier: u32,         // Dungeon tier 1-3 (0 for the classic 4-room crawl)
+    #[default(0u32)]
+    difficulty: u32,   // Star rating 1-5
+    #[default(0u64)]
+    started_at: u64,   // Unix timestamp in ms, used for clear time
+    #[default(0u64)]
+    rng_state: u64,    // Dungeon RNG stream, starts at `seed`
+    #[default(None::<u64>)]
+    raid_id: Option<u64>, // Raid instance fought in this dungeon, if any
 }
 
 /// An enemy inside an active dungeon room
@@ -54,7 +91,7 @@ pub struct DungeonEnemy {
     pub is_alive: bool,
 
     // AI state for server-authoritative enemy behavior
-    pub ai_state: String,     // "idle", "chase", "telegraph", "charge", "stunned", "orbit", "flee", "fuse", "explode"
+    pub ai_state: String,     // "idle", "chase", "telegraph", "charge", "stunned", "orbit", "flee", "fuse", "explode", "summon", "crumble"
     pub state_timer: f32,     // Seconds remaining in current state
     pub target_x: f32,        // Charge destination, orbit center, etc.
     pub target_y: f32,
@@ -70,6 +107,75 @@ pub struct DungeonEnemy {
     // Boss-specific fields
     pub is_boss: bool,
     pub boss_phase: u32,                 // 1, 2, or 3
+
+    // Summoned minions crumble when their summoner dies
+    #[default(None::<u64>)]
+    pub summoner_id: Option<u64>,
+    #[default(0i32)]
+    pub def: i32,                        // Flat mitigation, scaled with depth like hp/atk
+    #[default(false)]
+    pub is_priority: bool,               // Raid add the party should kill first
+}
+
+/// Stats and behavior for an enemy type, seeded in `init` and editable by admins
+#[derive(Clone)]
+#[table(name = enemy_definition, public)]
+pub struct EnemyDefinition {
+    #[primary_key]
+    pub enemy_type: String,
+    pub hp: i32,               // Base values at depth 1
+    pub atk: i32,
+    pub def: i32,
+    pub speed: f32,            // Multiple of ENEMY_MOVE_SPEED
+    pub xp: u64,
+    pub ai: AiArchetype,
+    pub loot_table: String,    // Drop table key for items::roll_enemy_drops
+    pub resistances: Resistances,
+    // Combat rolls (percent)
+    #[default(5i32)]
+    pub crit_chance: i32,
+    #[default(150i32)]
+    pub crit_damage: i32,
+    #[default(0i32)]
+    pub dodge: i32,
+    #[default(0i32)]
+    pub block: i32,
+    #[default(None::<OnHitStatus>)]
+    pub on_hit: Option<OnHitStatus>, // Status its attacks can inflict
+}
+
+/// A timed effect (stun, slow, DoT, absorb shield) on a player or dungeon enemy,
+/// ticked in `tick_enemies`
+#[table(name = status_effect, public)]
+pub struct StatusEffect {
+    #[primary_key]
+    #[auto_inc]
+    id: u64,
+    dungeon_id: u64,
+    target: Combatant,
+    kind: StatusKind,
+    magnitude: i32,            // Slow %, DoT damage per second, or shield absorb left
+    remaining: f32,            // Seconds
+    source: Combatant,
+    tick_timer: f32,           // Seconds until the next DoT tick
+}
+
+/// One resolved hit, so clients can show floating combat text from server truth.
+/// Rows are pruned after COMBAT_EVENT_TTL_MS.
+#[table(name = combat_event, public)]
+pub struct CombatEvent {
+    #[primary_key]
+    #[auto_inc]
+    id: u64,
+    dungeon_id: u64,           // 0 in the open world
+    source: Combatant,
+    target: Combatant,
+    outcome: HitOutcome,
+    damage_type: DamageType,
+    amount: i32,
+    x: f32,                    // Target position when hit
+    y: f32,
+    created_at: u64,           // Unix ms
 }
 
 /// Real-time player position in a dungeon
@@ -102,9 +208,11 @@ pub struct LootDrop {
     room_index: u32,
     x: f32,
     y: f32,
-    item_data_json: String,
+    item_data_json: String,  // Legacy free-form JSON, empty once `item` is set
     rarity: String,
     picked_up: bool,
+    #[default(None::<Item>)]
+    item: Option<Item>,
 }
 
 /// Player inventory item
@@ -113,10 +221,121 @@ pub struct InventoryItem {
     #[primary_key]
     #[auto_inc]
     id: u64,
+    #[index(btree)]
     owner_identity: Identity,
-    item_data_json: String,
+    item_data_json: String,          // Legacy free-form JSON, empty once `item` is set
     equipped_slot: Option<String>,
-    card_data_json: Option<String>,
+    card_data_json: Option<String>,  // Legacy, superseded by `item.card`
+    #[default(None::<Item>)]
+    item: Option<Item>,
+}
+
+/// A collected card waiting to be socketed into an item
+#[table(name = player_card, public)]
+pub struct PlayerCard {
+    #[primary_key]
+    #[auto_inc]
+    id: u64,
+    owner_identity: Identity,
+    card_type: String,  // Enemy type the card dropped from
+}
+
+/// A room in a dungeon's room graph
+#[table(name = dungeon_room, public)]
+pub struct DungeonRoom {
+    #[primary_key]
+    #[auto_inc]
+    id: u64,
+    dungeon_id: u64,
+    room_index: u32,
+    kind: RoomKind,
+    unlocked: bool,   // Reachable: the start room, or next to a cleared room
+    visited: bool,    // Layout and contents have been generated
+    cleared: bool,    // No enemies left alive
+}
+
+/// Door between two rooms of a dungeon (walkable both ways)
+#[table(name = room_connection, public)]
+pub struct RoomConnection {
+    #[primary_key]
+    #[auto_inc]
+    id: u64,
+    dungeon_id: u64,
+    from_room: u32,
+    to_room: u32,
+    from_side: Side,  // Door in `from_room`
+    to_side: Side,    // Door in `to_room`
+}
+
+/// An enemy projectile in flight (arrows, boss bullets)
+#[table(name = projectile, public)]
+pub struct Projectile {
+    #[primary_key]
+    #[auto_inc]
+    id: u64,
+    dungeon_id: u64,
+    room_index: u32,
+    owner_enemy_id: u64,
+    kind: String,      // "arrow", "boss_orb"
+    x: f32,
+    y: f32,
+    vx: f32,           // Pixels per second
+    vy: f32,
+    damage: i32,       // Raw damage, reduced by the target's DEF on hit
+    radius: f32,
+    lifetime: f32,     // Seconds remaining
+}
+
+/// Tile layout of a dungeon room, generated from the dungeon seed
+#[table(name = room_tile_map, public)]
+pub struct RoomTileMap {
+    #[primary_key]
+    #[auto_inc]
+    id: u64,
+    dungeon_id: u64,
+    room_index: u32,
+    width: u32,        // In tiles
+    height: u32,
+    tiles: Vec<Tile>,  // Row-major, width * height
+}
+
+/// A dungeon participant at 0 HP. Teammates can channel a revive until the
+/// bleed-out timer runs out; after that the player stays dead until the run ends.
+#[table(name = downed_player, public)]
+pub struct DownedPlayer {
+    #[primary_key]
+    identity: Identity,
+    dungeon_id: u64,
+    bleed_out: f32,            // Seconds left to be revived
+    is_dead: bool,             // Bled out, can no longer be revived
+    reviver: Option<Identity>, // Teammate channeling a revive
+    revive_progress: f32,      // Seconds channeled so far
+}
+
+/// Last accepted move per player, used to cap movement speed in
+/// `update_position` and `update_open_world_position`
+#[table(name = movement_state)]
+pub struct MovementState {
+    #[primary_key]
+    identity: Identity,
+    accepted_at: u64,    // Unix timestamp in ms of the last move, when the travel budget was refilled
+    grace_until: u64,    // Server teleports (room entry, dash) skip the speed check until then
+    violations: u32,     // Speed/bounds violations in the current window
+    window_start: u64,
+    #[default(0.0f32)]
+    travel_budget: f32,  // Unused travel time in ms carried to the next move, at most MOVE_LATENCY_MS
+}
+
+/// Players flagged by server-side validation, for admins to review
+#[table(name = suspicious_activity)]
+pub struct SuspiciousActivity {
+    #[primary_key]
+    #[auto_inc]
+    id: u64,
+    identity: Identity,
+    kind: String,        // "speed", "bounds"
+    detail: String,
+    created_at: u64,
 }
 
 /// Tracks which players are participating in a dungeon
@@ -179,6 +398,12 @@ pub struct PlayerAbilityState {
     dash_cd: f32,
     // DPS post-dash bonus timer
     post_dash_bonus_timer: f32,
+    // Enemy hits miss while this runs (granted by dash)
+    #[default(0.0f32)]
+    iframe_timer: f32,
+    // Swing timer: unix ms before which `attack` is rejected
+    #[default(0u64)]
+    next_attack_at: u64,
 }
 
 /// Active healing zones placed by healers
@@ -215,6 +440,8 @@ pub struct OpenWorldInstance {
     pub id: u64,
     pub created_at: u64,
     pub player_count: u32,
+    #[default(0u64)]
+    pub rng_state: u64,  // Combat RNG stream, advanced after every draw
 }
 
 /// Enemy in Open World (fixed spawn points with respawn timers)
@@ -241,6 +468,8 @@ pub struct OpenWorldEnemy {
     pub target_x: f32,
     pub target_y: f32,
     pub facing_angle: f32,
+    #[default(0i32)]
+    pub def: i32,
 }
 
 /// Player position in Open World
@@ -293,6 +522,10 @@ pub struct RaidInstance {
     pub boss_max_hp: i32,
     pub boss_phase: u32,
     pub wipe_count: u32,
+    #[default(0u64)]
+    pub ended_at: u64,  // Unix timestamp in ms, 0 while the fight is on
+    #[default(0u32)]
+    pub boss_index: u32,  // Current boss within the encounter
 }
 
 /// Raid participant (links player to raid instance)
@@ -305,6 +538,8 @@ pub struct RaidParticipant {
     pub player_identity: Identity,
     pub player_class: String,
     pub disconnected_at: Option<u64>,  // For reconnect window
+    #[default(0u32)]
+    pub slot: u32,                     // Spawn slot in the arena line-up
 }
 
 /// Player raid cooldown (2 min after wipe)
@@ -323,6 +558,108 @@ pub struct DailyRaidClear {
     pub last_clear_day: u32,  // Day number since epoch
 }
 
+/// Rewarded clears of a raid boss this week; past the cap the boss drops nothing
+/// for that player until the weekly reset
+#[table(name = raid_lockout, public)]
+pub struct RaidLockout {
+    #[primary_key]
+    #[auto_inc]
+    id: u64,
+    identity: Identity,
+    pub boss_type: String,
+    pub week: u32,  // Raid week number (see `raid_week`)
+    pub clears: u32,
+}
+
+/// A raid: bosses fought one after another in the arena
+#[table(name = raid_encounter, public)]
+pub struct RaidEncounter {
+    #[primary_key]
+    pub name: String,
+    pub bosses: Vec<String>,  // Enemy types in fight order
+}
+
+/// A scripted boss mechanic, seeded in `init` and interpreted by `tick_boss_mechanics`.
+/// It arms once the boss is at or below `hp_pct`, first goes off `start` seconds
+/// later and then repeats every `interval` seconds (0 = once).
+#[derive(Clone)]
+#[table(name = raid_mechanic, public)]
+pub struct RaidMechanic {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub boss_type: String,
+    pub name: String,
+    pub kind: MechanicKind,
+    pub hp_pct: u32,
+    pub start: f32,
+    pub interval: f32,
+    pub damage_pct: i32,  // Percent of boss ATK (ATK gained for soft enrages)
+    pub radius: f32,
+    pub telegraph: f32,   // Seconds a zone shows before it resolves
+    pub count: u32,       // Zones, adds or max Vulnerable stacks
+    pub add_type: String,
+    #[default(0.0f32)]
+    pub arc: f32,         // Cone half-angle in radians
+}
+
+/// Countdown of one mechanic for one living boss
+#[table(name = boss_mechanic_timer)]
+pub struct BossMechanicTimer {
+    #[primary_key]
+    #[auto_inc]
+    id: u64,
+    dungeon_id: u64,
+    enemy_id: u64,
+    mechanic_id: u64,
+    armed: bool,
+    remaining: f32,  // Seconds until the mechanic goes off
+    done: bool,      // One-shot mechanic already used
+}
+
+/// Danger area an enemy has committed to. Clients draw it from `created_at` to
+/// `resolve_at`; then the enemy tick hits every player inside.
+#[table(name = telegraph_zone, public)]
+pub struct TelegraphZone {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub dungeon_id: u64,
+    pub room_index: u32,
+    pub source_enemy_id: u64,
+    pub shape: ZoneShape,
+    pub x: f32,
+    pub y: f32,
+    pub radius: f32,      // Circle and cone radius, line length
+    pub angle: f32,       // Cone and line direction in radians
+    pub arc: f32,         // Cone half-angle in radians
+    pub width: f32,       // Line width
+    pub created_at: u64,  // Unix timestamp in ms
+    pub resolve_at: u64,  // Unix timestamp in ms
+    pub damage: i32,
+    pub damage_type: DamageType,
+    pub shared: bool,     // Damage is split between everyone inside (stack mechanics)
+    pub follow: Option<Identity>,  // Spread and stack zones move with their player
+}
+
+/// Scheduler table for the weekly raid lockout reset
+#[table(name = raid_reset_schedule, scheduled(reset_raid_lockouts))]
+pub struct RaidResetSchedule {
+    #[primary_key]
+    #[auto_inc]
+    scheduled_id: u64,
+    scheduled_at: ScheduleAt,
+}
+
+/// Module administrators (allowed to run privileged reducers). `init` seeds the
+/// publisher; databases published before this table existed get their first admin
+/// from the owner, e.g. `spacetime sql <db> "INSERT INTO admin (identity) VALUES (0x...)"`.
+#[table(name = admin)]
+pub struct Admin {
+    #[primary_key]
+    identity: Identity,
+}
+
 /// Scheduler table for matchmaking ticks
 #[table(name = matchmaking_tick_schedule, scheduled(tick_matchmaking))]
 pub struct MatchmakingTickSchedule {
@@ -344,18 +681,39 @@ pub struct OpenWorldTickSchedule {
 // ─── Constants ─────────────────────────────────────────────────────────────────
 
 const ATTACK_RANGE: f32 = 100.0;
+const KNOCKBACK_DAMAGE_PCT: i32 = 50;  // Tank knockback hits for half ATK as physical damage
 const ENEMY_ATTACK_RANGE: f32 = 40.0;
 const ENEMY_MOVE_SPEED: f32 = 2.0;
 const LOOT_PICKUP_RANGE: f32 = 50.0;
 const BASE_XP_PER_LEVEL: u64 = 100;
+// Stat growth per level above 1
+const LEVEL_HP_GROWTH: i32 = 10;
+const LEVEL_ATK_GROWTH: i32 = 2;
+const LEVEL_DEF_GROWTH: i32 = 1;
+
+// Removing a socketed card costs gold, scaled by item level
+const UNSOCKET_BASE_COST: u64 = 50;
+const UNSOCKET_COST_PER_ILVL: u64 = 10;
+
+// Dungeon completion rewards
+const DUNGEON_CLEAR_XP_PER_DEPTH: u64 = 50;
+const DUNGEON_CLEAR_GOLD_PER_DEPTH: u64 = 20;
+const DUNGEON_KILL_XP: u64 = 5;
+const DUNGEON_KILL_GOLD: u64 = 2;
+const DUNGEON_PAR_TIME_PER_ROOM_MS: u64 = 90_000;  // 90 seconds per room
 
 // AI tick rate: 50ms = 0.05 seconds
 const AI_DT: f32 = 0.05;
 
 // Room bounds (in pixels, matching client TILE=36, ROOM_W=15, ROOM_H=20)
 const TILE_SIZE: f32 = 36.0;
-const ROOM_W: f32 = 15.0 * TILE_SIZE; // 540
-const ROOM_H: f32 = 20.0 * TILE_SIZE; // 720
+const ROOM_W: f32 = room_layout::ROOM_COLS as f32 * TILE_SIZE; // 540
+const ROOM_H: f32 = room_layout::ROOM_ROWS as f32 * TILE_SIZE; // 720
+// Obstacle groups per generated room (arenas stay mostly open)
+const ROOM_BASE_FEATURES: u32 = 4;
+const ARENA_FEATURES: u32 = 2;
+// How far (in tiles) kiting/fleeing enemies search for a retreat point
+const RETREAT_SEARCH_STEPS: u32 = 6;
 
 // Charger AI
 const CHARGER_TELEGRAPH_TIME: f32 = 0.8;
@@ -363,11 +721,10 @@ const CHARGER_CHARGE_SPEED_MULT: f32 = 5.0;
 const CHARGER_CHARGE_DURATION: f32 = 1.5;
 const CHARGER_STUN_TIME: f32 = 1.0;
 const CHARGER_DETECT_RANGE: f32 = 200.0;
+const CHARGER_LANE_WIDTH: f32 = 60.0;
 
 // Wolf AI
 const WOLF_ORBIT_RADIUS: f32 = 50.0;
-#[allow(dead_code)]
-const WOLF_PACK_ATTACK_CD: f32 = 1.0;
 
 // Bomber AI
 const BOMBER_FUSE_TIME: f32 = 1.5;
@@ -377,8 +734,9 @@ const BOMBER_TRIGGER_RANGE: f32 = 60.0;
 // Necromancer AI
 const NECRO_FLEE_DISTANCE: f32 = 80.0;
 const NECRO_TELEPORT_CD: f32 = 3.0;
-#[allow(dead_code)]
 const NECRO_SUMMON_CD: f32 = 5.0;
+const NECRO_MAX_SUMMONS: usize = 3;  // Live minions per necromancer
+const MINION_XP_PCT: u64 = 20;       // Minions grant a fraction of normal XP and no loot
 
 // Shield Knight AI
 const SHIELD_BASH_CD: f32 = 4.0;
@@ -389,9 +747,34 @@ const ARCHER_KITE_DISTANCE: f32 = 120.0;
 const ARCHER_SHOOT_CD: f32 = 2.0;
 const ARCHER_SHOOT_RANGE: f32 = 180.0;
 
+// Status effects
+const STATUS_TICK_INTERVAL: f32 = 1.0;   // DoTs deal their damage once per second
+const TANK_AURA_RADIUS: f32 = 50.0;
+const TANK_AURA_SLOW_PCT: i32 = 30;
+const KNOCKBACK_STUN_TIME: f32 = 0.5;
+const SHIELD_BASH_STUN_TIME: f32 = 0.6;
+const HEALING_ZONE_SHIELD: i32 = 20;     // Absorb granted to allies in a fresh healing zone
+
+// Raid bosses
+const RAID_BOSS_SPEED: f32 = 40.0;
+const RAID_BOSS_ATTACK_CD: f32 = 1.0;
+const VULNERABLE_PCT_PER_STACK: i32 = 25; // Extra damage taken per tank buster stack
+const VULNERABLE_TIME: f32 = 20.0;
+const BOSS_MEND_PCT: f32 = 1.0;           // Boss max HP healed per second per living priority add
+
+// Combat events live long enough for every client to render them
+const COMBAT_EVENT_TTL_MS: u64 = 2000;
+
+// Projectiles
+const PLAYER_HIT_RADIUS: f32 = 14.0;
+const ARROW_SPEED: f32 = 360.0;
+const ARROW_RADIUS: f32 = 4.0;
+const BOSS_ORB_SPEED: f32 = 180.0;
+const BOSS_ORB_RADIUS: f32 = 8.0;
+const BOSS_ORB_LIFETIME: f32 = 4.0;
+
 // Open World Constants
 const OPEN_WORLD_SIZE: i32 = 10;  // 10x10 grid of rooms
-const OPEN_WORLD_SPAWN_POINTS_PER_ROOM: u32 = 10;  // 8-12 fixed spawn points per room
 const OPEN_WORLD_BASE_RESPAWN_MS: u64 = 45000;  // 45 second base respawn
 const OPEN_WORLD_HOTSPOT_RESPAWN_MS: u64 = 20000;  // 20 second respawn at hotspots
 const OPEN_WORLD_MAX_PLAYERS_PER_SHARD: u32 = 50;
@@ -401,9 +784,314 @@ const DUNGEON_TIER_1_MAX_LEVEL: u32 = 5;
 const DUNGEON_TIER_2_MAX_LEVEL: u32 = 10;
 const DUNGEON_TIER_3_MAX_LEVEL: u32 = 15;
 
+// Attacks
+const MIN_SWING_TIME: f32 = 0.15;        // Attack speed can't push swings below this
+const SWING_TOLERANCE_MS: u64 = 50;      // Network jitter allowed on the swing timer
+
+// Dash
+const DASH_DISTANCE: f32 = 150.0;
+const DASH_IFRAME_TIME: f32 = 0.25;      // Seconds of invulnerability at the start of a dash
+
+// Movement validation
+const PLAYER_BASE_MOVE_SPEED: f32 = 160.0;    // Pixels per second before the speed stat
+const PLAYER_MOVE_SPEED_PER_POINT: f32 = 10.0;
+const MOVE_SPEED_TOLERANCE: f32 = 1.25;       // Headroom for passives and client frame jitter
+const MOVE_LATENCY_MS: u64 = 150;             // Unused travel time a player can carry between updates
+const MOVE_MAX_WINDOW_MS: u64 = 1000;         // Idle time can't be banked into one long move
+const MOVE_TELEPORT_GRACE_MS: u64 = 500;
+const MOVE_VIOLATION_WINDOW_MS: u64 = 10000;
+const MOVE_VIOLATION_THRESHOLD: u32 = 5;      // Violations in one window before flagging
+
+// Downed players
+const DOWNED_BLEED_OUT_TIME: f32 = 30.0;
+const REVIVE_CHANNEL_TIME: f32 = 3.0;
+const REVIVE_RANGE: f32 = 40.0;
+const REVIVE_HP_PCT: i32 = 30;           // HP restored by a revive, percent of max
+
 // Raid constants
+const RAID_PARTY_SIZE: usize = 4;  // 1 tank, 1 healer, 2 dps
 const RAID_RECONNECT_WINDOW_MS: u64 = 60000;  // 60 seconds
 const RAID_WIPE_COOLDOWN_MS: u64 = 120000;  // 2 minutes
+const RAID_CLEAR_COOLDOWN_MS: u64 = 300000;  // 5 minutes
+const RAID_ARENA_FEATURES: u32 = 2;  // Mostly open floor so the boss patterns have room
+const RAID_WEEKLY_LOOT_CLEARS: u32 = 5;  // Rewarded clears per boss per week
+const MS_PER_DAY: u64 = 86_400_000;
+
+// ─── Module Lifecycle ──────────────────────────────────────────────────────────
+
+/// Module init: the publishing identity becomes the first admin
+#[reducer(init)]
+pub fn init(ctx: &ReducerContext) {
+    ctx.db.admin().insert(Admin { identity: ctx.sender });
+    let seeded = seed_missing_enemy_definitions(ctx);
+    let mechanics = seed_missing_raid_encounters(ctx);
+    schedule_raid_reset(ctx);
+    log::info!("Module initialized, admin: {:?}, {} enemy definitions, {} raid mechanics", ctx.sender, seeded, mechanics);
+}
+
+/// A client connected. Raid members coming back inside the reconnect window
+/// pick up where they left off.
+#[reducer(client_connected)]
+pub fn client_connected(ctx: &ReducerContext) {
+    if let Some(part) = active_raid_participant(ctx, ctx.sender) {
+        if part.disconnected_at.is_some() {
+            log::info!("{:?} reconnected to raid {}", ctx.sender, part.raid_id);
+            start_movement_grace(ctx, ctx.sender);
+            ctx.db.raid_participant().id().update(RaidParticipant {
+                disconnected_at: None,
+                ..part
+            });
+        }
+    }
+}
+
+/// A client disconnected. Raid members keep their slot for
+/// RAID_RECONNECT_WINDOW_MS, and enemies stop targeting them meanwhile.
+#[reducer(client_disconnected)]
+pub fn client_disconnected(ctx: &ReducerContext) {
+    let now = ctx.timestamp.to_duration_since_unix_epoch()
+        .unwrap_or_default().as_millis() as u64;
+    if let Some(part) = active_raid_participant(ctx, ctx.sender) {
+        log::info!("{:?} disconnected from raid {}", ctx.sender, part.raid_id);
+        ctx.db.raid_participant().id().update(RaidParticipant {
+            disconnected_at: Some(now),
+            ..part
+        });
+    }
+}
+
+/// Check whether the caller is an admin
+fn is_admin(ctx: &ReducerContext) -> bool {
+    ctx.db.admin().identity().find(ctx.sender).is_some()
+}
+
+/// Grant admin rights to another identity (admin only)
+#[reducer]
+pub fn grant_admin(ctx: &ReducerContext, identity: Identity) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Admin only".into());
+    }
+    if ctx.db.admin().identity().find(identity).is_none() {
+        ctx.db.admin().insert(Admin { identity });
+    }
+    Ok(())
+}
+
+// ─── Enemy Definitions ─────────────────────────────────────────────────────────
+
+fn definition_from_template(t: &EnemyTemplate) -> EnemyDefinition {
+    EnemyDefinition {
+        enemy_type: t.enemy_type.to_string(),
+        hp: t.hp,
+        atk: t.atk,
+        def: t.def,
+        speed: t.speed,
+        xp: t.xp,
+        ai: t.ai,
+        loot_table: t.enemy_type.to_string(),
+        resistances: t.resistances,
+        crit_chance: t.rolls.crit_chance,
+        crit_damage: t.rolls.crit_damage,
+        dodge: t.rolls.dodge,
+        block: t.rolls.block,
+        on_hit: t.on_hit,
+    }
+}
+
+/// Insert built-in definitions that have no row yet, returns how many were added
+fn seed_missing_enemy_definitions(ctx: &ReducerContext) -> usize {
+    let mut seeded = 0;
+    for t in enemy_defs::DEFAULTS {
+        if ctx.db.enemy_definition().enemy_type().find(t.enemy_type.to_string()).is_none() {
+            ctx.db.enemy_definition().insert(definition_from_template(t));
+            seeded += 1;
+        }
+    }
+    seeded
+}
+
+/// Insert built-in raid encounters without a row, and the built-in mechanics of
+/// bosses that have none. Returns how many mechanics were added.
+fn seed_missing_raid_encounters(ctx: &ReducerContext) -> usize {
+    for t in raid_encounters::ENCOUNTERS {
+        if ctx.db.raid_encounter().name().find(t.name.to_string()).is_none() {
+            ctx.db.raid_encounter().insert(RaidEncounter {
+                name: t.name.to_string(),
+                bosses: t.bosses.iter().map(|b| b.to_string()).collect(),
+            });
+        }
+    }
+    let scripted: std::collections::HashSet<String> = ctx.db.raid_mechanic().iter()
+        .map(|m| m.boss_type)
+        .collect();
+    let mut seeded = 0;
+    for t in raid_encounters::MECHANICS {
+        if !scripted.contains(t.boss_type) {
+            ctx.db.raid_mechanic().insert(mechanic_from_template(t));
+            seeded += 1;
+        }
+    }
+    seeded
+}
+
+fn mechanic_from_template(t: &MechanicTemplate) -> RaidMechanic {
+    RaidMechanic {
+        id: 0,
+        boss_type: t.boss_type.to_string(),
+        name: t.name.to_string(),
+        kind: t.kind,
+        hp_pct: t.hp_pct,
+        start: t.start,
+        interval: t.interval,
+        damage_pct: t.damage_pct,
+        radius: t.radius,
+        telegraph: t.telegraph,
+        count: t.count,
+        add_type: t.add_type.to_string(),
+        arc: t.arc,
+    }
+}
+
+/// Bosses of an encounter in fight order (just the raid boss if the row is missing)
+fn encounter_bosses(ctx: &ReducerContext, name: &str) -> Vec<String> {
+    ctx.db.raid_encounter().name().find(name.to_string())
+        .map(|e| e.bosses)
+        .filter(|b| !b.is_empty())
+        .unwrap_or_else(|| vec!["raid_boss".to_string()])
+}
+
+/// Definition for an enemy type. Falls back to the built-in stats (or generic ones)
+/// for types without a row.
+fn enemy_definition(ctx: &ReducerContext, enemy_type: &str) -> EnemyDefinition {
+    ctx.db.enemy_definition().enemy_type().find(enemy_type.to_string())
+        .unwrap_or_else(|| EnemyDefinition {
+            enemy_type: enemy_type.to_string(),
+            ..definition_from_template(enemy_defs::template(enemy_type))
+        })
+}
+
+/// Add or replace an enemy definition (admin only)
+#[allow(clippy::too_many_arguments)]
+#[reducer]
+pub fn set_enemy_definition(
+    ctx: &ReducerContext,
+    enemy_type: String,
+    hp: i32,
+    atk: i32,
+    def: i32,
+    speed: f32,
+    xp: u64,
+    ai: AiArchetype,
+    loot_table: String,
+    resistances: Resistances,
+) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Admin only".into());
+    }
+    if enemy_type.is_empty() {
+        return Err("Enemy type is required".into());
+    }
+    if hp <= 0 || atk < 0 || def < 0 {
+        return Err("HP must be positive, ATK and DEF non-negative".into());
+    }
+    if !(0.0..=10.0).contains(&speed) {
+        return Err("Speed must be between 0 and 10".into());
+    }
+
+    // Combat rolls are tuned separately; keep the current ones
+    let current = enemy_definition(ctx, &enemy_type);
+    let definition = EnemyDefinition { enemy_type, hp, atk, def, speed, xp, ai, loot_table, resistances, ..current };
+    log::info!("Enemy definition {} set by {:?}", definition.enemy_type, ctx.sender);
+    upsert_enemy_definition(ctx, definition);
+    Ok(())
+}
+
+/// Set an enemy type's crit, dodge and block chances (admin only)
+#[reducer]
+pub fn set_enemy_combat_rolls(
+    ctx: &ReducerContext,
+    enemy_type: String,
+    crit_chance: i32,
+    crit_damage: i32,
+    dodge: i32,
+    block: i32,
+) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Admin only".into());
+    }
+    if [crit_chance, dodge, block].iter().any(|pct| !(0..=100).contains(pct)) {
+        return Err("Crit, dodge and block chances must be between 0 and 100".into());
+    }
+    if crit_damage < 100 {
+        return Err("Crit damage must be at least 100%".into());
+    }
+
+    let definition = EnemyDefinition {
+        crit_chance,
+        crit_damage,
+        dodge,
+        block,
+        ..enemy_definition(ctx, &enemy_type)
+    };
+    upsert_enemy_definition(ctx, definition);
+    Ok(())
+}
+
+/// Set or clear the status an enemy type's attacks can inflict (admin only)
+#[reducer]
+pub fn set_enemy_on_hit(ctx: &ReducerContext, enemy_type: String, on_hit: Option<OnHitStatus>) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Admin only".into());
+    }
+    if let Some(status) = on_hit {
+        if !(0.0..=1.0).contains(&status.chance) {
+            return Err("On-hit chance must be between 0 and 1".into());
+        }
+        if status.duration <= 0.0 || status.magnitude < 0 {
+            return Err("On-hit duration must be positive and magnitude non-negative".into());
+        }
+    }
+
+    let definition = EnemyDefinition {
+        on_hit,
+        ..enemy_definition(ctx, &enemy_type)
+    };
+    upsert_enemy_definition(ctx, definition);
+    Ok(())
+}
+
+fn upsert_enemy_definition(ctx: &ReducerContext, definition: EnemyDefinition) {
+    if ctx.db.enemy_definition().enemy_type().find(definition.enemy_type.clone()).is_some() {
+        ctx.db.enemy_definition().enemy_type().update(definition);
+    } else {
+        ctx.db.enemy_definition().insert(definition);
+    }
+}
+
+/// Remove an enemy definition; built-in types fall back to their compiled stats (admin only)
+#[reducer]
+pub fn delete_enemy_definition(ctx: &ReducerContext, enemy_type: String) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Admin only".into());
+    }
+    if !ctx.db.enemy_definition().enemy_type().delete(enemy_type.clone()) {
+        return Err(format!("No definition for {}", enemy_type));
+    }
+    Ok(())
+}
+
+/// Seed built-in definitions missing from the table, e.g. on databases published
+/// before the table existed (admin only)
+#[reducer]
+pub fn seed_enemy_definitions(ctx: &ReducerContext) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Admin only".into());
+    }
+    let seeded = seed_missing_enemy_definitions(ctx);
+    let mechanics = seed_missing_raid_encounters(ctx);
+    log::info!("Seeded {} enemy definitions, {} raid mechanics", seeded, mechanics);
+    Ok(())
+}
 
 // ─── Account Reducers ──────────────────────────────────────────────────────────
 
@@ -431,6 +1119,15 @@ fn get_class_stats(player_class: &str) -> (i32, i32, i32, i32) {
     }
 }
 
+/// Combat rolls for a player class: (crit_chance, crit_damage, dodge, block) in percent
+fn get_class_rolls(player_class: &str) -> (i32, i32, i32, i32) {
+    match player_class {
+        "tank" => (5, 150, 0, 15),
+        "dps" => (10, 175, 10, 0),
+        _ => (5, 150, 5, 0),
+    }
+}
+
 /// Add threat from a player attacking an enemy
 fn add_threat(ctx: &ReducerContext, dungeon_id: u64, enemy_id: u64, player_identity: Identity, amount: i32) {
     // Find existing threat entry for this player-enemy pair
@@ -464,11 +1161,12 @@ fn get_highest_threat_player(ctx: &ReducerContext, dungeon_id: u64, enemy_id: u6
     let mut highest_player: Option<Identity> = None;
 
     for entry in ctx.db.threat_entry().iter() {
-        if entry.dungeon_id == dungeon_id && entry.enemy_id == enemy_id {
-            if entry.threat_value > highest_threat {
-                highest_threat = entry.threat_value;
-                highest_player = Some(entry.player_identity);
-            }
+        if entry.dungeon_id == dungeon_id
+            && entry.enemy_id == enemy_id
+            && entry.threat_value > highest_threat
+        {
+            highest_threat = entry.threat_value;
+            highest_player = Some(entry.player_identity);
         }
     }
 
@@ -485,6 +1183,7 @@ fn tick_ability_cooldowns(ctx: &ReducerContext, dt: f32) {
         updated.healing_zone_cd = (updated.healing_zone_cd - dt).max(0.0);
         updated.dash_cd = (updated.dash_cd - dt).max(0.0);
         updated.post_dash_bonus_timer = (updated.post_dash_bonus_timer - dt).max(0.0);
+        updated.iframe_timer = (updated.iframe_timer - dt).max(0.0);
         ctx.db.player_ability_state().identity().update(updated);
     }
 }
@@ -492,7 +1191,10 @@ fn tick_ability_cooldowns(ctx: &ReducerContext, dt: f32) {
 /// Tick healing zones (heal players inside, decrement duration)
 fn tick_healing_zones(ctx: &ReducerContext, dt: f32) {
     let zones: Vec<ActiveHealingZone> = ctx.db.active_healing_zone().iter().collect();
-    let positions: Vec<PlayerPosition> = ctx.db.player_position().iter().collect();
+    // Healing doesn't pick downed players back up; that takes a revive
+    let positions: Vec<PlayerPosition> = ctx.db.player_position().iter()
+        .filter(|p| ctx.db.player().identity().find(p.identity).is_some_and(|pl| pl.hp > 0))
+        .collect();
 
     for zone in zones {
         if zone.duration_remaining <= 0.0 {
@@ -568,6 +1270,7 @@ pub fn register_player(ctx: &ReducerContext, name: String, player_class: String)
     }
 
     let (max_hp, atk, def, speed) = get_class_stats(&class_lower);
+    let (crit_chance, crit_damage, dodge, block) = get_class_rolls(&class_lower);
 
     ctx.db.player().insert(Player {
         identity: ctx.sender,
@@ -582,6 +1285,10 @@ pub fn register_player(ctx: &ReducerContext, name: String, player_class: String)
         speed,
         gold: 0,
         dungeons_cleared: 0,
+        crit_chance,
+        crit_damage,
+        dodge,
+        block,
     });
     log::info!("Player registered: {:?}", ctx.sender);
     Ok(())
@@ -605,6 +1312,14 @@ pub fn start_dungeon(ctx: &ReducerContext) -> Result<(), String> {
     let player = ctx.db.player().identity().find(ctx.sender)
         .ok_or("Player not found")?;
 
+    // Downed players wait for a revive or the wipe instead of respawning mid-run
+    if let Some(downed) = ctx.db.downed_player().identity().find(ctx.sender) {
+        if ctx.db.active_dungeon().id().find(downed.dungeon_id).is_some() {
+            return Err("You are downed; wait for a revive or the party wipe".into());
+        }
+        ctx.db.downed_player().identity().delete(ctx.sender);
+    }
+
     // Check if player was dead (respawning) - if so, clean up their old dungeon
     let was_dead = player.hp <= 0;
 
@@ -633,7 +1348,7 @@ pub fn start_dungeon(ctx: &ReducerContext) -> Result<(), String> {
             if participant_count <= 1 {
                 // Only participant, clean up the whole dungeon
                 cleanup_dungeon(ctx, dungeon_id);
-                if let Some(_) = ctx.db.active_dungeon().id().find(dungeon_id) {
+                if ctx.db.active_dungeon().id().find(dungeon_id).is_some() {
                     ctx.db.active_dungeon().id().delete(dungeon_id);
                 }
             } else {
@@ -650,8 +1365,11 @@ pub fn start_dungeon(ctx: &ReducerContext) -> Result<(), String> {
     }
 
     // Check if an active dungeon with OTHER participants exists — join it
-    // (After cleanup, respawning players can join other players' dungeons)
-    let latest = ctx.db.active_dungeon().iter().max_by_key(|d| d.id);
+    // (After cleanup, respawning players can join other players' dungeons).
+    // Raid arenas and tiered runs are formed by their queues and never joined here.
+    let latest = ctx.db.active_dungeon().iter()
+        .filter(|d| d.raid_id.is_none() && d.tier == 0)
+        .max_by_key(|d| d.id);
     if let Some(existing) = latest {
         let dungeon_id = existing.id;
         let has_other_participants = ctx.db.dungeon_participant().iter()
@@ -673,6 +1391,7 @@ pub fn start_dungeon(ctx: &ReducerContext) -> Result<(), String> {
                 .ok_or("Player not found")?;
 
             // Initialize player position in the existing dungeon
+            start_movement_grace(ctx, ctx.sender);
             if let Some(old_pos) = ctx.db.player_position().identity().find(ctx.sender) {
                 ctx.db.player_position().identity().update(PlayerPosition {
                     identity: ctx.sender,
@@ -716,8 +1435,10 @@ pub fn start_dungeon(ctx: &ReducerContext) -> Result<(), String> {
 
     let seed = ctx.timestamp.to_duration_since_unix_epoch()
         .unwrap_or_default().as_micros() as u64;
-    let total_rooms = 4; // Fixed 4-room structure: Basic, Tactical, Complex, Raid
     let depth = player.dungeons_cleared + 1;
+    let mut rng = Rng::new(seed);
+    let plan = dungeon_graph::generate(&mut rng, depth);
+    let total_rooms = plan.rooms.len() as u32;
 
     let dungeon = ctx.db.active_dungeon().insert(ActiveDungeon {
         id: 0,
@@ -726,6 +1447,11 @@ pub fn start_dungeon(ctx: &ReducerContext) -> Result<(), String> {
         current_room: 0,
         total_rooms,
         seed,
+        tier: 0,
+        difficulty: 1,
+        started_at: seed / 1000,
+        rng_state: seed,
+        raid_id: None,
     });
 
     // Add owner as participant
@@ -735,7 +1461,30 @@ pub fn start_dungeon(ctx: &ReducerContext) -> Result<(), String> {
         player_identity: ctx.sender,
     });
 
-    spawn_enemies_for_room(ctx, dungeon.id, 0, depth, seed);
+    for (room_index, kind) in plan.rooms.iter().enumerate() {
+        ctx.db.dungeon_room().insert(DungeonRoom {
+            id: 0,
+            dungeon_id: dungeon.id,
+            room_index: room_index as u32,
+            kind: *kind,
+            unlocked: room_index == 0,
+            visited: false,
+            cleared: false,
+        });
+    }
+    for link in &plan.links {
+        ctx.db.room_connection().insert(RoomConnection {
+            id: 0,
+            dungeon_id: dungeon.id,
+            from_room: link.from,
+            to_room: link.to,
+            from_side: link.from_side,
+            to_side: link.to_side,
+        });
+    }
+
+    populate_room(ctx, &mut rng, dungeon.id, 0, depth);
+    store_dungeon_rng(ctx, dungeon.id, &rng);
 
     // Start the enemy AI tick scheduler (only if not already running)
     if ctx.db.enemy_tick_schedule().iter().count() == 0 {
@@ -744,6 +1493,7 @@ pub fn start_dungeon(ctx: &ReducerContext) -> Result<(), String> {
     }
 
     // Initialize player position (player variable is from line ~309)
+    start_movement_grace(ctx, ctx.sender);
     if let Some(old_pos) = ctx.db.player_position().identity().find(ctx.sender) {
         ctx.db.player_position().identity().update(PlayerPosition {
             identity: ctx.sender,
@@ -776,7 +1526,7 @@ pub fn start_dungeon(ctx: &ReducerContext) -> Result<(), String> {
         });
     }
 
-    log::info!("Dungeon started: id={}, depth={}, rooms={}", dungeon.id, depth, total_rooms);
+    log::info!("Dungeon started: id={}, depth={}, rooms={}, seed={}", dungeon.id, depth, total_rooms, seed);
     Ok(())
 }
 
@@ -794,17 +1544,35 @@ pub fn enter_room(ctx: &ReducerContext, dungeon_id: u64, room_index: u32) -> Res
         return Err("Room index out of bounds".into());
     }
 
+    // Tier dungeons are a single room without a room graph
+    let room = find_dungeon_room(ctx, dungeon_id, room_index);
+    if let Some(room) = &room {
+        if room_index != dungeon.current_room {
+            let adjacent = ctx.db.room_connection().iter().any(|c| {
+                c.dungeon_id == dungeon_id
+                    && ((c.from_room == dungeon.current_room && c.to_room == room_index)
+                        || (c.to_room == dungeon.current_room && c.from_room == room_index))
+            });
+            if !adjacent {
+                return Err(format!("Room {} is not connected to room {}", room_index, dungeon.current_room));
+            }
+        }
+        if !room.unlocked {
+            return Err(format!("Room {} is locked: clear room {} first", room_index, dungeon.current_room));
+        }
+    }
+
     // Update current room
     ctx.db.active_dungeon().id().update(ActiveDungeon {
         current_room: room_index,
         ..dungeon
     });
 
-    // Only spawn enemies if none exist for this room yet
-    let enemies_exist = ctx.db.dungeon_enemy().iter()
-        .any(|e| e.dungeon_id == dungeon_id && e.room_index == room_index);
-    if !enemies_exist {
-        spawn_enemies_for_room(ctx, dungeon_id, room_index, dungeon.depth, dungeon.seed);
+    // Generate the room's layout and contents on first entry
+    if room.is_some_and(|r| !r.visited) {
+        let mut rng = Rng::new(dungeon.rng_state);
+        populate_room(ctx, &mut rng, dungeon_id, room_index, dungeon.depth);
+        store_dungeon_rng(ctx, dungeon_id, &rng);
     }
 
     // Reset all participants' positions for new room
@@ -814,6 +1582,7 @@ pub fn enter_room(ctx: &ReducerContext, dungeon_id: u64, room_index: u32) -> Res
         .collect();
     for pid in participant_ids {
         if let Some(pos) = ctx.db.player_position().identity().find(pid) {
+            start_movement_grace(ctx, pid);
             ctx.db.player_position().identity().update(PlayerPosition {
                 identity: pid,
                 dungeon_id: pos.dungeon_id,
@@ -836,6 +1605,8 @@ pub fn enter_room(ctx: &ReducerContext, dungeon_id: u64, room_index: u32) -> Res
 }
 
 /// Complete a dungeon. Award XP and gold, increment dungeons_cleared.
+/// Rewards are calculated on the server; `client_gold`/`client_xp` are only
+/// hints and are rejected if they claim more than the server awards.
 #[reducer]
 pub fn complete_dungeon(ctx: &ReducerContext, dungeon_id: u64, client_gold: Option<u64>, client_xp: Option<u64>) -> Result<(), String> {
     let dungeon = ctx.db.active_dungeon().id().find(dungeon_id)
@@ -845,30 +1616,49 @@ pub fn complete_dungeon(ctx: &ReducerContext, dungeon_id: u64, client_gold: Opti
     if !is_participant {
         return Err("Not a participant in this dungeon".into());
     }
+    if !dungeon_finished(ctx, &dungeon) {
+        return Err("Dungeon not finished".into());
+    }
 
     let player = ctx.db.player().identity().find(ctx.sender)
         .ok_or("Player not found")?;
 
-    let xp_reward = client_xp.unwrap_or(50 * dungeon.depth as u64);
-    let gold_reward = client_gold.unwrap_or(20 * dungeon.depth as u64);
+    let (kills, rooms_cleared) = count_dungeon_kills(ctx, dungeon_id);
+    let party_size = ctx.db.dungeon_participant().iter()
+        .filter(|p| p.dungeon_id == dungeon_id)
+        .count() as u32;
+    let now = ctx.timestamp.to_duration_since_unix_epoch()
+        .unwrap_or_default().as_millis() as u64;
+    let clear_time_ms = now.saturating_sub(dungeon.started_at);
+
+    let (xp_reward, gold_reward) = calculate_dungeon_rewards(
+        &dungeon, kills, rooms_cleared, party_size, clear_time_ms, player.level,
+    );
+
+    // Client values are advisory only — reject claims above the server reward
+    if client_xp.is_some_and(|xp| xp > xp_reward) {
+        return Err(format!("Claimed XP exceeds server reward ({})", xp_reward));
+    }
+    if client_gold.is_some_and(|gold| gold > gold_reward) {
+        return Err(format!("Claimed gold exceeds server reward ({})", gold_reward));
+    }
+
     let new_xp = player.xp + xp_reward;
     let new_gold = player.gold + gold_reward;
     let new_cleared = player.dungeons_cleared + 1;
 
     // Check for level up
-    let (new_level, new_max_hp, new_atk, new_def) = check_level_up(
-        player.level, new_xp, player.max_hp, player.atk, player.def,
-    );
+    let new_level = check_level_up(player.level, new_xp);
 
-    ctx.db.player().identity().update(Player {
+    let player = with_derived_stats(ctx, Player {
         xp: new_xp,
         gold: new_gold,
         dungeons_cleared: new_cleared,
         level: new_level,
-        max_hp: new_max_hp,
-        hp: new_max_hp, // full heal on dungeon complete
-        atk: new_atk,
-        def: new_def,
+        ..player
+    });
+    ctx.db.player().identity().update(Player {
+        hp: player.max_hp, // full heal on dungeon complete
         ..player
     });
 
@@ -876,13 +1666,17 @@ pub fn complete_dungeon(ctx: &ReducerContext, dungeon_id: u64, client_gold: Opti
     cleanup_dungeon(ctx, dungeon_id);
     ctx.db.active_dungeon().id().delete(dungeon_id);
 
-    log::info!("Dungeon {} completed! +{}xp +{}gold", dungeon_id, xp_reward, gold_reward);
+    log::info!(
+        "Dungeon {} completed! {} kills, {}/{} rooms, {}s, +{}xp +{}gold",
+        dungeon_id, kills, rooms_cleared, dungeon.total_rooms, clear_time_ms / 1000, xp_reward, gold_reward,
+    );
     Ok(())
 }
 
 // ─── Real-time Gameplay Reducers ───────────────────────────────────────────────
 
 /// Update player position (called frequently by client)
+#[allow(clippy::too_many_arguments)]
 #[reducer]
 pub fn update_position(
     ctx: &ReducerContext,
@@ -895,47 +1689,47 @@ pub fn update_position(
     armor_icon: String,
     accessory_icon: String,
 ) -> Result<(), String> {
-    if let Some(pos) = ctx.db.player_position().identity().find(ctx.sender) {
-        // Preserve name/level/class from existing position, update equipment
-        ctx.db.player_position().identity().update(PlayerPosition {
-            identity: ctx.sender,
-            dungeon_id,
-            x,
-            y,
-            facing_x,
-            facing_y,
-            name: pos.name.clone(),
-            level: pos.level,
-            player_class: pos.player_class.clone(),
-            weapon_icon,
-            armor_icon,
-            accessory_icon,
-        });
-    } else {
-        // Fetch player for visual data
-        let player = ctx.db.player().identity().find(ctx.sender)
-            .ok_or("Player not found")?;
-        ctx.db.player_position().insert(PlayerPosition {
-            identity: ctx.sender,
-            dungeon_id,
-            x,
-            y,
-            facing_x,
-            facing_y,
-            name: player.name.clone(),
-            level: player.level,
-            player_class: player.player_class.clone(),
-            weapon_icon,
-            armor_icon,
-            accessory_icon,
-        });
+    let joined = ctx.db.dungeon_participant().iter()
+        .any(|p| p.dungeon_id == dungeon_id && p.player_identity == ctx.sender);
+    if !joined {
+        return Err("Not in this dungeon".into());
+    }
+    // Joining a dungeon places the player server-side; a position update never moves between dungeons
+    let pos = ctx.db.player_position().identity().find(ctx.sender)
+        .ok_or("Position not found")?;
+    if pos.dungeon_id != dungeon_id {
+        return Err("Not in this dungeon".into());
     }
+
+    // Stunned and downed players can turn and swap gear but not move
+    let (x, y) = if ensure_can_act(ctx).is_err() {
+        (pos.x, pos.y)
+    } else {
+        let (x, y) = clamp_to_room(ctx, ctx.sender, x, y);
+        validate_move(ctx, ctx.sender, (pos.x, pos.y), (x, y))
+    };
+    // Preserve name/level/class from existing position, update equipment
+    ctx.db.player_position().identity().update(PlayerPosition {
+        identity: ctx.sender,
+        dungeon_id,
+        x,
+        y,
+        facing_x,
+        facing_y,
+        name: pos.name.clone(),
+        level: pos.level,
+        player_class: pos.player_class.clone(),
+        weapon_icon,
+        armor_icon,
+        accessory_icon,
+    });
     Ok(())
 }
 
 /// Player attacks an enemy. Server validates range and applies damage.
 #[reducer]
 pub fn attack(ctx: &ReducerContext, dungeon_id: u64, target_enemy_id: u64) -> Result<(), String> {
+    ensure_can_act(ctx)?;
     let player = ctx.db.player().identity().find(ctx.sender)
         .ok_or("Player not found")?;
     let pos = ctx.db.player_position().identity().find(ctx.sender)
@@ -955,17 +1749,15 @@ pub fn attack(ctx: &ReducerContext, dungeon_id: u64, target_enemy_id: u64) -> Re
         return Err("Target out of range".into());
     }
 
+    let gear = equipped_items(ctx, ctx.sender);
+    start_swing(ctx, dungeon_id, &gear)?;
+
     // Calculate damage with class bonuses
     let mut damage = player.atk.max(1);
 
     // DPS backstab bonus: +50% damage when hitting from behind (>120° from enemy facing)
     if player.player_class == "dps" {
-        let attack_angle = (pos.y - enemy.y).atan2(pos.x - enemy.x);
-        let mut angle_diff = (attack_angle - enemy.facing_angle).abs();
-        if angle_diff > std::f32::consts::PI {
-            angle_diff = 2.0 * std::f32::consts::PI - angle_diff;
-        }
-        // Backstab: attack from behind (angle_diff > 2π/3 ≈ 120°)
+        let angle_diff = combat::angle_off_facing(enemy.facing_angle, (enemy.x, enemy.y), (pos.x, pos.y));
         if angle_diff > std::f32::consts::PI * 2.0 / 3.0 {
             damage = (damage as f32 * 1.5) as i32;
         }
@@ -980,57 +1772,192 @@ pub fn attack(ctx: &ReducerContext, dungeon_id: u64, target_enemy_id: u64) -> Re
         }
     }
 
-    let new_hp = enemy.hp - damage;
+    // Socketed cards add damage against their own enemy type
+    let card_bonus = card_bonus_vs(&gear, &enemy.enemy_type);
+    if card_bonus > 0 {
+        damage = damage * (100 + card_bonus) / 100;
+    }
+
+    // Roll dodge/block/crit and mitigate by the enemy's DEF, resistances and shield
+    let mut rng = load_dungeon_rng(ctx, dungeon_id).ok_or("Dungeon not found")?;
+    let definition = enemy_definition(ctx, &enemy.enemy_type);
+    let damage_type = weapon_damage_type(&gear);
+    let hit = combat::resolve_damage(&mut rng, damage, damage_type, &player_attacker(&player, pos.x, pos.y), &dungeon_defender(&enemy, &definition));
+    log_combat_event(ctx, dungeon_id, Combatant::Player(ctx.sender), Combatant::Enemy(enemy.id), &hit, damage_type, (enemy.x, enemy.y));
+    let dealt = hit.amount;
+    let new_hp = enemy.hp - dealt;
 
     // Generate threat: tanks generate 2x threat, others 1x
     let threat_mult = if player.player_class == "tank" { 2 } else { 1 };
-    let threat_generated = damage * threat_mult;
+    let threat_generated = dealt * threat_mult;
     add_threat(ctx, dungeon_id, target_enemy_id, ctx.sender, threat_generated);
 
+    // On-hit card procs (a dodged swing doesn't proc)
+    let mut heal = 0;
+    let mut splashes: Vec<(f32, i32)> = Vec::new(); // (radius, damage)
+    for gear_item in gear.iter().filter(|_| dealt > 0) {
+        let Some(card) = &gear_item.card else { continue };
+        match items::card_effect(&card.card_type).proc_effect {
+            Some(CardProc::Explode { chance, pct, radius }) if rng.chance(chance) => {
+                splashes.push((radius, (damage * pct / 100).max(1)));
+            }
+            Some(CardProc::Drain { chance, pct }) if rng.chance(chance) => {
+                heal += (dealt * pct / 100).max(1);
+            }
+            Some(CardProc::Inflict(status)) => {
+                roll_on_hit_status(ctx, &mut rng, dungeon_id, Combatant::Enemy(target_enemy_id), Combatant::Player(ctx.sender), status, dealt);
+            }
+            _ => {}
+        }
+    }
+    // The weapon's own on-hit effect
+    if let Some(status) = gear.iter().find(|g| g.slot == ItemSlot::Weapon).and_then(|w| w.on_hit_status()).filter(|_| dealt > 0) {
+        roll_on_hit_status(ctx, &mut rng, dungeon_id, Combatant::Enemy(target_enemy_id), Combatant::Player(ctx.sender), status, dealt);
+    }
+
+    let (hit_x, hit_y, hit_room) = (enemy.x, enemy.y, enemy.room_index);
+    let mut xp_reward = 0;
     if new_hp <= 0 {
-        // Enemy dies — capture loot info before moving
-        let enemy_type = enemy.enemy_type.clone();
-        let e_dungeon_id = enemy.dungeon_id;
-        let e_room_index = enemy.room_index;
-        let e_x = enemy.x;
-        let e_y = enemy.y;
-        let e_atk = enemy.atk;
-        let e_max_hp = enemy.max_hp;
+        xp_reward += kill_dungeon_enemy(ctx, &mut rng, ctx.sender, enemy);
+        log::info!("Enemy {} killed in dungeon {}", target_enemy_id, dungeon_id);
+    } else if dealt > 0 {
         ctx.db.dungeon_enemy().id().update(DungeonEnemy {
-            hp: 0,
-            is_alive: false,
+            hp: new_hp,
             ..enemy
         });
-        // Drop loot
-        drop_loot_for_dead_enemy(ctx, &enemy_type, e_dungeon_id, e_room_index, e_x, e_y, e_atk, e_max_hp);
+    }
+
+    // Explosions splash nearby enemies in the same room as fire damage (they can't crit)
+    let blast = Attacker { x: hit_x, y: hit_y, crit_chance: 0, crit_damage: 100 };
+    for (radius, splash_damage) in splashes {
+        let victims: Vec<DungeonEnemy> = ctx.db.dungeon_enemy().iter()
+            .filter(|e| e.dungeon_id == dungeon_id && e.room_index == hit_room && e.is_alive && e.id != target_enemy_id)
+            .filter(|e| ((e.x - hit_x).powi(2) + (e.y - hit_y).powi(2)).sqrt() <= radius)
+            .collect();
+        for victim in victims {
+            // A summoner dying earlier in the splash takes its minions with it
+            let Some(victim) = ctx.db.dungeon_enemy().id().find(victim.id).filter(|v| v.is_alive) else {
+                continue;
+            };
+            let victim_def = enemy_definition(ctx, &victim.enemy_type);
+            let hit = combat::resolve_damage(&mut rng, splash_damage, DamageType::Fire, &blast, &dungeon_defender(&victim, &victim_def));
+            log_combat_event(ctx, dungeon_id, Combatant::Player(ctx.sender), Combatant::Enemy(victim.id), &hit, DamageType::Fire, (victim.x, victim.y));
+            let hp = victim.hp - hit.amount;
+            if hp <= 0 {
+                xp_reward += kill_dungeon_enemy(ctx, &mut rng, ctx.sender, victim);
+            } else if hit.amount > 0 {
+                ctx.db.dungeon_enemy().id().update(DungeonEnemy { hp, ..victim });
+            }
+        }
+    }
+
+    // Arcing weapons cleave through enemies in a cone toward the target
+    let profile = weapon_profile(&gear);
+    if profile.cleave_targets > 0 {
+        let aim = (hit_y - pos.y).atan2(hit_x - pos.x);
+        let mut cleaved: Vec<DungeonEnemy> = ctx.db.dungeon_enemy().iter()
+            .filter(|e| e.dungeon_id == dungeon_id && e.room_index == hit_room && e.is_alive && e.id != target_enemy_id)
+            .filter(|e| ((e.x - pos.x).powi(2) + (e.y - pos.y).powi(2)).sqrt() <= ATTACK_RANGE)
+            .filter(|e| combat::angle_off_facing(aim, (pos.x, pos.y), (e.x, e.y)) <= profile.arc)
+            .collect();
+        cleaved.sort_by(|a, b| {
+            let da = (a.x - pos.x).powi(2) + (a.y - pos.y).powi(2);
+            let db = (b.x - pos.x).powi(2) + (b.y - pos.y).powi(2);
+            da.partial_cmp(&db).unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let cleave_damage = (damage * profile.cleave_pct / 100).max(1);
+        for victim in cleaved.into_iter().take(profile.cleave_targets) {
+            let Some(victim) = ctx.db.dungeon_enemy().id().find(victim.id).filter(|v| v.is_alive) else {
+                continue;
+            };
+            let victim_def = enemy_definition(ctx, &victim.enemy_type);
+            let hit = combat::resolve_damage(&mut rng, cleave_damage, damage_type, &player_attacker(&player, pos.x, pos.y), &dungeon_defender(&victim, &victim_def));
+            log_combat_event(ctx, dungeon_id, Combatant::Player(ctx.sender), Combatant::Enemy(victim.id), &hit, damage_type, (victim.x, victim.y));
+            add_threat(ctx, dungeon_id, victim.id, ctx.sender, hit.amount * threat_mult);
+            let hp = victim.hp - hit.amount;
+            if hp <= 0 {
+                xp_reward += kill_dungeon_enemy(ctx, &mut rng, ctx.sender, victim);
+            } else if hit.amount > 0 {
+                ctx.db.dungeon_enemy().id().update(DungeonEnemy { hp, ..victim });
+            }
+        }
+    }
+
+    store_dungeon_rng(ctx, dungeon_id, &rng);
 
-        // Award XP for kill
-        let xp_reward = get_enemy_xp(&enemy_type);
+    if xp_reward > 0 || heal > 0 {
         let new_xp = player.xp + xp_reward;
-        let (new_level, new_max_hp, new_atk, new_def) = check_level_up(
-            player.level, new_xp, player.max_hp, player.atk, player.def,
-        );
-        ctx.db.player().identity().update(Player {
+        let new_level = check_level_up(player.level, new_xp);
+        let hp = (player.hp + heal).min(player.max_hp);
+        ctx.db.player().identity().update(with_derived_stats(ctx, Player {
             xp: new_xp,
             level: new_level,
-            max_hp: new_max_hp,
-            atk: new_atk,
-            def: new_def,
+            hp,
             ..player
-        });
+        }));
+        if xp_reward > 0 {
+            log::info!("{:?} gained {}xp in dungeon {}", ctx.sender, xp_reward, dungeon_id);
+        }
+    }
 
-        log::info!("Enemy {} killed in dungeon {}, +{}xp", target_enemy_id, dungeon_id, xp_reward);
-    } else {
+    Ok(())
+}
+
+/// Mark a dungeon enemy dead, drop its loot and roll its card for the killer.
+/// Returns the XP it is worth.
+fn kill_dungeon_enemy(ctx: &ReducerContext, rng: &mut Rng, killer: Identity, enemy: DungeonEnemy) -> u64 {
+    let enemy_type = enemy.enemy_type.clone();
+    let (dungeon_id, room_index, x, y) = (enemy.dungeon_id, enemy.room_index, enemy.x, enemy.y);
+    ctx.db.dungeon_enemy().id().update(DungeonEnemy {
+        hp: 0,
+        is_alive: false,
+        ..enemy
+    });
+    let is_minion = enemy.summoner_id.is_some();
+    let enemy_id = enemy.id;
+    // Killing an enemy calls off its pending telegraphs
+    let zones: Vec<u64> = ctx.db.telegraph_zone().iter()
+        .filter(|z| z.source_enemy_id == enemy_id)
+        .map(|z| z.id)
+        .collect();
+    for id in zones {
+        ctx.db.telegraph_zone().id().delete(id);
+    }
+    // Raid bosses reward each member in `clear_raid` instead of dropping floor loot
+    let raid_boss = enemy.is_boss
+        && ctx.db.active_dungeon().id().find(dungeon_id).is_some_and(|d| d.raid_id.is_some());
+    // Summons can't be farmed: no loot or cards, reduced XP
+    if !is_minion {
+        if !raid_boss {
+            let loot_table = enemy_definition(ctx, &enemy_type).loot_table;
+            drop_loot_for_dead_enemy(ctx, rng, &loot_table, dungeon_id, room_index, x, y);
+        }
+        roll_card_drop(ctx, rng, killer, &enemy_type);
+    }
+
+    // Minions crumble with their summoner
+    let minions: Vec<DungeonEnemy> = ctx.db.dungeon_enemy().iter()
+        .filter(|m| m.summoner_id == Some(enemy_id) && m.is_alive)
+        .collect();
+    for minion in minions {
         ctx.db.dungeon_enemy().id().update(DungeonEnemy {
-            hp: new_hp,
-            ..enemy
+            hp: 0,
+            is_alive: false,
+            ai_state: "crumble".to_string(),
+            ..minion
         });
     }
 
-    Ok(())
+    check_room_clear(ctx, dungeon_id, room_index);
+    if is_minion {
+        get_enemy_xp(ctx, &enemy_type) * MINION_XP_PCT / 100
+    } else {
+        get_enemy_xp(ctx, &enemy_type)
+    }
 }
 
-/// Player uses dash ability. Server validates cooldown (simplified: always allow for now).
+/// Player uses dash ability. Server validates the class cooldown, normalizes the
+/// direction and stops the dash at walls, pits and the room edge.
 #[reducer]
 pub fn use_dash(
     ctx: &ReducerContext,
@@ -1038,45 +1965,74 @@ pub fn use_dash(
     dir_x: f32,
     dir_y: f32,
 ) -> Result<(), String> {
+    ensure_can_act(ctx)?;
     let player = ctx.db.player().identity().find(ctx.sender)
         .ok_or("Player not found")?;
+    let joined = ctx.db.dungeon_participant().iter()
+        .any(|p| p.dungeon_id == dungeon_id && p.player_identity == ctx.sender);
     let pos = ctx.db.player_position().identity().find(ctx.sender)
         .ok_or("Position not found")?;
+    if !joined || pos.dungeon_id != dungeon_id {
+        return Err("Not in this dungeon".into());
+    }
+    let dungeon = ctx.db.active_dungeon().id().find(pos.dungeon_id)
+        .ok_or("Dungeon not found")?;
+
+    let mag = (dir_x * dir_x + dir_y * dir_y).sqrt();
+    if !mag.is_finite() || mag < 0.01 {
+        return Err("Invalid dash direction".into());
+    }
+    let (dir_x, dir_y) = (dir_x / mag, dir_y / mag);
+
+    ensure_ability_state(ctx, dungeon_id);
+    let state = ctx.db.player_ability_state().identity().find(ctx.sender)
+        .ok_or("Ability state not found")?;
+    if state.dash_cd > 0.0 {
+        return Err("Dash is on cooldown".into());
+    }
 
-    let dash_distance = 150.0;
-    let new_x = pos.x + dir_x * dash_distance;
-    let new_y = pos.y + dir_y * dash_distance;
+    let new_x = (pos.x + dir_x * DASH_DISTANCE).clamp(TILE_SIZE, ROOM_W - TILE_SIZE);
+    let new_y = (pos.y + dir_y * DASH_DISTANCE).clamp(TILE_SIZE, ROOM_H - TILE_SIZE);
 
+    // Dash stops at the first wall, pillar or pit along the path
+    let tiles = room_tiles(ctx, dungeon.id, dungeon.current_room).unwrap_or_else(room_layout::open_room);
+    let (new_x, new_y) = room_layout::sweep(&tiles, (pos.x, pos.y), (new_x, new_y));
+
+    start_movement_grace(ctx, ctx.sender);
     ctx.db.player_position().identity().update(PlayerPosition {
-        identity: ctx.sender,
-        dungeon_id: pos.dungeon_id,
         x: new_x,
         y: new_y,
         facing_x: dir_x,
         facing_y: dir_y,
-        name: pos.name.clone(),
-        level: pos.level,
-        player_class: pos.player_class.clone(),
-        weapon_icon: pos.weapon_icon.clone(),
-        armor_icon: pos.armor_icon.clone(),
-        accessory_icon: pos.accessory_icon.clone(),
+        ..pos
     });
 
-    // DPS gets post-dash damage bonus (0.5s window for +25% damage)
-    if player.player_class == "dps" {
-        ensure_ability_state(ctx, dungeon_id);
-        if let Some(state) = ctx.db.player_ability_state().identity().find(ctx.sender) {
-            ctx.db.player_ability_state().identity().update(PlayerAbilityState {
-                post_dash_bonus_timer: 0.5,
-                ..state
-            });
-        }
-    }
+    // DPS get a post-dash damage bonus (0.5s window for +25% damage)
+    ctx.db.player_ability_state().identity().update(PlayerAbilityState {
+        dash_cd: dash_cooldown(&player.player_class),
+        iframe_timer: DASH_IFRAME_TIME,
+        post_dash_bonus_timer: if player.player_class == "dps" { 0.5 } else { state.post_dash_bonus_timer },
+        ..state
+    });
 
     log::info!("Player dashed in dungeon {}", dungeon_id);
     Ok(())
 }
 
+/// Dash cooldown in seconds; DPS dash most often, tanks least
+fn dash_cooldown(player_class: &str) -> f32 {
+    match player_class {
+        "dps" => 3.0,
+        "healer" => 5.0,
+        _ => 6.0,
+    }
+}
+
+/// True while a player's dash i-frames are running
+fn has_iframes(ctx: &ReducerContext, identity: Identity) -> bool {
+    ctx.db.player_ability_state().identity().find(identity).is_some_and(|s| s.iframe_timer > 0.0)
+}
+
 /// Ensure a player has an ability state record
 fn ensure_ability_state(ctx: &ReducerContext, dungeon_id: u64) {
     if ctx.db.player_ability_state().identity().find(ctx.sender).is_none() {
@@ -1088,6 +2044,8 @@ fn ensure_ability_state(ctx: &ReducerContext, dungeon_id: u64) {
             healing_zone_cd: 0.0,
             dash_cd: 0.0,
             post_dash_bonus_timer: 0.0,
+            iframe_timer: 0.0,
+            next_attack_at: 0,
         });
     }
 }
@@ -1095,6 +2053,7 @@ fn ensure_ability_state(ctx: &ReducerContext, dungeon_id: u64) {
 /// Tank ability: Taunt a single enemy to force it to attack the tank for 4 seconds
 #[reducer]
 pub fn use_taunt(ctx: &ReducerContext, dungeon_id: u64, target_enemy_id: u64) -> Result<(), String> {
+    ensure_can_act(ctx)?;
     let player = ctx.db.player().identity().find(ctx.sender)
         .ok_or("Player not found")?;
 
@@ -1142,6 +2101,7 @@ pub fn use_taunt(ctx: &ReducerContext, dungeon_id: u64, target_enemy_id: u64) ->
 /// Tank ability: Knockback all enemies within 60px, pushing them back 100px
 #[reducer]
 pub fn use_knockback(ctx: &ReducerContext, dungeon_id: u64) -> Result<(), String> {
+    ensure_can_act(ctx)?;
     let player = ctx.db.player().identity().find(ctx.sender)
         .ok_or("Player not found")?;
     let pos = ctx.db.player_position().identity().find(ctx.sender)
@@ -1167,27 +2127,66 @@ pub fn use_knockback(ctx: &ReducerContext, dungeon_id: u64) -> Result<(), String
         .filter(|e| e.dungeon_id == dungeon_id && e.is_alive)
         .collect();
 
+    let mut rng = load_dungeon_rng(ctx, dungeon_id).ok_or("Dungeon not found")?;
+    let mut xp_reward = 0;
     for enemy in enemies {
+        // Minions crumble if their summoner dies earlier in the loop
+        let Some(enemy) = ctx.db.dungeon_enemy().id().find(enemy.id).filter(|e| e.is_alive) else {
+            continue;
+        };
         let dx = enemy.x - pos.x;
         let dy = enemy.y - pos.y;
         let dist = (dx * dx + dy * dy).sqrt();
 
         if dist <= knockback_radius && dist > 0.1 {
+            let definition = enemy_definition(ctx, &enemy.enemy_type);
+            let hit = combat::resolve_damage(
+                &mut rng,
+                player.atk * KNOCKBACK_DAMAGE_PCT / 100,
+                DamageType::Physical,
+                &player_attacker(&player, pos.x, pos.y),
+                &dungeon_defender(&enemy, &definition),
+            );
+            log_combat_event(ctx, dungeon_id, Combatant::Player(ctx.sender), Combatant::Enemy(enemy.id), &hit, DamageType::Physical, (enemy.x, enemy.y));
+            let dealt = hit.amount;
+            if enemy.hp - dealt <= 0 {
+                xp_reward += kill_dungeon_enemy(ctx, &mut rng, ctx.sender, enemy);
+                continue;
+            }
+
             let nx = dx / dist;
             let ny = dy / dist;
-            let new_x = (enemy.x + nx * knockback_distance).clamp(TILE_SIZE, ROOM_W - TILE_SIZE);
-            let new_y = (enemy.y + ny * knockback_distance).clamp(TILE_SIZE, ROOM_H - TILE_SIZE);
+            let mut new_x = (enemy.x + nx * knockback_distance).clamp(TILE_SIZE, ROOM_W - TILE_SIZE);
+            let mut new_y = (enemy.y + ny * knockback_distance).clamp(TILE_SIZE, ROOM_H - TILE_SIZE);
+            // Knocked back enemies stop at walls and pits
+            if let Some(tiles) = room_tiles(ctx, dungeon_id, enemy.room_index) {
+                (new_x, new_y) = room_layout::sweep(&tiles, (enemy.x, enemy.y), (new_x, new_y));
+            }
 
+            let enemy_id = enemy.id;
             ctx.db.dungeon_enemy().id().update(DungeonEnemy {
+                hp: enemy.hp - dealt,
                 x: new_x,
                 y: new_y,
-                ai_state: "stunned".to_string(),
-                state_timer: 0.5, // Stunned briefly
                 ..enemy
             });
+            // Stunned briefly
+            apply_status(ctx, dungeon_id, Combatant::Enemy(enemy_id), StatusKind::Stun, 0, KNOCKBACK_STUN_TIME, Combatant::Player(ctx.sender));
         }
     }
 
+    store_dungeon_rng(ctx, dungeon_id, &rng);
+
+    if xp_reward > 0 {
+        let new_xp = player.xp + xp_reward;
+        let new_level = check_level_up(player.level, new_xp);
+        ctx.db.player().identity().update(with_derived_stats(ctx, Player {
+            xp: new_xp,
+            level: new_level,
+            ..player
+        }));
+    }
+
     // Set cooldown (12 seconds)
     ctx.db.player_ability_state().identity().update(PlayerAbilityState {
         knockback_cd: 12.0,
@@ -1201,6 +2200,7 @@ pub fn use_knockback(ctx: &ReducerContext, dungeon_id: u64) -> Result<(), String
 /// Healer ability: Place a healing zone at position (60px radius, heals for 8 seconds)
 #[reducer]
 pub fn place_healing_zone(ctx: &ReducerContext, dungeon_id: u64, x: f32, y: f32) -> Result<(), String> {
+    ensure_can_act(ctx)?;
     let player = ctx.db.player().identity().find(ctx.sender)
         .ok_or("Player not found")?;
 
@@ -1216,6 +2216,15 @@ pub fn place_healing_zone(ctx: &ReducerContext, dungeon_id: u64, x: f32, y: f32)
         return Err("Healing zone is on cooldown".into());
     }
 
+    // Allies standing in the zone when it lands get an absorb shield
+    let allies: Vec<Identity> = ctx.db.player_position().iter()
+        .filter(|p| p.dungeon_id == dungeon_id && ((p.x - x).powi(2) + (p.y - y).powi(2)).sqrt() <= 60.0)
+        .map(|p| p.identity)
+        .collect();
+    for ally in allies {
+        apply_status(ctx, dungeon_id, Combatant::Player(ally), StatusKind::Shield, HEALING_ZONE_SHIELD, 8.0, Combatant::Player(ctx.sender));
+    }
+
     // Create healing zone (60px radius, 5 HP/sec heal, 8 second duration)
     ctx.db.active_healing_zone().insert(ActiveHealingZone {
         id: 0,
@@ -1238,6 +2247,43 @@ pub fn place_healing_zone(ctx: &ReducerContext, dungeon_id: u64, x: f32, y: f32)
     Ok(())
 }
 
+/// Start channeling a revive on a downed teammate. The channel completes in
+/// `tick_enemies` if the reviver stays close and on their feet.
+#[reducer]
+pub fn revive_player(ctx: &ReducerContext, dungeon_id: u64, target: Identity) -> Result<(), String> {
+    ensure_can_act(ctx)?;
+    if target == ctx.sender {
+        return Err("Cannot revive yourself".into());
+    }
+    let downed = ctx.db.downed_player().identity().find(target)
+        .ok_or("Player is not downed")?;
+    if downed.dungeon_id != dungeon_id {
+        return Err("Player is not in this dungeon".into());
+    }
+    if downed.is_dead {
+        return Err("Player has bled out".into());
+    }
+
+    let pos = ctx.db.player_position().identity().find(ctx.sender)
+        .ok_or("Position not found")?;
+    let target_pos = ctx.db.player_position().identity().find(target)
+        .ok_or("Target position not found")?;
+    let dist = ((pos.x - target_pos.x).powi(2) + (pos.y - target_pos.y).powi(2)).sqrt();
+    if pos.dungeon_id != dungeon_id || dist > REVIVE_RANGE {
+        return Err("Too far away to revive".into());
+    }
+
+    // Another teammate taking over starts the channel from scratch
+    if downed.reviver != Some(ctx.sender) {
+        ctx.db.downed_player().identity().update(DownedPlayer {
+            reviver: Some(ctx.sender),
+            revive_progress: 0.0,
+            ..downed
+        });
+    }
+    Ok(())
+}
+
 // ─── Loot & Inventory Reducers ─────────────────────────────────────────────────
 
 /// Pick up a loot drop. Validates proximity, adds to inventory.
@@ -1251,6 +2297,9 @@ pub fn pickup_loot(ctx: &ReducerContext, loot_id: u64) -> Result<(), String> {
     if loot.picked_up {
         return Err("Already picked up".into());
     }
+    if loot.dungeon_id != pos.dungeon_id {
+        return Err("Loot is not in your dungeon".into());
+    }
 
     // Range check
     let dx = pos.x - loot.x;
@@ -1261,7 +2310,7 @@ pub fn pickup_loot(ctx: &ReducerContext, loot_id: u64) -> Result<(), String> {
     }
 
     // Capture before move
-    let item_data = loot.item_data_json.clone();
+    let item = loot.item.clone().ok_or("Loot has not been migrated to a typed item")?;
 
     // Mark as picked up
     ctx.db.loot_drop().id().update(LootDrop {
@@ -1273,44 +2322,137 @@ pub fn pickup_loot(ctx: &ReducerContext, loot_id: u64) -> Result<(), String> {
     ctx.db.inventory_item().insert(InventoryItem {
         id: 0, // auto_inc
         owner_identity: ctx.sender,
-        item_data_json: item_data,
+        item_data_json: String::new(),
         equipped_slot: None,
         card_data_json: None,
+        item: Some(item),
     });
 
     log::info!("Loot {} picked up by {:?}", loot_id, ctx.sender);
     Ok(())
 }
 
-/// Add an inventory item directly (client-authoritative loot)
+/// Add an inventory item directly (admin only — all player loot is rolled by the server)
 #[reducer]
-pub fn add_inventory_item(ctx: &ReducerContext, item_data_json: String, rarity: String) -> Result<(), String> {
+pub fn add_inventory_item(ctx: &ReducerContext, item_data_json: String) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Admin only: items are generated by the server".into());
+    }
     if ctx.db.player().identity().find(ctx.sender).is_none() {
         return Err("Player not found".into());
     }
+    let item = items::parse_legacy_item_json(&item_data_json)
+        .ok_or("Invalid item data")?;
+    let rarity = item.rarity;
     ctx.db.inventory_item().insert(InventoryItem {
         id: 0,
         owner_identity: ctx.sender,
-        item_data_json,
+        item_data_json: String::new(),
         equipped_slot: None,
         card_data_json: None,
+        item: Some(item),
     });
-    log::info!("Inventory item added for {:?} (rarity: {})", ctx.sender, rarity);
+    log::info!("Inventory item added for {:?} (rarity: {})", ctx.sender, rarity.as_str());
+    Ok(())
+}
+
+/// One-time migration: parse legacy `item_data_json`/`card_data_json` rows into typed items (admin only)
+#[reducer]
+pub fn migrate_item_json(ctx: &ReducerContext) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Admin only".into());
+    }
+
+    let mut migrated = 0;
+    let mut failed = 0;
+
+    let legacy_items: Vec<InventoryItem> = ctx.db.inventory_item().iter()
+        .filter(|i| i.item.is_none())
+        .collect();
+    for row in legacy_items {
+        let Some(mut item) = items::parse_legacy_item_json(&row.item_data_json) else {
+            log::warn!("Could not parse inventory item {}: {}", row.id, row.item_data_json);
+            failed += 1;
+            continue;
+        };
+        if let Some(card) = row.card_data_json.as_deref().and_then(items::parse_legacy_card_json) {
+            item.card = Some(card);
+        }
+        ctx.db.inventory_item().id().update(InventoryItem {
+            item_data_json: String::new(),
+            card_data_json: None,
+            item: Some(item),
+            ..row
+        });
+        migrated += 1;
+    }
+
+    let legacy_loot: Vec<LootDrop> = ctx.db.loot_drop().iter()
+        .filter(|l| l.item.is_none())
+        .collect();
+    for row in legacy_loot {
+        let Some(item) = items::parse_legacy_item_json(&row.item_data_json) else {
+            log::warn!("Could not parse loot drop {}: {}", row.id, row.item_data_json);
+            failed += 1;
+            continue;
+        };
+        ctx.db.loot_drop().id().update(LootDrop {
+            item_data_json: String::new(),
+            rarity: item.rarity.as_str().to_string(),
+            item: Some(item),
+            ..row
+        });
+        migrated += 1;
+    }
+
+    log::info!("Item migration: {} rows migrated, {} failed", migrated, failed);
     Ok(())
 }
 
 /// Equip an inventory item to a slot
 #[reducer]
 pub fn equip_item(ctx: &ReducerContext, item_id: u64, slot: String) -> Result<(), String> {
-    let item = ctx.db.inventory_item().id().find(item_id)
+    let inv = ctx.db.inventory_item().id().find(item_id)
         .ok_or("Item not found")?;
-    if item.owner_identity != ctx.sender {
+    if inv.owner_identity != ctx.sender {
         return Err("Not your item".into());
     }
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+
+    let target_slot = ItemSlot::parse(&slot).ok_or_else(|| {
+        let valid: Vec<&str> = ItemSlot::ALL.iter().map(|s| s.as_str()).collect();
+        format!("Unknown slot '{}'. Valid slots: {}", slot, valid.join(", "))
+    })?;
+    let item = inv.item.as_ref()
+        .ok_or("Item has not been migrated to a typed item")?;
+    if item.slot != target_slot {
+        return Err(format!("{} goes in the {} slot, not {}", item.name, item.slot.as_str(), target_slot.as_str()));
+    }
+    item.check_requirements(&player.player_class, player.level)?;
+
+    // Two-handed weapons and off-hand items are mutually exclusive
+    let equipped: Vec<InventoryItem> = ctx.db.inventory_item().owner_identity().filter(ctx.sender)
+        .filter(|i| i.id != item_id && i.equipped_slot.is_some())
+        .collect();
+    let equipped_in = |s: ItemSlot| {
+        equipped.iter().find(|i| i.equipped_slot.as_deref() == Some(s.as_str()))
+            .and_then(|i| i.item.as_ref())
+    };
+    if item.is_two_handed() {
+        if let Some(off) = equipped_in(ItemSlot::OffHand) {
+            return Err(format!("{} is two-handed; unequip {} first", item.name, off.name));
+        }
+    }
+    if target_slot == ItemSlot::OffHand {
+        if let Some(weapon) = equipped_in(ItemSlot::Weapon).filter(|w| w.is_two_handed()) {
+            return Err(format!("Cannot use an off-hand item with two-handed {}", weapon.name));
+        }
+    }
 
     // Unequip anything currently in that slot
-    for existing in ctx.db.inventory_item().iter() {
-        if existing.owner_identity == ctx.sender && existing.equipped_slot.as_deref() == Some(&slot) {
+    for existing in equipped {
+        if existing.equipped_slot.as_deref() == Some(target_slot.as_str()) {
             ctx.db.inventory_item().id().update(InventoryItem {
                 equipped_slot: None,
                 ..existing
@@ -1319,9 +2461,10 @@ pub fn equip_item(ctx: &ReducerContext, item_id: u64, slot: String) -> Result<()
     }
 
     ctx.db.inventory_item().id().update(InventoryItem {
-        equipped_slot: Some(slot),
-        ..item
+        equipped_slot: Some(target_slot.as_str().to_string()),
+        ..inv
     });
+    recompute_player_stats(ctx, ctx.sender);
     Ok(())
 }
 
@@ -1337,6 +2480,81 @@ pub fn unequip_item(ctx: &ReducerContext, item_id: u64) -> Result<(), String> {
         equipped_slot: None,
         ..item
     });
+    recompute_player_stats(ctx, ctx.sender);
+    Ok(())
+}
+
+/// Socket a card from the card inventory into an item. Sockets are permanent
+/// unless removed with `unsocket_card`.
+#[reducer]
+pub fn socket_card(ctx: &ReducerContext, item_id: u64, card_id: u64) -> Result<(), String> {
+    let inv = ctx.db.inventory_item().id().find(item_id)
+        .ok_or("Item not found")?;
+    if inv.owner_identity != ctx.sender {
+        return Err("Not your item".into());
+    }
+    let card = ctx.db.player_card().id().find(card_id)
+        .ok_or("Card not found")?;
+    if card.owner_identity != ctx.sender {
+        return Err("Not your card".into());
+    }
+
+    let mut item = inv.item.clone()
+        .ok_or("Item has not been migrated to a typed item")?;
+    if let Some(existing) = &item.card {
+        return Err(format!("{} already has a {} card socketed", item.name, existing.card_type));
+    }
+    item.card = Some(CardSocket { card_type: card.card_type.clone() });
+
+    let was_equipped = inv.equipped_slot.is_some();
+    ctx.db.player_card().id().delete(card_id);
+    ctx.db.inventory_item().id().update(InventoryItem {
+        item: Some(item),
+        ..inv
+    });
+    if was_equipped {
+        recompute_player_stats(ctx, ctx.sender);
+    }
+    Ok(())
+}
+
+/// Remove a socketed card for a gold cost, returning it to the card inventory
+#[reducer]
+pub fn unsocket_card(ctx: &ReducerContext, item_id: u64) -> Result<(), String> {
+    let inv = ctx.db.inventory_item().id().find(item_id)
+        .ok_or("Item not found")?;
+    if inv.owner_identity != ctx.sender {
+        return Err("Not your item".into());
+    }
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+
+    let mut item = inv.item.clone()
+        .ok_or("Item has not been migrated to a typed item")?;
+    let card = item.card.take()
+        .ok_or("Item has no card socketed")?;
+    let cost = UNSOCKET_BASE_COST + UNSOCKET_COST_PER_ILVL * item.item_level as u64;
+    if player.gold < cost {
+        return Err(format!("Unsocketing costs {} gold (you have {})", cost, player.gold));
+    }
+
+    ctx.db.player().identity().update(Player {
+        gold: player.gold - cost,
+        ..player
+    });
+    ctx.db.player_card().insert(PlayerCard {
+        id: 0,
+        owner_identity: ctx.sender,
+        card_type: card.card_type,
+    });
+    let was_equipped = inv.equipped_slot.is_some();
+    ctx.db.inventory_item().id().update(InventoryItem {
+        item: Some(item),
+        ..inv
+    });
+    if was_equipped {
+        recompute_player_stats(ctx, ctx.sender);
+    }
     Ok(())
 }
 
@@ -1348,7 +2566,11 @@ pub fn discard_item(ctx: &ReducerContext, item_id: u64) -> Result<(), String> {
     if item.owner_identity != ctx.sender {
         return Err("Not your item".into());
     }
+    let was_equipped = item.equipped_slot.is_some();
     ctx.db.inventory_item().id().delete(item_id);
+    if was_equipped {
+        recompute_player_stats(ctx, ctx.sender);
+    }
     Ok(())
 }
 
@@ -1428,8 +2650,12 @@ pub fn send_chat(ctx: &ReducerContext, dungeon_id: u64, text: String) -> Result<
 pub fn tick_enemies(ctx: &ReducerContext, _arg: EnemyTickSchedule) {
     let dt = AI_DT;
 
-    // Collect all player positions
-    let positions: Vec<PlayerPosition> = ctx.db.player_position().iter().collect();
+    // Collect positions of players still standing; downed players and raid members
+    // waiting to reconnect aren't targeted
+    let positions: Vec<PlayerPosition> = ctx.db.player_position().iter()
+        .filter(|p| ctx.db.player().identity().find(p.identity).is_some_and(|pl| pl.hp > 0))
+        .filter(|p| !ctx.db.raid_participant().iter().any(|r| r.player_identity == p.identity && r.disconnected_at.is_some()))
+        .collect();
 
     // Collect all enemies for pack coordination
     let all_enemies: Vec<DungeonEnemy> = ctx.db.dungeon_enemy().iter().collect();
@@ -1440,11 +2666,48 @@ pub fn tick_enemies(ctx: &ReducerContext, _arg: EnemyTickSchedule) {
     // Tick healing zones
     tick_healing_zones(ctx, dt);
 
-    // Process each alive enemy
+    prune_combat_events(ctx);
+
+    // Each dungeon's RNG stream, written back after the tick
+    let mut rngs: std::collections::HashMap<u64, Rng> = std::collections::HashMap::new();
+
+    // Room tile maps, keyed by (dungeon_id, room_index). Rooms without a stored
+    // layout are open rectangles.
+    let layouts: std::collections::HashMap<(u64, u32), Vec<Tile>> = ctx.db.room_tile_map().iter()
+        .map(|m| ((m.dungeon_id, m.room_index), m.tiles))
+        .collect();
+    let open_room = room_layout::open_room();
+
+    // Flow fields shared by every enemy chasing the same player in the same room
+    let mut flow_fields: std::collections::HashMap<(u64, u32, Identity), FlowField> = std::collections::HashMap::new();
+
+    // Raid mechanics by boss type, read once per tick for the first boss of each type
+    let mut boss_mechanics: std::collections::HashMap<String, Vec<RaidMechanic>> = std::collections::HashMap::new();
+
+    // Move projectiles fired on earlier ticks
+    tick_projectiles(ctx, dt, &layouts, &open_room, &mut rngs);
+
+    // Count down stuns, slows, DoTs and shields
+    tick_status_effects(ctx, dt, &mut rngs);
+
+    // Resolve telegraphed zones that ran out
+    tick_telegraph_zones(ctx, &positions, &mut rngs);
+
+    // Players stand in their dungeon's current room; enemies left behind in other
+    // rooms of a branching dungeon wait there until the party comes back
+    let current_rooms: std::collections::HashMap<u64, u32> = ctx.db.active_dungeon().iter()
+        .map(|d| (d.id, d.current_room))
+        .collect();
+
+    // Process each alive enemy in the room the party is in
     for enemy in ctx.db.dungeon_enemy().iter() {
-        if !enemy.is_alive {
+        if !enemy.is_alive || current_rooms.get(&enemy.dungeon_id) != Some(&enemy.room_index) {
             continue;
         }
+        // DoT ticks above may have killed it
+        let Some(enemy) = ctx.db.dungeon_enemy().id().find(enemy.id).filter(|e| e.is_alive) else {
+            continue;
+        };
 
         // Clone for modification
         let mut e = enemy.clone();
@@ -1489,30 +2752,57 @@ pub fn tick_enemies(ctx: &ReducerContext, _arg: EnemyTickSchedule) {
         // Store current target identity for rendering
         e.current_target = Some(target.identity.to_string());
 
-        // Tank slow aura: enemies within 50px of any tank move at 70% speed
-        let tank_nearby = positions.iter().any(|p| {
-            if p.dungeon_id != e.dungeon_id || p.player_class != "tank" {
-                return false;
-            }
-            let dist = ((p.x - e.x).powi(2) + (p.y - e.y).powi(2)).sqrt();
-            dist <= 50.0
+        // Tank slow aura: enemies near a tank are slowed for as long as they stay close
+        let tank_nearby = positions.iter().find(|p| {
+            p.dungeon_id == e.dungeon_id
+                && p.player_class == "tank"
+                && ((p.x - e.x).powi(2) + (p.y - e.y).powi(2)).sqrt() <= TANK_AURA_RADIUS
         });
-        let speed_mult = if tank_nearby { 0.7 } else { 1.0 };
+        if let Some(tank) = tank_nearby {
+            apply_status(ctx, e.dungeon_id, Combatant::Enemy(e.id), StatusKind::Slow, TANK_AURA_SLOW_PCT, dt * 2.0, Combatant::Player(tank.identity));
+        }
+
+        // Stunned enemies hold still
+        if has_status(ctx, Combatant::Enemy(e.id), StatusKind::Stun) {
+            ctx.db.dungeon_enemy().id().update(e);
+            continue;
+        }
+        let slow = strongest_status(ctx, Combatant::Enemy(e.id), StatusKind::Slow).clamp(0, 90);
+        let speed_mult = (100 - slow) as f32 / 100.0;
 
         let dx = target.x - e.x;
         let dy = target.y - e.y;
         let dist = (dx * dx + dy * dy).sqrt();
         let (nx, ny) = if dist > 0.1 { (dx / dist, dy / dist) } else { (0.0, 0.0) };
 
-        match e.enemy_type.as_str() {
-            "charger" => ai_charger(&mut e, target, dx, dy, dist, nx, ny, dt * speed_mult, ctx),
-            "wolf" => ai_wolf(&mut e, target, dx, dy, dist, dt * speed_mult, &all_enemies, ctx),
-            "necromancer" => ai_necromancer(&mut e, target, dx, dy, dist, nx, ny, dt),
-            "bomber" => ai_bomber(&mut e, target, dx, dy, dist, nx, ny, dt * speed_mult, ctx),
-            "shield_knight" => ai_shield_knight(&mut e, target, dx, dy, dist, nx, ny, dt * speed_mult, ctx),
-            "archer" => ai_archer(&mut e, target, dx, dy, dist, nx, ny, dt, ctx),
-            "raid_boss" => ai_raid_boss(&mut e, target, dx, dy, dist, nx, ny, dt, ctx, &positions),
-            _ => ai_basic_melee(&mut e, target, dx, dy, dist, nx, ny, dt * speed_mult, ctx),
+        // Pathfinding and collision use the party's room, which is this enemy's room
+        let tiles = layouts.get(&(e.dungeon_id, e.room_index)).unwrap_or(&open_room);
+        let flow = &*flow_fields.entry((e.dungeon_id, e.room_index, target.identity))
+            .or_insert_with(|| FlowField::toward(tiles, target.x, target.y));
+        let (prev_x, prev_y) = (e.x, e.y);
+
+        // Combat rolls and teleports draw from the dungeon's stream
+        let rng = rngs.entry(e.dungeon_id)
+            .or_insert_with(|| dungeon_rng(ctx, e.dungeon_id));
+
+        match enemy_definition(ctx, &e.enemy_type).ai {
+            AiArchetype::Charger => ai_charger(&mut e, dist, nx, ny, dt * speed_mult, ctx, tiles),
+            AiArchetype::Wolf => ai_wolf(&mut e, target, dx, dy, dist, dt * speed_mult, &all_enemies, ctx, rng, flow),
+            AiArchetype::Necromancer => ai_necromancer(&mut e, target, dx, dy, dist, nx, ny, dt, ctx, rng, tiles),
+            AiArchetype::Bomber => ai_bomber(&mut e, dist, nx, ny, dt * speed_mult, ctx),
+            AiArchetype::ShieldKnight => ai_shield_knight(&mut e, target, dx, dy, dist, nx, ny, dt * speed_mult, ctx, rng, flow),
+            AiArchetype::Archer => ai_archer(&mut e, target, dx, dy, dist, nx, ny, dt, ctx, tiles, flow),
+            AiArchetype::RaidBoss => {
+                let mechanics = boss_mechanics.entry(e.enemy_type.clone())
+                    .or_insert_with(|| ctx.db.raid_mechanic().iter().filter(|m| m.boss_type == e.enemy_type).collect());
+                ai_raid_boss(&mut e, target, dist, nx, ny, dt, ctx, rng, &positions, tiles, mechanics)
+            }
+            AiArchetype::BasicMelee => ai_basic_melee(&mut e, target, dx, dy, dist, nx, ny, dt * speed_mult, ctx, rng, flow),
+        }
+
+        // Collide the move against the room layout (teleports land on valid tiles already)
+        if e.ai_state != "teleport" {
+            (e.x, e.y) = room_layout::slide_move(tiles, (prev_x, prev_y), (e.x, e.y));
         }
 
         // Clamp position to room bounds
@@ -1520,16 +2810,39 @@ pub fn tick_enemies(ctx: &ReducerContext, _arg: EnemyTickSchedule) {
         e.y = e.y.clamp(TILE_SIZE, ROOM_H - TILE_SIZE);
 
         // Update the enemy in the database
+        let died = (!e.is_alive).then_some((e.dungeon_id, e.room_index));
         ctx.db.dungeon_enemy().id().update(e);
+        // Bombers blow themselves up without going through `kill_dungeon_enemy`
+        if let Some((dungeon_id, room_index)) = died {
+            check_room_clear(ctx, dungeon_id, room_index);
+        }
+    }
+
+    for (dungeon_id, rng) in rngs {
+        store_dungeon_rng(ctx, dungeon_id, &rng);
     }
+
+    // Mirror raid bosses into their raid instance and end cleared raids
+    tick_raids(ctx);
+
+    // Down players who hit 0 HP this tick, advance revives, and end wiped runs
+    tick_downed_players(ctx, dt);
     // Note: No need to reschedule - ScheduleAt::Interval auto-repeats
 }
 
 // ─── AI Functions ──────────────────────────────────────────────────────────────
 
+/// Direction away from the target toward a reachable retreat point, falling
+/// back to straight away from the target
+fn retreat_direction(tiles: &[Tile], e: &DungeonEnemy, target: &PlayerPosition, nx: f32, ny: f32) -> (f32, f32) {
+    pathfinding::retreat_step(tiles, (e.x, e.y), (target.x, target.y), RETREAT_SEARCH_STEPS)
+        .unwrap_or((-nx, -ny))
+}
+
 /// Basic melee AI (skeleton, slime, bat): chase → attack → chase
-fn ai_basic_melee(e: &mut DungeonEnemy, target: &PlayerPosition, _dx: f32, _dy: f32, dist: f32, nx: f32, ny: f32, dt: f32, ctx: &ReducerContext) {
-    let speed = get_enemy_speed(&e.enemy_type) * dt * 60.0; // Scale to 60fps equivalent
+#[allow(clippy::too_many_arguments)]
+fn ai_basic_melee(e: &mut DungeonEnemy, target: &PlayerPosition, _dx: f32, _dy: f32, dist: f32, nx: f32, ny: f32, dt: f32, ctx: &ReducerContext, rng: &mut Rng, flow: &FlowField) {
+    let speed = get_enemy_speed(ctx, &e.enemy_type) * dt * 60.0; // Scale to 60fps equivalent
 
     // Update facing
     e.facing_angle = ny.atan2(nx);
@@ -1546,26 +2859,21 @@ fn ai_basic_melee(e: &mut DungeonEnemy, target: &PlayerPosition, _dx: f32, _dy:
             e.ai_state = "attack".to_string();
 
             // Deal damage to player
-            if let Some(player) = ctx.db.player().identity().find(target.identity) {
-                let damage = (e.atk - player.def / 2).max(1);
-                let new_hp = player.hp - damage;
-                ctx.db.player().identity().update(Player {
-                    hp: new_hp.max(0),
-                    ..player
-                });
-            }
+            hit_player(ctx, rng, e.dungeon_id, Combatant::Enemy(e.id), &enemy_attacker(ctx, e), target.identity, (target.x, target.y), e.atk, DamageType::Physical);
         }
     } else {
-        // Chase
+        // Chase along the flow field
         e.ai_state = "chase".to_string();
-        e.x += nx * speed;
-        e.y += ny * speed;
+        let (mx, my) = flow.direction(e.x, e.y).unwrap_or((nx, ny));
+        e.x += mx * speed;
+        e.y += my * speed;
     }
 }
 
-/// Charger AI: chase → telegraph → charge → stunned
-fn ai_charger(e: &mut DungeonEnemy, target: &PlayerPosition, dx: f32, dy: f32, dist: f32, nx: f32, ny: f32, dt: f32, ctx: &ReducerContext) {
-    let base_speed = get_enemy_speed(&e.enemy_type) * dt * 60.0;
+/// Charger AI: chase → telegraph → charge → stunned. The charge lane is a
+/// telegraph zone that hits whoever is still in it when the charge goes.
+fn ai_charger(e: &mut DungeonEnemy, dist: f32, nx: f32, ny: f32, dt: f32, ctx: &ReducerContext, tiles: &[Tile]) {
+    let base_speed = get_enemy_speed(ctx, &e.enemy_type) * dt * 60.0;
 
     match e.ai_state.as_str() {
         "stunned" => {
@@ -1577,17 +2885,6 @@ fn ai_charger(e: &mut DungeonEnemy, target: &PlayerPosition, dx: f32, dy: f32, d
         }
         "telegraph" => {
             e.state_timer -= dt;
-            // Lock charge direction at the start
-            if e.state_timer > CHARGER_TELEGRAPH_TIME - 0.1 {
-                e.target_x = dx;
-                e.target_y = dy;
-                let mag = (dx * dx + dy * dy).sqrt();
-                if mag > 0.1 {
-                    e.target_x /= mag;
-                    e.target_y /= mag;
-                }
-                e.facing_angle = e.target_y.atan2(e.target_x);
-            }
             if e.state_timer <= 0.0 {
                 e.ai_state = "charge".to_string();
                 e.state_timer = CHARGER_CHARGE_DURATION;
@@ -1599,30 +2896,19 @@ fn ai_charger(e: &mut DungeonEnemy, target: &PlayerPosition, dx: f32, dy: f32, d
             let new_x = e.x + e.target_x * charge_speed;
             let new_y = e.y + e.target_y * charge_speed;
 
-            // Check wall collision (simple bounds check)
-            if new_x < TILE_SIZE || new_x > ROOM_W - TILE_SIZE ||
-               new_y < TILE_SIZE || new_y > ROOM_H - TILE_SIZE {
-                // Hit wall → stunned
+            // Check collision against the room layout
+            let tile = room_layout::tile_at(tiles, new_x, new_y);
+            if tile.is_solid() {
+                // Hit wall or pillar → stunned
                 e.ai_state = "stunned".to_string();
                 e.state_timer = CHARGER_STUN_TIME;
+            } else if !tile.is_walkable() {
+                // Pulls up at the edge of a pit
+                e.ai_state = "idle".to_string();
+                e.state_timer = 0.0;
             } else {
                 e.x = new_x;
                 e.y = new_y;
-
-                // Hit player while charging - deal damage!
-                let player_dist = ((target.x - e.x).powi(2) + (target.y - e.y).powi(2)).sqrt();
-                if player_dist < 30.0 {
-                    e.ai_state = "stunned".to_string();
-                    e.state_timer = CHARGER_STUN_TIME;
-                    // Deal charge damage to player
-                    if let Some(player) = ctx.db.player().identity().find(target.identity) {
-                        let damage = ((e.atk as f32 * 1.5) as i32 - player.def / 2).max(1);
-                        ctx.db.player().identity().update(Player {
-                            hp: (player.hp - damage).max(0),
-                            ..player
-                        });
-                    }
-                }
             }
 
             if e.state_timer <= 0.0 {
@@ -1640,17 +2926,29 @@ fn ai_charger(e: &mut DungeonEnemy, target: &PlayerPosition, dx: f32, dy: f32, d
             }
 
             e.state_timer -= dt;
-            if e.state_timer <= 0.0 && dist < CHARGER_DETECT_RANGE {
+            if e.state_timer <= 0.0 && dist < CHARGER_DETECT_RANGE && (nx, ny) != (0.0, 0.0) {
                 e.ai_state = "telegraph".to_string();
                 e.state_timer = CHARGER_TELEGRAPH_TIME;
+
+                // Lock the charge direction and mark the lane up to the first obstacle
+                e.target_x = nx;
+                e.target_y = ny;
+                let reach = get_enemy_speed(ctx, &e.enemy_type) * 60.0 * CHARGER_CHARGE_SPEED_MULT * CHARGER_CHARGE_DURATION;
+                let end = room_layout::sweep(tiles, (e.x, e.y), (e.x + nx * reach, e.y + ny * reach));
+                ctx.db.telegraph_zone().insert(TelegraphZone {
+                    radius: ((end.0 - e.x).powi(2) + (end.1 - e.y).powi(2)).sqrt(),
+                    width: CHARGER_LANE_WIDTH,
+                    ..new_zone(ctx, e, ZoneShape::Line, (e.x, e.y), CHARGER_TELEGRAPH_TIME, (e.atk as f32 * 1.5) as i32, DamageType::Physical)
+                });
             }
         }
     }
 }
 
 /// Wolf AI: orbit around player in pack formation, attack together
-fn ai_wolf(e: &mut DungeonEnemy, target: &PlayerPosition, _dx: f32, _dy: f32, dist: f32, dt: f32, all_enemies: &[DungeonEnemy], ctx: &ReducerContext) {
-    let speed = get_enemy_speed(&e.enemy_type) * dt * 60.0;
+#[allow(clippy::too_many_arguments)]
+fn ai_wolf(e: &mut DungeonEnemy, target: &PlayerPosition, _dx: f32, _dy: f32, dist: f32, dt: f32, all_enemies: &[DungeonEnemy], ctx: &ReducerContext, rng: &mut Rng, flow: &FlowField) {
+    let speed = get_enemy_speed(ctx, &e.enemy_type) * dt * 60.0;
 
     // Count pack members
     let pack_members: Vec<&DungeonEnemy> = all_enemies.iter()
@@ -1674,8 +2972,14 @@ fn ai_wolf(e: &mut DungeonEnemy, target: &PlayerPosition, _dx: f32, _dy: f32, di
     let tdist = (tdx * tdx + tdy * tdy).sqrt();
 
     if tdist > 5.0 {
-        e.x += (tdx / tdist) * speed;
-        e.y += (tdy / tdist) * speed;
+        // Head straight for the orbit slot if nothing is in the way, else path to the player
+        let (mx, my) = if flow.line_clear((e.x, e.y), (orbit_x, orbit_y)) {
+            (tdx / tdist, tdy / tdist)
+        } else {
+            flow.direction(e.x, e.y).unwrap_or((tdx / tdist, tdy / tdist))
+        };
+        e.x += mx * speed;
+        e.y += my * speed;
     }
 
     // Face the player
@@ -1696,13 +3000,7 @@ fn ai_wolf(e: &mut DungeonEnemy, target: &PlayerPosition, _dx: f32, _dy: f32, di
         if e.target_x <= 0.0 {
             // target_x is used as attack cooldown for wolf
             e.target_x = 1.5; // Attack cooldown
-            if let Some(player) = ctx.db.player().identity().find(target.identity) {
-                let damage = (e.atk - player.def / 2).max(1);
-                ctx.db.player().identity().update(Player {
-                    hp: (player.hp - damage).max(0),
-                    ..player
-                });
-            }
+            hit_player(ctx, rng, e.dungeon_id, Combatant::Enemy(e.id), &enemy_attacker(ctx, e), target.identity, (target.x, target.y), e.atk, DamageType::Physical);
         } else {
             e.target_x -= dt;
         }
@@ -1712,8 +3010,9 @@ fn ai_wolf(e: &mut DungeonEnemy, target: &PlayerPosition, _dx: f32, _dy: f32, di
 }
 
 /// Necromancer AI: flee → teleport → summon
-fn ai_necromancer(e: &mut DungeonEnemy, _target: &PlayerPosition, _dx: f32, _dy: f32, dist: f32, nx: f32, ny: f32, dt: f32) {
-    let speed = get_enemy_speed(&e.enemy_type) * dt * 60.0;
+#[allow(clippy::too_many_arguments)]
+fn ai_necromancer(e: &mut DungeonEnemy, target: &PlayerPosition, _dx: f32, _dy: f32, dist: f32, nx: f32, ny: f32, dt: f32, ctx: &ReducerContext, rng: &mut Rng, tiles: &[Tile]) {
+    let speed = get_enemy_speed(ctx, &e.enemy_type) * dt * 60.0;
 
     e.facing_angle = ny.atan2(nx);
     e.state_timer -= dt;
@@ -1722,32 +3021,82 @@ fn ai_necromancer(e: &mut DungeonEnemy, _target: &PlayerPosition, _dx: f32, _dy:
         // Too close - flee or teleport
         if e.state_timer <= 0.0 {
             // Teleport to random position away from player
-            e.target_x = TILE_SIZE * 2.0 + (e.id as f32 * 1.7).sin().abs() * (ROOM_W - TILE_SIZE * 4.0);
-            e.target_y = TILE_SIZE * 3.0 + (e.id as f32 * 2.3).cos().abs() * (ROOM_H - TILE_SIZE * 6.0);
+            (e.target_x, e.target_y) = room_layout::random_walkable(tiles, rng);
             e.x = e.target_x;
             e.y = e.target_y;
             e.ai_state = "teleport".to_string();
             e.state_timer = NECRO_TELEPORT_CD;
         } else {
-            // Move away from player
+            // Move away from player toward a reachable retreat point
             e.ai_state = "flee".to_string();
-            e.x -= nx * speed;
-            e.y -= ny * speed;
+            let (mx, my) = retreat_direction(tiles, e, target, nx, ny);
+            e.x += mx * speed;
+            e.y += my * speed;
         }
     } else if dist < 150.0 {
         // Maintain distance - move away slowly
         e.ai_state = "flee".to_string();
-        e.x -= nx * speed * 0.5;
-        e.y -= ny * speed * 0.5;
+        let (mx, my) = retreat_direction(tiles, e, target, nx, ny);
+        e.x += mx * speed * 0.5;
+        e.y += my * speed * 0.5;
     } else {
-        // Safe distance - can summon
+        // Safe distance - raise a skeleton when the cast timer is ready
         e.ai_state = "summon".to_string();
+        if e.state_timer <= 0.0 {
+            let live_summons = ctx.db.dungeon_enemy().iter()
+                .filter(|m| m.summoner_id == Some(e.id) && m.is_alive)
+                .count();
+            if live_summons < NECRO_MAX_SUMMONS {
+                let angle = rng.next_f32() * std::f32::consts::TAU;
+                spawn_minion(ctx, e, "skeleton", angle, tiles);
+                e.state_timer = NECRO_SUMMON_CD;
+            }
+        }
     }
 }
 
-/// Bomber AI: chase → fuse → explode
-fn ai_bomber(e: &mut DungeonEnemy, _target: &PlayerPosition, _dx: f32, _dy: f32, dist: f32, nx: f32, ny: f32, dt: f32, ctx: &ReducerContext) {
-    let speed = get_enemy_speed(&e.enemy_type) * dt * 60.0;
+/// Spawn a minion next to its summoner. Minions use base stats and are tied to
+/// the summoner through `summoner_id`.
+fn spawn_minion(ctx: &ReducerContext, summoner: &DungeonEnemy, enemy_type: &str, angle: f32, tiles: &[Tile]) -> DungeonEnemy {
+    let (hp, atk, def) = get_enemy_stats(ctx, enemy_type, 1);
+    let (x, y) = room_layout::nearest_walkable(
+        tiles,
+        summoner.x + angle.cos() * 50.0,
+        summoner.y + angle.sin() * 50.0,
+    );
+    ctx.db.dungeon_enemy().insert(DungeonEnemy {
+        id: 0,
+        dungeon_id: summoner.dungeon_id,
+        room_index: summoner.room_index,
+        enemy_type: enemy_type.to_string(),
+        x,
+        y,
+        hp,
+        max_hp: hp,
+        atk,
+        is_alive: true,
+        ai_state: "chase".to_string(),
+        state_timer: 0.0,
+        target_x: summoner.x,
+        target_y: summoner.y,
+        facing_angle: angle,
+        pack_id: None,
+        current_target: None,
+        is_taunted: false,
+        taunted_by: None,
+        taunt_timer: 0.0,
+        is_boss: false,
+        boss_phase: 0,
+        summoner_id: Some(summoner.id),
+        def,
+        is_priority: false,
+    })
+}
+
+/// Bomber AI: chase → fuse → explode. The blast is a telegraph zone placed when
+/// the fuse is lit.
+fn ai_bomber(e: &mut DungeonEnemy, dist: f32, nx: f32, ny: f32, dt: f32, ctx: &ReducerContext) {
+    let speed = get_enemy_speed(ctx, &e.enemy_type) * dt * 60.0;
 
     e.facing_angle = ny.atan2(nx);
 
@@ -1755,26 +3104,9 @@ fn ai_bomber(e: &mut DungeonEnemy, _target: &PlayerPosition, _dx: f32, _dy: f32,
         "fuse" => {
             e.state_timer -= dt;
             if e.state_timer <= 0.0 {
-                // EXPLODE - damage nearby players
+                // EXPLODE - the blast zone placed with the fuse deals the damage
                 e.ai_state = "explode".to_string();
 
-                // Damage all players in explosion radius
-                for pos in ctx.db.player_position().iter() {
-                    if pos.dungeon_id == e.dungeon_id {
-                        let exp_dist = ((pos.x - e.x).powi(2) + (pos.y - e.y).powi(2)).sqrt();
-                        if exp_dist < BOMBER_EXPLOSION_RADIUS {
-                            if let Some(player) = ctx.db.player().identity().find(pos.identity) {
-                                let damage = (e.atk - player.def / 2).max(1);
-                                let new_hp = player.hp - damage;
-                                ctx.db.player().identity().update(Player {
-                                    hp: new_hp.max(0),
-                                    ..player
-                                });
-                            }
-                        }
-                    }
-                }
-
                 // Kill self (mark for death, will be processed separately)
                 e.hp = 0;
                 e.is_alive = false;
@@ -1788,6 +3120,10 @@ fn ai_bomber(e: &mut DungeonEnemy, _target: &PlayerPosition, _dx: f32, _dy: f32,
             if dist < BOMBER_TRIGGER_RANGE {
                 e.ai_state = "fuse".to_string();
                 e.state_timer = BOMBER_FUSE_TIME;
+                ctx.db.telegraph_zone().insert(TelegraphZone {
+                    radius: BOMBER_EXPLOSION_RADIUS,
+                    ..new_zone(ctx, e, ZoneShape::Circle, (e.x, e.y), BOMBER_FUSE_TIME, e.atk, DamageType::Fire)
+                });
             } else {
                 e.ai_state = "chase".to_string();
                 e.x += nx * speed;
@@ -1798,8 +3134,9 @@ fn ai_bomber(e: &mut DungeonEnemy, _target: &PlayerPosition, _dx: f32, _dy: f32,
 }
 
 /// Shield Knight AI: advance → shield_bash → recover
-fn ai_shield_knight(e: &mut DungeonEnemy, target: &PlayerPosition, _dx: f32, _dy: f32, dist: f32, nx: f32, ny: f32, dt: f32, ctx: &ReducerContext) {
-    let speed = get_enemy_speed(&e.enemy_type) * dt * 60.0;
+#[allow(clippy::too_many_arguments)]
+fn ai_shield_knight(e: &mut DungeonEnemy, target: &PlayerPosition, _dx: f32, _dy: f32, dist: f32, nx: f32, ny: f32, dt: f32, ctx: &ReducerContext, rng: &mut Rng, flow: &FlowField) {
+    let speed = get_enemy_speed(ctx, &e.enemy_type) * dt * 60.0;
 
     // Shield always faces player
     e.facing_angle = ny.atan2(nx);
@@ -1814,13 +3151,9 @@ fn ai_shield_knight(e: &mut DungeonEnemy, target: &PlayerPosition, _dx: f32, _dy
 
                 // Damage player if in range
                 if dist < 50.0 {
-                    if let Some(player) = ctx.db.player().identity().find(target.identity) {
-                        let damage = ((e.atk as f32 * 0.5) as i32 - player.def / 2).max(1);
-                        let new_hp = player.hp - damage;
-                        ctx.db.player().identity().update(Player {
-                            hp: new_hp.max(0),
-                            ..player
-                        });
+                    let dealt = hit_player(ctx, rng, e.dungeon_id, Combatant::Enemy(e.id), &enemy_attacker(ctx, e), target.identity, (target.x, target.y), (e.atk as f32 * 0.5) as i32, DamageType::Physical);
+                    if dealt > 0 {
+                        apply_status(ctx, e.dungeon_id, Combatant::Player(target.identity), StatusKind::Stun, 0, SHIELD_BASH_STUN_TIME, Combatant::Enemy(e.id));
                     }
                 }
             }
@@ -1832,10 +3165,11 @@ fn ai_shield_knight(e: &mut DungeonEnemy, target: &PlayerPosition, _dx: f32, _dy
             }
         }
         _ => {
-            // Advance toward player
+            // Advance toward player along the flow field
             if dist > ENEMY_ATTACK_RANGE {
-                e.x += nx * speed;
-                e.y += ny * speed;
+                let (mx, my) = flow.direction(e.x, e.y).unwrap_or((nx, ny));
+                e.x += mx * speed;
+                e.y += my * speed;
             }
 
             // Start bash if cooldown ready and in range
@@ -1847,22 +3181,16 @@ fn ai_shield_knight(e: &mut DungeonEnemy, target: &PlayerPosition, _dx: f32, _dy
             // Regular attack
             if dist < ENEMY_ATTACK_RANGE && e.state_timer <= -1.0 {
                 e.state_timer = -2.5; // Attack cooldown (negative to distinguish from bash)
-                if let Some(player) = ctx.db.player().identity().find(target.identity) {
-                    let damage = (e.atk - player.def / 2).max(1);
-                    let new_hp = player.hp - damage;
-                    ctx.db.player().identity().update(Player {
-                        hp: new_hp.max(0),
-                        ..player
-                    });
-                }
+                hit_player(ctx, rng, e.dungeon_id, Combatant::Enemy(e.id), &enemy_attacker(ctx, e), target.identity, (target.x, target.y), e.atk, DamageType::Physical);
             }
         }
     }
 }
 
 /// Archer AI: kite → shoot → kite
-fn ai_archer(e: &mut DungeonEnemy, target: &PlayerPosition, _dx: f32, _dy: f32, dist: f32, nx: f32, ny: f32, dt: f32, ctx: &ReducerContext) {
-    let speed = get_enemy_speed(&e.enemy_type) * dt * 60.0;
+#[allow(clippy::too_many_arguments)]
+fn ai_archer(e: &mut DungeonEnemy, target: &PlayerPosition, _dx: f32, _dy: f32, dist: f32, nx: f32, ny: f32, dt: f32, ctx: &ReducerContext, tiles: &[Tile], flow: &FlowField) {
+    let speed = get_enemy_speed(ctx, &e.enemy_type) * dt * 60.0;
 
     e.facing_angle = ny.atan2(nx);
     e.state_timer -= dt;
@@ -1870,427 +3198,1796 @@ fn ai_archer(e: &mut DungeonEnemy, target: &PlayerPosition, _dx: f32, _dy: f32,
     // Kite - maintain distance
     if dist < ARCHER_KITE_DISTANCE {
         e.ai_state = "kite".to_string();
-        e.x -= nx * speed;
-        e.y -= ny * speed;
+        let (mx, my) = retreat_direction(tiles, e, target, nx, ny);
+        e.x += mx * speed;
+        e.y += my * speed;
     } else if dist < ARCHER_SHOOT_RANGE {
         // In shoot range
         if e.state_timer <= 0.0 {
             e.ai_state = "shoot".to_string();
             e.state_timer = ARCHER_SHOOT_CD;
-            // Store target position for projectile (client will render)
+            // Aim point, kept for the client's draw animation
             e.target_x = target.x;
             e.target_y = target.y;
-            // Deal arrow damage (instant hit for simplicity)
-            if let Some(player) = ctx.db.player().identity().find(target.identity) {
-                let damage = (e.atk - player.def / 2).max(1);
-                ctx.db.player().identity().update(Player {
-                    hp: (player.hp - damage).max(0),
-                    ..player
-                });
+            // Loose an arrow; it can be dodged while in flight
+            fire_projectile(ctx, e, "arrow", (nx, ny), ARROW_SPEED, e.atk, ARROW_RADIUS, ARCHER_SHOOT_RANGE * 1.5 / ARROW_SPEED);
+        } else {
+            e.ai_state = "kite".to_string();
+        }
+    } else {
+        // Too far, approach
+        e.ai_state = "chase".to_string();
+        let (mx, my) = flow.direction(e.x, e.y).unwrap_or((nx, ny));
+        e.x += mx * speed * 0.5;
+        e.y += my * speed * 0.5;
+    }
+}
+
+/// Raid Boss AI: chase and melee the threat target while the boss's scripted
+/// mechanics from `raid_mechanic` run on their own timers
+#[allow(clippy::too_many_arguments)]
+fn ai_raid_boss(e: &mut DungeonEnemy, target: &PlayerPosition, dist: f32, nx: f32, ny: f32, dt: f32, ctx: &ReducerContext, rng: &mut Rng, all_positions: &[PlayerPosition], tiles: &[Tile], mechanics: &[RaidMechanic]) {
+    let speed = RAID_BOSS_SPEED * dt * 60.0; // Slow but menacing
+
+    e.facing_angle = ny.atan2(nx);
+    e.state_timer -= dt;
+
+    if dist <= ENEMY_ATTACK_RANGE + 15.0 {
+        if e.state_timer <= 0.0 {
+            e.state_timer = RAID_BOSS_ATTACK_CD;
+            e.ai_state = "attack".to_string();
+            hit_player(ctx, rng, e.dungeon_id, Combatant::Enemy(e.id), &enemy_attacker(ctx, e), target.identity, (target.x, target.y), e.atk, DamageType::Physical);
+        }
+    } else {
+        e.ai_state = "chase".to_string();
+        e.x += nx * speed;
+        e.y += ny * speed;
+    }
+
+    tick_boss_mechanics(e, target, dt, ctx, rng, all_positions, tiles, mechanics);
+}
+
+/// Count down a boss's mechanic timers and fire the ones that are due. Each of the
+/// boss type's `raid_mechanic` rows gets a timer the first tick it has none, so
+/// mechanics added mid-fight join in; HP thresholds crossed so far set the boss phase.
+#[allow(clippy::too_many_arguments)]
+fn tick_boss_mechanics(e: &mut DungeonEnemy, target: &PlayerPosition, dt: f32, ctx: &ReducerContext, rng: &mut Rng, all_positions: &[PlayerPosition], tiles: &[Tile], mechanics: &[RaidMechanic]) {
+    let mut timers: Vec<BossMechanicTimer> = ctx.db.boss_mechanic_timer().iter()
+        .filter(|t| t.enemy_id == e.id)
+        .collect();
+    for m in mechanics {
+        if !timers.iter().any(|t| t.mechanic_id == m.id) {
+            timers.push(ctx.db.boss_mechanic_timer().insert(BossMechanicTimer {
+                id: 0,
+                dungeon_id: e.dungeon_id,
+                enemy_id: e.id,
+                mechanic_id: m.id,
+                armed: false,
+                remaining: m.start,
+                done: false,
+            }));
+        }
+    }
+
+    let hp_pct = (e.hp.max(0) as u64 * 100 / e.max_hp.max(1) as u64) as u32;
+    let mut thresholds: Vec<u32> = mechanics.iter()
+        .map(|m| m.hp_pct)
+        .filter(|&pct| pct < 100 && hp_pct <= pct)
+        .collect();
+    thresholds.sort_unstable();
+    thresholds.dedup();
+    e.boss_phase = 1 + thresholds.len() as u32;
+
+    // Priority adds keep mending the boss until the party kills them
+    let menders = ctx.db.dungeon_enemy().iter()
+        .filter(|m| m.summoner_id == Some(e.id) && m.is_alive && m.is_priority)
+        .count();
+    if menders > 0 {
+        let mend = (e.max_hp as f32 * BOSS_MEND_PCT / 100.0 * dt).ceil() as i32 * menders as i32;
+        e.hp = (e.hp + mend).min(e.max_hp);
+    }
+
+    for mut timer in timers.into_iter().filter(|t| !t.done) {
+        let Some(m) = mechanics.iter().find(|m| m.id == timer.mechanic_id) else {
+            continue;
+        };
+        if !timer.armed {
+            if hp_pct > m.hp_pct {
+                continue;
+            }
+            timer.armed = true;
+        }
+        timer.remaining -= dt;
+        if timer.remaining <= 0.0 {
+            fire_boss_mechanic(e, m, target, ctx, rng, all_positions, tiles);
+            if m.interval > 0.0 {
+                timer.remaining += m.interval;
+            } else {
+                timer.done = true;
+            }
+        }
+        ctx.db.boss_mechanic_timer().id().update(timer);
+    }
+}
+
+/// Carry out one boss mechanic
+fn fire_boss_mechanic(e: &mut DungeonEnemy, m: &RaidMechanic, target: &PlayerPosition, ctx: &ReducerContext, rng: &mut Rng, all_positions: &[PlayerPosition], tiles: &[Tile]) {
+    log::info!("Boss {} uses {}", e.id, m.name);
+    e.ai_state = m.kind.ai_state().to_string();
+    let damage = (e.atk * m.damage_pct / 100).max(1);
+    let raid: Vec<&PlayerPosition> = all_positions.iter()
+        .filter(|p| p.dungeon_id == e.dungeon_id)
+        .collect();
+    let place_circle = |x: f32, y: f32, follow: Option<Identity>| {
+        ctx.db.telegraph_zone().insert(TelegraphZone {
+            radius: m.radius,
+            shared: m.kind == MechanicKind::Stack,
+            follow,
+            ..new_zone(ctx, e, ZoneShape::Circle, (x, y), m.telegraph, damage, DamageType::Physical)
+        });
+    };
+
+    match m.kind {
+        MechanicKind::GroundAoe => {
+            for _ in 0..m.count.max(1) {
+                if raid.is_empty() {
+                    break;
+                }
+                let p = raid[rng.index(raid.len())];
+                place_circle(p.x, p.y, None);
+            }
+        }
+        MechanicKind::Spread => {
+            for p in &raid {
+                place_circle(p.x, p.y, Some(p.identity));
+            }
+        }
+        MechanicKind::Stack => {
+            if !raid.is_empty() {
+                let p = raid[rng.index(raid.len())];
+                place_circle(p.x, p.y, Some(p.identity));
+            }
+        }
+        MechanicKind::TankBuster => {
+            let dealt = hit_player(ctx, rng, e.dungeon_id, Combatant::Enemy(e.id), &enemy_attacker(ctx, e), target.identity, (target.x, target.y), damage, DamageType::Physical);
+            if dealt > 0 {
+                add_vulnerable_stack(ctx, e.dungeon_id, target.identity, e.id, m.count.max(1));
+            }
+        }
+        MechanicKind::Adds | MechanicKind::PriorityAdds => {
+            let count = m.count.max(1);
+            for i in 0..count {
+                let angle = (i as f32 / count as f32) * std::f32::consts::TAU;
+                let add = spawn_minion(ctx, e, &m.add_type, angle, tiles);
+                if m.kind == MechanicKind::PriorityAdds {
+                    ctx.db.dungeon_enemy().id().update(DungeonEnemy { is_priority: true, ..add });
+                }
+            }
+        }
+        MechanicKind::OrbRing => {
+            for i in 0..m.count {
+                let angle = (i as f32 / m.count as f32) * std::f32::consts::TAU;
+                fire_projectile(ctx, e, "boss_orb", (angle.cos(), angle.sin()), BOSS_ORB_SPEED, damage, BOSS_ORB_RADIUS, BOSS_ORB_LIFETIME);
+            }
+        }
+        MechanicKind::Pulse => {
+            // A zero radius covers the whole room
+            let radius = if m.radius > 0.0 { m.radius } else { ROOM_W + ROOM_H };
+            ctx.db.telegraph_zone().insert(TelegraphZone {
+                radius,
+                ..new_zone(ctx, e, ZoneShape::Circle, (e.x, e.y), m.telegraph, damage, DamageType::Fire)
+            });
+        }
+        MechanicKind::Cleave => {
+            ctx.db.telegraph_zone().insert(TelegraphZone {
+                radius: m.radius,
+                angle: (target.y - e.y).atan2(target.x - e.x),
+                arc: m.arc,
+                ..new_zone(ctx, e, ZoneShape::Cone, (e.x, e.y), m.telegraph, damage, DamageType::Physical)
+            });
+        }
+        MechanicKind::HardEnrage => {
+            for p in &raid {
+                hit_player(ctx, rng, e.dungeon_id, Combatant::Enemy(e.id), &enemy_attacker(ctx, e), p.identity, (p.x, p.y), damage, DamageType::Fire);
+            }
+        }
+        MechanicKind::SoftEnrage => {
+            e.atk = e.atk * (100 + m.damage_pct) / 100;
+        }
+    }
+}
+
+/// Add a Vulnerable stack from a boss, up to `max_stacks`, refreshing its duration
+fn add_vulnerable_stack(ctx: &ReducerContext, dungeon_id: u64, identity: Identity, boss_id: u64, max_stacks: u32) {
+    let target = Combatant::Player(identity);
+    let source = Combatant::Enemy(boss_id);
+    let existing = ctx.db.status_effect().iter()
+        .find(|fx| fx.target == target && fx.kind == StatusKind::Vulnerable && fx.source == source);
+    let cap = VULNERABLE_PCT_PER_STACK * max_stacks as i32;
+    match existing {
+        Some(fx) => {
+            ctx.db.status_effect().id().update(StatusEffect {
+                magnitude: (fx.magnitude + VULNERABLE_PCT_PER_STACK).min(cap),
+                remaining: VULNERABLE_TIME,
+                ..fx
+            });
+        }
+        None => apply_status(ctx, dungeon_id, target, StatusKind::Vulnerable, VULNERABLE_PCT_PER_STACK, VULNERABLE_TIME, source),
+    }
+}
+
+/// Zone from an enemy that resolves `delay` seconds from now. Callers fill in the
+/// shape's dimensions.
+fn new_zone(ctx: &ReducerContext, e: &DungeonEnemy, shape: ZoneShape, at: (f32, f32), delay: f32, damage: i32, damage_type: DamageType) -> TelegraphZone {
+    let now = ctx.timestamp.to_duration_since_unix_epoch()
+        .unwrap_or_default().as_millis() as u64;
+    TelegraphZone {
+        id: 0,
+        dungeon_id: e.dungeon_id,
+        room_index: e.room_index,
+        source_enemy_id: e.id,
+        shape,
+        x: at.0,
+        y: at.1,
+        radius: 0.0,
+        angle: e.facing_angle,
+        arc: 0.0,
+        width: 0.0,
+        created_at: now,
+        resolve_at: now + (delay.max(0.0) * 1000.0) as u64,
+        damage: damage.max(1),
+        damage_type,
+        shared: false,
+        follow: None,
+    }
+}
+
+/// Move following zones with their players and resolve zones whose time is up
+/// against player positions
+fn tick_telegraph_zones(ctx: &ReducerContext, positions: &[PlayerPosition], rngs: &mut std::collections::HashMap<u64, Rng>) {
+    let now = ctx.timestamp.to_duration_since_unix_epoch()
+        .unwrap_or_default().as_millis() as u64;
+    let zones: Vec<TelegraphZone> = ctx.db.telegraph_zone().iter().collect();
+    for mut zone in zones {
+        let moved = match zone.follow.and_then(|id| positions.iter().find(|p| p.identity == id)) {
+            Some(pos) if (pos.x, pos.y) != (zone.x, zone.y) => {
+                (zone.x, zone.y) = (pos.x, pos.y);
+                true
+            }
+            _ => false,
+        };
+        if now < zone.resolve_at {
+            if moved {
+                ctx.db.telegraph_zone().id().update(zone);
+            }
+            continue;
+        }
+
+        let inside: Vec<&PlayerPosition> = positions.iter()
+            .filter(|p| p.dungeon_id == zone.dungeon_id
+                && zone.shape.contains((zone.x, zone.y), zone.radius, zone.angle, zone.arc, zone.width, (p.x, p.y), PLAYER_HIT_RADIUS))
+            .collect();
+        // Stack damage is shared by everyone who made it in
+        let damage = if zone.shared {
+            (zone.damage / inside.len().max(1) as i32).max(1)
+        } else {
+            zone.damage
+        };
+        // The source may have died on its own (bombers) after committing to the zone
+        let attacker = match ctx.db.dungeon_enemy().id().find(zone.source_enemy_id) {
+            Some(source) => Attacker { x: zone.x, y: zone.y, ..enemy_attacker(ctx, &source) },
+            None => Attacker { x: zone.x, y: zone.y, crit_chance: 0, crit_damage: 100 },
+        };
+        let rng = rngs.entry(zone.dungeon_id)
+            .or_insert_with(|| dungeon_rng(ctx, zone.dungeon_id));
+        for p in inside {
+            hit_player(ctx, rng, zone.dungeon_id, Combatant::Enemy(zone.source_enemy_id), &attacker, p.identity, (p.x, p.y), damage, zone.damage_type);
+        }
+        ctx.db.telegraph_zone().id().delete(zone.id);
+    }
+}
+
+// ─── Projectiles ───────────────────────────────────────────────────────────────
+
+/// Spawn a projectile from an enemy in direction `dir` (unit vector)
+#[allow(clippy::too_many_arguments)]
+fn fire_projectile(ctx: &ReducerContext, e: &DungeonEnemy, kind: &str, dir: (f32, f32), speed: f32, damage: i32, radius: f32, lifetime: f32) {
+    ctx.db.projectile().insert(Projectile {
+        id: 0,
+        dungeon_id: e.dungeon_id,
+        room_index: e.room_index,
+        owner_enemy_id: e.id,
+        kind: kind.to_string(),
+        x: e.x,
+        y: e.y,
+        vx: dir.0 * speed,
+        vy: dir.1 * speed,
+        damage: damage.max(1),
+        radius,
+        lifetime,
+    });
+}
+
+/// Advance projectiles; remove them when they hit a player, a wall or expire
+fn tick_projectiles(
+    ctx: &ReducerContext,
+    dt: f32,
+    layouts: &std::collections::HashMap<(u64, u32), Vec<Tile>>,
+    open_room: &[Tile],
+    rngs: &mut std::collections::HashMap<u64, Rng>,
+) {
+    let projectiles: Vec<Projectile> = ctx.db.projectile().iter().collect();
+    if projectiles.is_empty() {
+        return;
+    }
+    let positions: Vec<PlayerPosition> = ctx.db.player_position().iter().collect();
+
+    for p in projectiles {
+        let x = p.x + p.vx * dt;
+        let y = p.y + p.vy * dt;
+        let lifetime = p.lifetime - dt;
+
+        // Pits don't stop projectiles, walls and pillars do
+        let tiles = layouts.get(&(p.dungeon_id, p.room_index)).map(|t| t.as_slice()).unwrap_or(open_room);
+        if lifetime <= 0.0 || room_layout::tile_at(tiles, x, y).is_solid() {
+            ctx.db.projectile().id().delete(p.id);
+            continue;
+        }
+
+        let hit = positions.iter().find(|pos| {
+            pos.dungeon_id == p.dungeon_id
+                && ((pos.x - x).powi(2) + (pos.y - y).powi(2)).sqrt() <= p.radius + PLAYER_HIT_RADIUS
+                && ctx.db.player().identity().find(pos.identity).is_some_and(|pl| pl.hp > 0)
+                && !has_iframes(ctx, pos.identity) // Dashing players slip through
+        });
+        if let Some(pos) = hit {
+            // Crits roll off the shooter if it is still around
+            let attacker = match ctx.db.dungeon_enemy().id().find(p.owner_enemy_id) {
+                Some(owner) => Attacker { x, y, ..enemy_attacker(ctx, &owner) },
+                None => Attacker { x, y, crit_chance: 0, crit_damage: 100 },
+            };
+            let damage_type = if p.kind == "boss_orb" { DamageType::Fire } else { DamageType::Physical };
+            let rng = rngs.entry(p.dungeon_id)
+                .or_insert_with(|| dungeon_rng(ctx, p.dungeon_id));
+            hit_player(ctx, rng, p.dungeon_id, Combatant::Enemy(p.owner_enemy_id), &attacker, pos.identity, (pos.x, pos.y), p.damage, damage_type);
+            ctx.db.projectile().id().delete(p.id);
+            continue;
+        }
+
+        ctx.db.projectile().id().update(Projectile { x, y, lifetime, ..p });
+    }
+}
+
+// ─── Helper Functions ──────────────────────────────────────────────────────────
+
+/// Schedule the next enemy AI tick (50ms = 20Hz for smooth multiplayer sync)
+fn schedule_enemy_tick(ctx: &ReducerContext) {
+    ctx.db.enemy_tick_schedule().insert(EnemyTickSchedule {
+        scheduled_id: 0,
+        scheduled_at: ScheduleAt::Interval(TimeDuration::from_micros(50_000)),
+    });
+}
+
+/// Spawn enemies for a given room
+fn spawn_enemies_for_room(ctx: &ReducerContext, dungeon_id: u64, room_index: u32, depth: u32, enemy_types: &[&str], rng: &mut Rng, tiles: &[Tile]) {
+    let mut pack_id_counter: u64 = rng.next_u64();
+
+    let enemy_count = enemy_types.len();
+
+    for (i, &et) in enemy_types.iter().enumerate() {
+        let (hp, atk, def) = get_enemy_stats(ctx, et, depth);
+        let ai = enemy_definition(ctx, et).ai;
+
+        // Spread enemies around the room (raid bosses centered)
+        let angle = if ai == AiArchetype::RaidBoss {
+            0.0
+        } else {
+            (i as f32 / enemy_count as f32) * std::f32::consts::TAU
+        };
+        let radius = if ai == AiArchetype::RaidBoss {
+            0.0 // Center of room
+        } else {
+            150.0 + rng.range_u32(0, 79) as f32
+        };
+        let (x, y) = room_layout::nearest_walkable(
+            tiles,
+            270.0 + angle.cos() * radius, // Room center
+            360.0 + angle.sin() * radius,
+        );
+
+        // Initial AI state depends on the enemy's archetype
+        let pack_id = if ai == AiArchetype::Wolf {
+            pack_id_counter += 1;
+            Some(pack_id_counter)
+        } else {
+            None
+        };
+        let initial_state = ai.initial_state().to_string();
+
+        let is_boss = et == "boss" || ai == AiArchetype::RaidBoss;
+        ctx.db.dungeon_enemy().insert(DungeonEnemy {
+            id: 0, // auto_inc
+            dungeon_id,
+            room_index,
+            enemy_type: et.to_string(),
+            x,
+            y,
+            hp,
+            max_hp: hp,
+            atk,
+            is_alive: true,
+            ai_state: initial_state,
+            state_timer: 0.0,
+            target_x: x,
+            target_y: y,
+            facing_angle: angle,
+            pack_id,
+            // Threat system fields
+            current_target: None,
+            is_taunted: false,
+            taunted_by: None,
+            taunt_timer: 0.0,
+            // Boss fields
+            is_boss,
+            boss_phase: if is_boss { 1 } else { 0 },
+            summoner_id: None,
+            def,
+            is_priority: false,
+        });
+    }
+}
+
+/// Get base (hp, atk, def) for an enemy type, scaled by dungeon depth
+fn get_enemy_stats(ctx: &ReducerContext, enemy_type: &str, depth: u32) -> (i32, i32, i32) {
+    let scale = 1.0 + (depth as f32 - 1.0) * 0.15;
+    let definition = enemy_definition(ctx, enemy_type);
+    (
+        (definition.hp as f32 * scale) as i32,
+        (definition.atk as f32 * scale) as i32,
+        (definition.def as f32 * scale) as i32,
+    )
+}
+
+/// Get movement speed for enemy type (base pixels per tick at 60fps equivalent)
+fn get_enemy_speed(ctx: &ReducerContext, enemy_type: &str) -> f32 {
+    ENEMY_MOVE_SPEED * enemy_definition(ctx, enemy_type).speed
+}
+
+/// Get XP reward for killing an enemy type
+fn get_enemy_xp(ctx: &ReducerContext, enemy_type: &str) -> u64 {
+    enemy_definition(ctx, enemy_type).xp
+}
+
+/// Count killed enemies and fully cleared rooms in a dungeon, returns (kills, rooms_cleared)
+fn count_dungeon_kills(ctx: &ReducerContext, dungeon_id: u64) -> (u32, u32) {
+    let mut kills = 0;
+    let mut rooms: std::collections::HashMap<u32, bool> = std::collections::HashMap::new();
+
+    for e in ctx.db.dungeon_enemy().iter().filter(|e| e.dungeon_id == dungeon_id) {
+        // Summoned minions don't count toward kills
+        if !e.is_alive && e.summoner_id.is_none() {
+            kills += 1;
+        }
+        let cleared = rooms.entry(e.room_index).or_insert(true);
+        *cleared &= !e.is_alive;
+    }
+
+    // Graph dungeons track clears per room (empty rooms count once entered)
+    let graph_rooms: Vec<DungeonRoom> = ctx.db.dungeon_room().iter()
+        .filter(|r| r.dungeon_id == dungeon_id)
+        .collect();
+    let rooms_cleared = if graph_rooms.is_empty() {
+        rooms.values().filter(|c| **c).count() as u32
+    } else {
+        graph_rooms.iter().filter(|r| r.cleared).count() as u32
+    };
+    (kills, rooms_cleared)
+}
+
+/// True once the run is won: the boss room is cleared in graph dungeons, or the
+/// party stands in the classic crawl's last room with nothing left alive
+fn dungeon_finished(ctx: &ReducerContext, dungeon: &ActiveDungeon) -> bool {
+    let graph_rooms: Vec<DungeonRoom> = ctx.db.dungeon_room().iter()
+        .filter(|r| r.dungeon_id == dungeon.id)
+        .collect();
+    if !graph_rooms.is_empty() {
+        return graph_rooms.iter().any(|r| r.kind == RoomKind::Boss && r.cleared);
+    }
+    dungeon.current_room + 1 >= dungeon.total_rooms
+        && !ctx.db.dungeon_enemy().iter()
+            .any(|e| e.dungeon_id == dungeon.id && e.room_index == dungeon.current_room && e.is_alive)
+}
+
+/// Highest player level a dungeon tier is tuned for (tier 0 = classic crawl, no cap)
+fn get_tier_max_level(tier: u32) -> Option<u32> {
+    match tier {
+        1 => Some(DUNGEON_TIER_1_MAX_LEVEL),
+        2 => Some(DUNGEON_TIER_2_MAX_LEVEL),
+        3 => Some(DUNGEON_TIER_3_MAX_LEVEL),
+        _ => None,
+    }
+}
+
+/// Server-side dungeon completion reward, returns (xp, gold)
+fn calculate_dungeon_rewards(
+    dungeon: &ActiveDungeon,
+    kills: u32,
+    rooms_cleared: u32,
+    party_size: u32,
+    clear_time_ms: u64,
+    player_level: u32,
+) -> (u64, u64) {
+    let depth = dungeon.depth.max(1) as f32;
+    let total_rooms = dungeon.total_rooms.max(1);
+
+    // Completion reward scales with how much of the dungeon was actually cleared
+    let clear_ratio = rooms_cleared.min(total_rooms) as f32 / total_rooms as f32;
+    let base_xp = DUNGEON_CLEAR_XP_PER_DEPTH as f32 * depth * clear_ratio
+        + (DUNGEON_KILL_XP * kills as u64) as f32;
+    let base_gold = DUNGEON_CLEAR_GOLD_PER_DEPTH as f32 * depth * clear_ratio
+        + (DUNGEON_KILL_GOLD * kills as u64) as f32;
+
+    // Difficulty: +25% per star above 1
+    let difficulty_mult = 1.0 + dungeon.difficulty.clamp(1, 5).saturating_sub(1) as f32 * 0.25;
+    // Tier: +50% per tier above 1
+    let tier_mult = 1.0 + dungeon.tier.saturating_sub(1) as f32 * 0.5;
+    // Party: +10% per extra player (matches loot bonus)
+    let party_mult = 1.0 + party_size.saturating_sub(1) as f32 * 0.1;
+
+    // Speed bonus for full clears under par time: up to +25%
+    let par_ms = DUNGEON_PAR_TIME_PER_ROOM_MS * total_rooms as u64;
+    let speed_mult = if rooms_cleared >= total_rooms && clear_time_ms < par_ms {
+        1.0 + 0.25 * (1.0 - clear_time_ms as f32 / par_ms as f32)
+    } else {
+        1.0
+    };
+
+    // Over-leveled players farming a low tier get half rewards
+    let level_mult = match get_tier_max_level(dungeon.tier) {
+        Some(max_level) if player_level > max_level => 0.5,
+        _ => 1.0,
+    };
+
+    let mult = difficulty_mult * tier_mult * party_mult * speed_mult * level_mult;
+    ((base_xp * mult) as u64, (base_gold * mult) as u64)
+}
+
+/// Resume a dungeon's RNG stream from its stored state
+fn load_dungeon_rng(ctx: &ReducerContext, dungeon_id: u64) -> Option<Rng> {
+    ctx.db.active_dungeon().id().find(dungeon_id)
+        .map(|d| Rng::new(d.rng_state))
+}
+
+/// Like `load_dungeon_rng`, for ticks that can still see rows of a dungeon that was
+/// just torn down. Nothing is stored for a missing dungeon, so it seeds from the ID.
+fn dungeon_rng(ctx: &ReducerContext, dungeon_id: u64) -> Rng {
+    load_dungeon_rng(ctx, dungeon_id).unwrap_or_else(|| Rng::new(dungeon_id))
+}
+
+/// Resume an open world shard's RNG stream from its stored state
+fn load_open_world_rng(ctx: &ReducerContext, instance_id: u64) -> Rng {
+    let state = ctx.db.open_world_instance().id().find(instance_id)
+        .map_or(instance_id, |i| i.rng_state);
+    Rng::new(state)
+}
+
+/// Persist an open world shard's RNG stream after drawing from it
+fn store_open_world_rng(ctx: &ReducerContext, instance_id: u64, rng: &Rng) {
+    if let Some(instance) = ctx.db.open_world_instance().id().find(instance_id) {
+        ctx.db.open_world_instance().id().update(OpenWorldInstance {
+            rng_state: rng.state(),
+            ..instance
+        });
+    }
+}
+
+/// Persist a dungeon's RNG stream after drawing from it
+fn store_dungeon_rng(ctx: &ReducerContext, dungeon_id: u64, rng: &Rng) {
+    if let Some(dungeon) = ctx.db.active_dungeon().id().find(dungeon_id) {
+        ctx.db.active_dungeon().id().update(ActiveDungeon {
+            rng_state: rng.state(),
+            ..dungeon
+        });
+    }
+}
+
+fn find_dungeon_room(ctx: &ReducerContext, dungeon_id: u64, room_index: u32) -> Option<DungeonRoom> {
+    ctx.db.dungeon_room().iter()
+        .find(|r| r.dungeon_id == dungeon_id && r.room_index == room_index)
+}
+
+/// Generate a graph room's layout and contents: enemies for combat, elite and boss
+/// rooms, a loot chest for treasure rooms. Rooms with no enemies clear immediately.
+fn populate_room(ctx: &ReducerContext, rng: &mut Rng, dungeon_id: u64, room_index: u32, depth: u32) {
+    let Some(room) = find_dungeon_room(ctx, dungeon_id, room_index) else {
+        return;
+    };
+
+    // A door for every connection touching this room
+    let doors: Vec<Side> = ctx.db.room_connection().iter()
+        .filter(|c| c.dungeon_id == dungeon_id)
+        .filter_map(|c| {
+            if c.from_room == room_index {
+                Some(c.from_side)
+            } else if c.to_room == room_index {
+                Some(c.to_side)
+            } else {
+                None
+            }
+        })
+        .collect();
+    let features = match room.kind {
+        RoomKind::Boss => ARENA_FEATURES,
+        RoomKind::Start | RoomKind::Treasure => ROOM_BASE_FEATURES / 2,
+        RoomKind::Combat | RoomKind::Elite => ROOM_BASE_FEATURES + depth / 2,
+    };
+    let tiles = create_room_layout(ctx, rng, dungeon_id, room_index, &doors, features);
+
+    let enemy_types = dungeon_graph::roll_room_enemies(rng, room.kind, depth);
+    spawn_enemies_for_room(ctx, dungeon_id, room_index, depth, &enemy_types, rng, &tiles);
+    if room.kind == RoomKind::Treasure {
+        drop_loot_for_dead_enemy(ctx, rng, "treasure", dungeon_id, room_index, ROOM_W / 2.0, ROOM_H / 2.0);
+    }
+
+    let cleared = enemy_types.is_empty();
+    ctx.db.dungeon_room().id().update(DungeonRoom {
+        visited: true,
+        ..room
+    });
+    if cleared {
+        mark_room_cleared(ctx, dungeon_id, room_index);
+    }
+}
+
+/// Mark the room cleared once none of its enemies are left alive
+fn check_room_clear(ctx: &ReducerContext, dungeon_id: u64, room_index: u32) {
+    let room_clear = !ctx.db.dungeon_enemy().iter()
+        .any(|e| e.dungeon_id == dungeon_id && e.room_index == room_index && e.is_alive);
+    if room_clear {
+        mark_room_cleared(ctx, dungeon_id, room_index);
+    }
+}
+
+/// Mark a room cleared and unlock every room connected to it
+fn mark_room_cleared(ctx: &ReducerContext, dungeon_id: u64, room_index: u32) {
+    let Some(room) = find_dungeon_room(ctx, dungeon_id, room_index) else {
+        return;
+    };
+    if room.cleared {
+        return;
+    }
+    ctx.db.dungeon_room().id().update(DungeonRoom {
+        cleared: true,
+        ..room
+    });
+
+    let neighbors: Vec<u32> = ctx.db.room_connection().iter()
+        .filter(|c| c.dungeon_id == dungeon_id)
+        .filter_map(|c| {
+            if c.from_room == room_index {
+                Some(c.to_room)
+            } else if c.to_room == room_index {
+                Some(c.from_room)
+            } else {
+                None
+            }
+        })
+        .collect();
+    for neighbor in neighbors {
+        if let Some(next) = find_dungeon_room(ctx, dungeon_id, neighbor) {
+            if !next.unlocked {
+                ctx.db.dungeon_room().id().update(DungeonRoom {
+                    unlocked: true,
+                    ..next
+                });
+            }
+        }
+    }
+    log::info!("Room {} cleared in dungeon {}", room_index, dungeon_id);
+}
+
+/// Generate and store the tile layout for a dungeon room
+fn create_room_layout(ctx: &ReducerContext, rng: &mut Rng, dungeon_id: u64, room_index: u32, doors: &[Side], features: u32) -> Vec<Tile> {
+    let tiles = room_layout::generate(rng, doors, features);
+    ctx.db.room_tile_map().insert(RoomTileMap {
+        id: 0,
+        dungeon_id,
+        room_index,
+        width: room_layout::ROOM_COLS,
+        height: room_layout::ROOM_ROWS,
+        tiles: tiles.clone(),
+    });
+    tiles
+}
+
+/// Stored tile layout of a dungeon room, if one was generated
+fn room_tiles(ctx: &ReducerContext, dungeon_id: u64, room_index: u32) -> Option<Vec<Tile>> {
+    ctx.db.room_tile_map().iter()
+        .find(|m| m.dungeon_id == dungeon_id && m.room_index == room_index)
+        .map(|m| m.tiles)
+}
+
+/// Drop loot when an enemy dies (takes individual fields to avoid borrow issues)
+fn drop_loot_for_dead_enemy(
+    ctx: &ReducerContext,
+    rng: &mut Rng,
+    enemy_type: &str,
+    dungeon_id: u64,
+    room_index: u32,
+    x: f32,
+    y: f32,
+) {
+    let Some(dungeon) = ctx.db.active_dungeon().id().find(dungeon_id) else {
+        return;
+    };
+
+    // Legendaries lean toward a random participant's class
+    let participants: Vec<Identity> = ctx.db.dungeon_participant().iter()
+        .filter(|p| p.dungeon_id == dungeon_id)
+        .map(|p| p.player_identity)
+        .collect();
+    let class_hint = if participants.is_empty() {
+        None
+    } else {
+        ctx.db.player().identity().find(participants[rng.index(participants.len())])
+            .map(|p| p.player_class)
+    };
+
+    let drops = items::roll_enemy_drops(rng, enemy_type, dungeon.depth, class_hint.as_deref());
+    let scatter = if drops.len() > 1 { 20.0 } else { 0.0 };
+    for (i, item) in drops.iter().enumerate() {
+        let angle = (i as f32 / drops.len() as f32) * std::f32::consts::TAU;
+        ctx.db.loot_drop().insert(LootDrop {
+            id: 0,
+            dungeon_id,
+            room_index,
+            x: x + angle.cos() * scatter,
+            y: y + angle.sin() * scatter,
+            item_data_json: String::new(),
+            rarity: item.rarity.as_str().to_string(),
+            picked_up: false,
+            item: Some(item.clone()),
+        });
+    }
+}
+
+/// Check if player should level up, returns the new level
+fn check_level_up(level: u32, xp: u64) -> u32 {
+    let mut lvl = level;
+
+    // Keep leveling up while XP exceeds threshold
+    while xp >= lvl as u64 * BASE_XP_PER_LEVEL {
+        lvl += 1;
+    }
+
+    lvl
+}
+
+/// Player with max_hp/atk/def/speed derived from class base stats, level growth
+/// and equipped items. HP is clamped to the new max.
+fn with_derived_stats(ctx: &ReducerContext, player: Player) -> Player {
+    let (base_hp, base_atk, base_def, base_speed) = get_class_stats(&player.player_class);
+    let growth = player.level.saturating_sub(1) as i32;
+
+    let mut gear = items::ItemStats::default();
+    for item in equipped_items(ctx, player.identity) {
+        gear.merge(&item.computed_stats());
+    }
+
+    let (crit_chance, crit_damage, dodge, block) = get_class_rolls(&player.player_class);
+
+    let max_hp = (base_hp + growth * LEVEL_HP_GROWTH + gear.hp).max(1);
+    Player {
+        max_hp,
+        hp: player.hp.min(max_hp),
+        atk: (base_atk + growth * LEVEL_ATK_GROWTH + gear.atk).max(1),
+        def: (base_def + growth * LEVEL_DEF_GROWTH + gear.def).max(0),
+        speed: (base_speed + gear.speed).max(1),
+        crit_chance: crit_chance + gear.crit,
+        crit_damage,
+        dodge,
+        block,
+        ..player
+    }
+}
+
+/// Typed items a player currently has equipped
+fn equipped_items(ctx: &ReducerContext, identity: Identity) -> Vec<Item> {
+    ctx.db.inventory_item().owner_identity().filter(identity)
+        .filter(|inv| inv.equipped_slot.is_some())
+        .filter_map(|inv| inv.item)
+        .collect()
+}
+
+/// Damage type of the equipped weapon (unarmed hits are physical)
+fn weapon_damage_type(gear: &[Item]) -> DamageType {
+    gear.iter()
+        .find(|item| item.slot == ItemSlot::Weapon)
+        .map(|item| item.damage_type())
+        .unwrap_or(DamageType::Physical)
+}
+
+/// Swing profile of the equipped weapon (unarmed if none)
+fn weapon_profile(gear: &[Item]) -> WeaponProfile {
+    gear.iter()
+        .find(|item| item.slot == ItemSlot::Weapon)
+        .map(|item| item.weapon_profile())
+        .unwrap_or(items::UNARMED)
+}
+
+/// Check and restart the caller's swing timer. Attack speed from gear shortens
+/// the weapon's base swing time.
+fn start_swing(ctx: &ReducerContext, dungeon_id: u64, gear: &[Item]) -> Result<(), String> {
+    let now = ctx.timestamp.to_duration_since_unix_epoch()
+        .unwrap_or_default().as_millis() as u64;
+    ensure_ability_state(ctx, dungeon_id);
+    let state = ctx.db.player_ability_state().identity().find(ctx.sender)
+        .ok_or("Ability state not found")?;
+    if now + SWING_TOLERANCE_MS < state.next_attack_at {
+        return Err(format!("Attack on cooldown ({}ms left)", state.next_attack_at - now));
+    }
+
+    let attack_speed: i32 = gear.iter().map(|item| item.computed_stats().attack_speed).sum();
+    let swing = (weapon_profile(gear).swing_time * 100.0 / (100 + attack_speed.max(0)) as f32).max(MIN_SWING_TIME);
+    ctx.db.player_ability_state().identity().update(PlayerAbilityState {
+        next_attack_at: now + (swing * 1000.0) as u64,
+        ..state
+    });
+    Ok(())
+}
+
+/// Defensive stats of a dungeon enemy for damage resolution
+fn dungeon_defender<'a>(e: &DungeonEnemy, definition: &'a EnemyDefinition) -> Defender<'a> {
+    Defender {
+        def: e.def,
+        resistances: &definition.resistances,
+        x: e.x,
+        y: e.y,
+        facing_angle: e.facing_angle,
+        // Shield knights lower their shield while recovering from a bash
+        shield_raised: definition.ai == AiArchetype::ShieldKnight && e.ai_state != "recover",
+        dodge: definition.dodge,
+        block: definition.block,
+    }
+}
+
+/// Attack rolls of a player standing at (x, y)
+fn player_attacker(player: &Player, x: f32, y: f32) -> Attacker {
+    Attacker { x, y, crit_chance: player.crit_chance, crit_damage: player.crit_damage }
+}
+
+/// Attack rolls of a dungeon enemy
+fn enemy_attacker(ctx: &ReducerContext, e: &DungeonEnemy) -> Attacker {
+    let definition = enemy_definition(ctx, &e.enemy_type);
+    Attacker { x: e.x, y: e.y, crit_chance: definition.crit_chance, crit_damage: definition.crit_damage }
+}
+
+/// Resolve a hit on a player standing at `at`, apply it and log the combat event.
+/// Returns the damage dealt (0 on a dodge).
+#[allow(clippy::too_many_arguments)]
+fn hit_player(
+    ctx: &ReducerContext,
+    rng: &mut Rng,
+    dungeon_id: u64,
+    source: Combatant,
+    attacker: &Attacker,
+    identity: Identity,
+    at: (f32, f32),
+    raw: i32,
+    damage_type: DamageType,
+) -> i32 {
+    let Some(player) = ctx.db.player().identity().find(identity).filter(|p| p.hp > 0) else {
+        return 0;
+    };
+    // Dashing through an attack avoids it entirely
+    if has_iframes(ctx, identity) {
+        let hit = HitResult { amount: 0, outcome: HitOutcome::Dodge };
+        log_combat_event(ctx, dungeon_id, source, Combatant::Player(identity), &hit, damage_type, at);
+        return 0;
+    }
+    let resistances = Resistances::default();
+    let defender = Defender {
+        def: player.def,
+        resistances: &resistances,
+        x: at.0,
+        y: at.1,
+        facing_angle: 0.0,
+        shield_raised: false,
+        dodge: player.dodge,
+        block: player.block,
+    };
+    // Tank buster stacks make every hit land harder
+    let vulnerable = strongest_status(ctx, Combatant::Player(identity), StatusKind::Vulnerable);
+    let mut hit = combat::resolve_damage(rng, raw * (100 + vulnerable) / 100, damage_type, attacker, &defender);
+    hit.amount = absorb_damage(ctx, Combatant::Player(identity), hit.amount);
+    if hit.amount > 0 {
+        ctx.db.player().identity().update(Player {
+            hp: (player.hp - hit.amount).max(0),
+            ..player
+        });
+    }
+    log_combat_event(ctx, dungeon_id, source, Combatant::Player(identity), &hit, damage_type, at);
+
+    // Enemy attacks can inflict their type's status
+    if let Combatant::Enemy(enemy_id) = source {
+        let status = ctx.db.dungeon_enemy().id().find(enemy_id)
+            .and_then(|e| enemy_definition(ctx, &e.enemy_type).on_hit);
+        if let (Some(status), true) = (status, hit.outcome != HitOutcome::Dodge) {
+            roll_on_hit_status(ctx, rng, dungeon_id, Combatant::Player(identity), source, status, raw);
+        }
+    }
+    hit.amount
+}
+
+/// Record a resolved hit for floating combat text
+fn log_combat_event(
+    ctx: &ReducerContext,
+    dungeon_id: u64,
+    source: Combatant,
+    target: Combatant,
+    hit: &HitResult,
+    damage_type: DamageType,
+    at: (f32, f32),
+) {
+    ctx.db.combat_event().insert(CombatEvent {
+        id: 0,
+        dungeon_id,
+        source,
+        target,
+        outcome: hit.outcome,
+        damage_type,
+        amount: hit.amount,
+        x: at.0,
+        y: at.1,
+        created_at: ctx.timestamp.to_duration_since_unix_epoch()
+            .unwrap_or_default().as_millis() as u64,
+    });
+}
+
+// ─── Status Effects ────────────────────────────────────────────────────────────
+
+/// Apply a status. Re-applying the same kind from the same source refreshes it
+/// (longest duration, strongest magnitude); different sources stack.
+fn apply_status(ctx: &ReducerContext, dungeon_id: u64, target: Combatant, kind: StatusKind, magnitude: i32, duration: f32, source: Combatant) {
+    let existing = ctx.db.status_effect().iter()
+        .find(|fx| fx.target == target && fx.kind == kind && fx.source == source);
+    if let Some(fx) = existing {
+        ctx.db.status_effect().id().update(StatusEffect {
+            magnitude: fx.magnitude.max(magnitude),
+            remaining: fx.remaining.max(duration),
+            ..fx
+        });
+    } else {
+        ctx.db.status_effect().insert(StatusEffect {
+            id: 0,
+            dungeon_id,
+            target,
+            kind,
+            magnitude,
+            remaining: duration,
+            source,
+            tick_timer: STATUS_TICK_INTERVAL,
+        });
+    }
+}
+
+/// Roll an on-hit status for a hit that dealt `damage`
+fn roll_on_hit_status(ctx: &ReducerContext, rng: &mut Rng, dungeon_id: u64, target: Combatant, source: Combatant, status: OnHitStatus, damage: i32) {
+    if rng.chance(status.chance) {
+        apply_status(ctx, dungeon_id, target, status.kind, status.magnitude_for(damage), status.duration, source);
+    }
+}
+
+fn has_status(ctx: &ReducerContext, target: Combatant, kind: StatusKind) -> bool {
+    ctx.db.status_effect().iter().any(|fx| fx.target == target && fx.kind == kind)
+}
+
+/// Strongest magnitude of a status on a target (0 if none)
+fn strongest_status(ctx: &ReducerContext, target: Combatant, kind: StatusKind) -> i32 {
+    ctx.db.status_effect().iter()
+        .filter(|fx| fx.target == target && fx.kind == kind)
+        .map(|fx| fx.magnitude)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Reject player actions while downed or stunned
+fn ensure_can_act(ctx: &ReducerContext) -> Result<(), String> {
+    if ctx.db.player().identity().find(ctx.sender).is_some_and(|p| p.hp <= 0) {
+        return Err("You are downed".into());
+    }
+    if has_status(ctx, Combatant::Player(ctx.sender), StatusKind::Stun) {
+        return Err("You are stunned".into());
+    }
+    Ok(())
+}
+
+/// Soak damage into the target's absorb shields, returns what gets through
+fn absorb_damage(ctx: &ReducerContext, target: Combatant, amount: i32) -> i32 {
+    let mut remaining = amount;
+    let shields: Vec<StatusEffect> = ctx.db.status_effect().iter()
+        .filter(|fx| fx.target == target && fx.kind == StatusKind::Shield)
+        .collect();
+    for shield in shields {
+        if remaining <= 0 {
+            break;
+        }
+        let soaked = remaining.min(shield.magnitude);
+        remaining -= soaked;
+        if soaked >= shield.magnitude {
+            ctx.db.status_effect().id().delete(shield.id);
+        } else {
+            ctx.db.status_effect().id().update(StatusEffect {
+                magnitude: shield.magnitude - soaked,
+                ..shield
+            });
+        }
+    }
+    remaining
+}
+
+/// Count down every status, deal DoT ticks and drop expired effects
+fn tick_status_effects(ctx: &ReducerContext, dt: f32, rngs: &mut std::collections::HashMap<u64, Rng>) {
+    let effects: Vec<StatusEffect> = ctx.db.status_effect().iter().collect();
+    for fx in effects {
+        // Effects end with their target
+        let target_alive = match fx.target {
+            Combatant::Player(identity) => ctx.db.player().identity().find(identity).is_some_and(|p| p.hp > 0),
+            Combatant::Enemy(id) => ctx.db.dungeon_enemy().id().find(id).is_some_and(|e| e.is_alive),
+            Combatant::OpenWorldEnemy(_) => false,
+        };
+        let remaining = fx.remaining - dt;
+        if !target_alive || remaining <= 0.0 {
+            ctx.db.status_effect().id().delete(fx.id);
+            continue;
+        }
+
+        let mut tick_timer = fx.tick_timer;
+        if let Some(damage_type) = fx.kind.dot_damage_type() {
+            tick_timer -= dt;
+            if tick_timer <= 0.0 {
+                tick_timer += STATUS_TICK_INTERVAL;
+                let rng = rngs.entry(fx.dungeon_id)
+                    .or_insert_with(|| dungeon_rng(ctx, fx.dungeon_id));
+                deal_dot_tick(ctx, rng, &fx, damage_type);
+            }
+        }
+        ctx.db.status_effect().id().update(StatusEffect { remaining, tick_timer, ..fx });
+    }
+}
+
+/// One DoT tick. DoTs skip dodge, DEF and crits but respect resistances and shields.
+fn deal_dot_tick(ctx: &ReducerContext, rng: &mut Rng, fx: &StatusEffect, damage_type: DamageType) {
+    match fx.target {
+        Combatant::Player(identity) => {
+            let Some(player) = ctx.db.player().identity().find(identity) else { return };
+            let amount = absorb_damage(ctx, fx.target, fx.magnitude);
+            let at = ctx.db.player_position().identity().find(identity).map(|p| (p.x, p.y)).unwrap_or_default();
+            ctx.db.player().identity().update(Player {
+                hp: (player.hp - amount).max(0),
+                ..player
+            });
+            let hit = HitResult { amount, outcome: HitOutcome::Hit };
+            log_combat_event(ctx, fx.dungeon_id, fx.source, fx.target, &hit, damage_type, at);
+        }
+        Combatant::Enemy(id) => {
+            let Some(enemy) = ctx.db.dungeon_enemy().id().find(id) else { return };
+            let resistances = enemy_definition(ctx, &enemy.enemy_type).resistances;
+            let amount = (fx.magnitude * (100 - resistances.against(damage_type)) / 100).max(1);
+            let hit = HitResult { amount, outcome: HitOutcome::Hit };
+            log_combat_event(ctx, fx.dungeon_id, fx.source, fx.target, &hit, damage_type, (enemy.x, enemy.y));
+            if enemy.hp - amount > 0 {
+                ctx.db.dungeon_enemy().id().update(DungeonEnemy { hp: enemy.hp - amount, ..enemy });
+                return;
+            }
+            // The player who applied the DoT gets the kill
+            let killer = match fx.source {
+                Combatant::Player(identity) => Some(identity),
+                _ => ctx.db.active_dungeon().id().find(fx.dungeon_id).map(|d| d.owner_identity),
+            };
+            if let Some(killer) = killer {
+                let xp = kill_dungeon_enemy(ctx, rng, killer, enemy);
+                if fx.source == Combatant::Player(killer) {
+                    grant_xp(ctx, killer, xp);
+                }
+            }
+        }
+        Combatant::OpenWorldEnemy(_) => {}
+    }
+}
+
+/// Add XP to a player, applying level-ups
+fn grant_xp(ctx: &ReducerContext, identity: Identity, xp: u64) {
+    if xp == 0 {
+        return;
+    }
+    let Some(player) = ctx.db.player().identity().find(identity) else { return };
+    let new_xp = player.xp + xp;
+    let new_level = check_level_up(player.level, new_xp);
+    ctx.db.player().identity().update(with_derived_stats(ctx, Player {
+        xp: new_xp,
+        level: new_level,
+        ..player
+    }));
+}
+
+// ─── Movement Validation ───────────────────────────────────────────────────────
+
+/// Let the next moves through without a speed check, after the server itself
+/// moved the player (room entry, dash, joining an instance)
+fn start_movement_grace(ctx: &ReducerContext, identity: Identity) {
+    let now = ctx.timestamp.to_duration_since_unix_epoch()
+        .unwrap_or_default().as_millis() as u64;
+    if let Some(state) = ctx.db.movement_state().identity().find(identity) {
+        ctx.db.movement_state().identity().update(MovementState {
+            accepted_at: now,
+            grace_until: now + MOVE_TELEPORT_GRACE_MS,
+            travel_budget: MOVE_LATENCY_MS as f32,
+            ..state
+        });
+    } else {
+        ctx.db.movement_state().insert(MovementState {
+            identity,
+            accepted_at: now,
+            grace_until: now + MOVE_TELEPORT_GRACE_MS,
+            violations: 0,
+            window_start: now,
+            travel_budget: MOVE_LATENCY_MS as f32,
+        });
+    }
+}
+
+/// Keep a position inside the room, flagging out-of-bounds coordinates
+fn clamp_to_room(ctx: &ReducerContext, identity: Identity, x: f32, y: f32) -> (f32, f32) {
+    if !x.is_finite() || !y.is_finite() {
+        record_move_violation(ctx, identity, "bounds", format!("non-finite position ({}, {})", x, y));
+        return (ROOM_W / 2.0, ROOM_H / 2.0);
+    }
+    let clamped = (x.clamp(0.0, ROOM_W - 1.0), y.clamp(0.0, ROOM_H - 1.0));
+    if clamped != (x, y) {
+        record_move_violation(ctx, identity, "bounds", format!("({:.0}, {:.0}) outside the room", x, y));
+    }
+    clamped
+}
+
+/// Cap a move at what the player's speed stat (minus slows) covers since the last
+/// move, plus whatever travel budget was left over. Over-long moves are shortened
+/// toward the target and counted.
+fn validate_move(ctx: &ReducerContext, identity: Identity, from: (f32, f32), to: (f32, f32)) -> (f32, f32) {
+    let now = ctx.timestamp.to_duration_since_unix_epoch()
+        .unwrap_or_default().as_millis() as u64;
+    let Some(state) = ctx.db.movement_state().identity().find(identity) else {
+        // First move we've seen: it becomes the baseline
+        start_movement_grace(ctx, identity);
+        return to;
+    };
+    if now < state.grace_until {
+        ctx.db.movement_state().identity().update(MovementState { accepted_at: now, ..state });
+        return to;
+    }
+
+    let speed_stat = ctx.db.player().identity().find(identity).map(|p| p.speed).unwrap_or(0);
+    let slow = strongest_status(ctx, Combatant::Player(identity), StatusKind::Slow).clamp(0, 90);
+    let speed = (PLAYER_BASE_MOVE_SPEED + speed_stat as f32 * PLAYER_MOVE_SPEED_PER_POINT) * (100 - slow) as f32 / 100.0;
+
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let dist = (dx * dx + dy * dy).sqrt();
+    let elapsed_ms = now.saturating_sub(state.accepted_at);
+    let (allowed, travel_budget) = spend_travel_budget(state.travel_budget, elapsed_ms, speed * MOVE_SPEED_TOLERANCE, dist);
+    ctx.db.movement_state().identity().update(MovementState { accepted_at: now, travel_budget, ..state });
+    if dist <= allowed {
+        return to;
+    }
+    record_move_violation(ctx, identity, "speed", format!("moved {:.0}px, allowed {:.0}px", dist, allowed));
+    (from.0 + dx / dist * allowed, from.1 + dy / dist * allowed)
+}
+
+/// Token bucket for movement: refill the carried travel budget (ms) with the time
+/// since the last move, spend `dist` pixels of it at `speed` px/s, and carry at most
+/// one latency window over. Returns the distance allowed and the new budget.
+fn spend_travel_budget(budget_ms: f32, elapsed_ms: u64, speed: f32, dist: f32) -> (f32, f32) {
+    let speed = speed.max(1.0);
+    let available_ms = budget_ms.clamp(0.0, MOVE_LATENCY_MS as f32) + elapsed_ms.min(MOVE_MAX_WINDOW_MS) as f32;
+    let allowed = speed * available_ms / 1000.0;
+    let used_ms = dist.min(allowed) / speed * 1000.0;
+    (allowed, (available_ms - used_ms).min(MOVE_LATENCY_MS as f32))
+}
+
+/// Count a movement violation and flag the player once they pile up
+fn record_move_violation(ctx: &ReducerContext, identity: Identity, kind: &str, detail: String) {
+    let now = ctx.timestamp.to_duration_since_unix_epoch()
+        .unwrap_or_default().as_millis() as u64;
+    let Some(state) = ctx.db.movement_state().identity().find(identity) else {
+        return;
+    };
+    let (violations, window_start) = if now.saturating_sub(state.window_start) > MOVE_VIOLATION_WINDOW_MS {
+        (1, now)
+    } else {
+        (state.violations + 1, state.window_start)
+    };
+
+    if violations >= MOVE_VIOLATION_THRESHOLD {
+        ctx.db.suspicious_activity().insert(SuspiciousActivity {
+            id: 0,
+            identity,
+            kind: kind.to_string(),
+            detail: format!("{} violations in {}s, last: {}", violations, MOVE_VIOLATION_WINDOW_MS / 1000, detail),
+            created_at: now,
+        });
+        log::warn!("Flagged {:?} for {} ({})", identity, kind, detail);
+        ctx.db.movement_state().identity().update(MovementState { violations: 0, window_start: now, ..state });
+    } else {
+        ctx.db.movement_state().identity().update(MovementState { violations, window_start, ..state });
+    }
+}
+
+// ─── Downed Players & Wipes ────────────────────────────────────────────────────
+
+/// Mark dungeon participants at 0 HP as downed, advance revive channels and
+/// bleed-out timers, and wipe dungeons where nobody is left standing
+fn tick_downed_players(ctx: &ReducerContext, dt: f32) {
+    for part in ctx.db.dungeon_participant().iter() {
+        let down = ctx.db.player().identity().find(part.player_identity).is_some_and(|p| p.hp <= 0);
+        if down && ctx.db.downed_player().identity().find(part.player_identity).is_none() {
+            ctx.db.downed_player().insert(DownedPlayer {
+                identity: part.player_identity,
+                dungeon_id: part.dungeon_id,
+                bleed_out: DOWNED_BLEED_OUT_TIME,
+                is_dead: false,
+                reviver: None,
+                revive_progress: 0.0,
+            });
+            log::info!("Player {:?} downed in dungeon {}", part.player_identity, part.dungeon_id);
+        }
+    }
+
+    let downed: Vec<DownedPlayer> = ctx.db.downed_player().iter().collect();
+    for d in downed {
+        let Some(player) = ctx.db.player().identity().find(d.identity) else {
+            ctx.db.downed_player().identity().delete(d.identity);
+            continue;
+        };
+        if player.hp > 0 {
+            // Back on their feet some other way
+            ctx.db.downed_player().identity().delete(d.identity);
+            continue;
+        }
+        if d.is_dead {
+            continue;
+        }
+
+        // The channel holds while the reviver stays in range and able to act
+        let channeling = d.reviver.is_some_and(|reviver| {
+            let standing = ctx.db.player().identity().find(reviver).is_some_and(|p| p.hp > 0)
+                && !has_status(ctx, Combatant::Player(reviver), StatusKind::Stun);
+            let in_range = match (
+                ctx.db.player_position().identity().find(reviver),
+                ctx.db.player_position().identity().find(d.identity),
+            ) {
+                (Some(a), Some(b)) => a.dungeon_id == d.dungeon_id
+                    && ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt() <= REVIVE_RANGE,
+                _ => false,
+            };
+            standing && in_range
+        });
+
+        if channeling && d.revive_progress + dt >= REVIVE_CHANNEL_TIME {
+            let hp = (player.max_hp * REVIVE_HP_PCT / 100).max(1);
+            log::info!("Player {:?} revived in dungeon {}", d.identity, d.dungeon_id);
+            ctx.db.player().identity().update(Player { hp, ..player });
+            ctx.db.downed_player().identity().delete(d.identity);
+            continue;
+        }
+
+        let bleed_out = d.bleed_out - dt;
+        if bleed_out <= 0.0 {
+            log::info!("Player {:?} bled out in dungeon {}", d.identity, d.dungeon_id);
+        }
+        ctx.db.downed_player().identity().update(DownedPlayer {
+            bleed_out: bleed_out.max(0.0),
+            is_dead: bleed_out <= 0.0,
+            reviver: if channeling { d.reviver } else { None },
+            revive_progress: if channeling { d.revive_progress + dt } else { 0.0 },
+            ..d
+        });
+    }
+
+    // A dungeon wipes once every participant is down
+    let dungeons: Vec<ActiveDungeon> = ctx.db.active_dungeon().iter().collect();
+    for dungeon in dungeons {
+        let mut participants = ctx.db.dungeon_participant().iter()
+            .filter(|p| p.dungeon_id == dungeon.id)
+            .peekable();
+        if participants.peek().is_none() {
+            continue;
+        }
+        let wiped = participants.all(|p| {
+            ctx.db.player().identity().find(p.player_identity).is_none_or(|pl| pl.hp <= 0)
+        });
+        if wiped {
+            wipe_dungeon(ctx, dungeon);
+        }
+    }
+}
+
+/// End a run where everyone went down. Raids count the wipe and put the whole
+/// raid group on cooldown. Players stay at 0 HP until they start a new run.
+fn wipe_dungeon(ctx: &ReducerContext, dungeon: ActiveDungeon) {
+    let now = ctx.timestamp.to_duration_since_unix_epoch()
+        .unwrap_or_default().as_millis() as u64;
+
+    if let Some(raid) = dungeon.raid_id.and_then(|id| ctx.db.raid_instance().id().find(id)) {
+        let raid_id = raid.id;
+        ctx.db.raid_instance().id().update(RaidInstance {
+            wipe_count: raid.wipe_count + 1,
+            ..raid
+        });
+        let members: Vec<Identity> = ctx.db.raid_participant().iter()
+            .filter(|p| p.raid_id == raid_id)
+            .map(|p| p.player_identity)
+            .collect();
+        for identity in members {
+            set_raid_cooldown(ctx, identity, now + RAID_WIPE_COOLDOWN_MS);
+        }
+        end_raid(ctx, raid_id);
+    }
+
+    // Send everyone back to the hub
+    let members: Vec<Identity> = ctx.db.dungeon_participant().iter()
+        .filter(|p| p.dungeon_id == dungeon.id)
+        .map(|p| p.player_identity)
+        .collect();
+    for identity in members {
+        if let Some(gm) = ctx.db.player_game_mode().identity().find(identity) {
+            ctx.db.player_game_mode().identity().update(PlayerGameMode {
+                mode: "hub".to_string(),
+                instance_id: None,
+                ..gm
+            });
+        }
+    }
+
+    log::info!("Dungeon {} wiped", dungeon.id);
+    cleanup_dungeon(ctx, dungeon.id);
+    ctx.db.active_dungeon().id().delete(dungeon.id);
+}
+
+// ─── Raids ─────────────────────────────────────────────────────────────────────
+
+/// Put a player into a raid and its arena at full health. `slot` picks their
+/// spot in the line-up at the south end of the arena.
+fn add_raid_member(ctx: &ReducerContext, raid_id: u64, dungeon_id: u64, identity: Identity, slot: usize, tiles: &[Tile]) {
+    let Some(player) = ctx.db.player().identity().find(identity) else {
+        return;
+    };
+    ctx.db.raid_participant().insert(RaidParticipant {
+        id: 0,
+        raid_id,
+        player_identity: identity,
+        player_class: player.player_class.clone(),
+        disconnected_at: None,
+        slot: slot as u32,
+    });
+    ctx.db.dungeon_participant().insert(DungeonParticipant {
+        id: 0,
+        dungeon_id,
+        player_identity: identity,
+    });
+
+    let (x, y) = room_layout::nearest_walkable(
+        tiles,
+        ROOM_W * (slot % RAID_PARTY_SIZE + 1) as f32 / (RAID_PARTY_SIZE + 1) as f32,
+        ROOM_H - 3.0 * TILE_SIZE,
+    );
+    start_movement_grace(ctx, identity);
+    let position = PlayerPosition {
+        identity,
+        dungeon_id,
+        x,
+        y,
+        facing_x: 0.0,
+        facing_y: -1.0,
+        name: player.name.clone(),
+        level: player.level,
+        player_class: player.player_class.clone(),
+        weapon_icon: String::new(),
+        armor_icon: String::new(),
+        accessory_icon: String::new(),
+    };
+    if let Some(old_pos) = ctx.db.player_position().identity().find(identity) {
+        ctx.db.player_position().identity().update(PlayerPosition {
+            weapon_icon: old_pos.weapon_icon,
+            armor_icon: old_pos.armor_icon,
+            accessory_icon: old_pos.accessory_icon,
+            ..position
+        });
+    } else {
+        ctx.db.player_position().insert(position);
+    }
+
+    // Fights start at full health
+    ctx.db.player().identity().update(Player {
+        hp: player.max_hp,
+        ..player
+    });
+
+    // Remove from queue
+    ctx.db.raid_queue().identity().delete(identity);
+
+    // Update game mode
+    if let Some(gm) = ctx.db.player_game_mode().identity().find(identity) {
+        ctx.db.player_game_mode().identity().update(PlayerGameMode {
+            mode: "raid".to_string(),
+            instance_id: Some(raid_id),
+            ..gm
+        });
+    }
+}
+
+/// Drop raid members whose reconnect window ran out and backfill each slot
+/// from the raid queue with a player of the same class
+fn expire_raid_disconnects(ctx: &ReducerContext, raid_id: u64, dungeon_id: u64) {
+    let now = ctx.timestamp.to_duration_since_unix_epoch()
+        .unwrap_or_default().as_millis() as u64;
+    let expired: Vec<RaidParticipant> = ctx.db.raid_participant().iter()
+        .filter(|p| p.raid_id == raid_id)
+        .filter(|p| p.disconnected_at.is_some_and(|t| now.saturating_sub(t) > RAID_RECONNECT_WINDOW_MS))
+        .collect();
+    if expired.is_empty() {
+        return;
+    }
+    let tiles = room_tiles(ctx, dungeon_id, 0).unwrap_or_else(room_layout::open_room);
+
+    for part in expired {
+        let identity = part.player_identity;
+        log::info!("{:?} missed the reconnect window for raid {}", identity, raid_id);
+        ctx.db.raid_participant().id().delete(part.id);
+        let dungeon_part = ctx.db.dungeon_participant().iter()
+            .find(|p| p.dungeon_id == dungeon_id && p.player_identity == identity);
+        if let Some(dp) = dungeon_part {
+            ctx.db.dungeon_participant().id().delete(dp.id);
+        }
+        if ctx.db.player_position().identity().find(identity).is_some_and(|p| p.dungeon_id == dungeon_id) {
+            ctx.db.player_position().identity().delete(identity);
+        }
+        if ctx.db.downed_player().identity().find(identity).is_some() {
+            ctx.db.downed_player().identity().delete(identity);
+        }
+        if let Some(gm) = ctx.db.player_game_mode().identity().find(identity) {
+            ctx.db.player_game_mode().identity().update(PlayerGameMode {
+                mode: "hub".to_string(),
+                instance_id: None,
+                ..gm
+            });
+        }
+
+        // Longest-waiting eligible player of the same class takes the slot
+        let replacement = ctx.db.raid_queue().iter()
+            .filter(|q| q.player_class == part.player_class && raid_eligible(ctx, q.identity, now))
+            .min_by_key(|q| q.queued_at);
+        if let Some(q) = replacement {
+            // The leaver's spawn slot, unless an older row left it ambiguous
+            let taken: Vec<u32> = ctx.db.raid_participant().iter()
+                .filter(|p| p.raid_id == raid_id)
+                .map(|p| p.slot)
+                .collect();
+            let slot = std::iter::once(part.slot)
+                .chain(0..RAID_PARTY_SIZE as u32)
+                .find(|s| !taken.contains(s))
+                .unwrap_or(part.slot);
+            add_raid_member(ctx, raid_id, dungeon_id, q.identity, slot as usize, &tiles);
+            log::info!("{:?} backfilled the {} slot in raid {}", q.identity, part.player_class, raid_id);
+        }
+    }
+}
+
+/// A queued player can be pulled into a raid: off raid cooldown and not already in
+/// a raid or dungeon
+fn raid_eligible(ctx: &ReducerContext, identity: Identity, now: u64) -> bool {
+    let on_cooldown = ctx.db.raid_cooldown().identity().find(identity)
+        .is_some_and(|cd| now < cd.cooldown_until);
+    !on_cooldown
+        && active_raid_participant(ctx, identity).is_none()
+        && !ctx.db.dungeon_participant().iter().any(|p| p.player_identity == identity)
+}
+
+/// Live raid membership of a player, if any
+fn active_raid_participant(ctx: &ReducerContext, identity: Identity) -> Option<RaidParticipant> {
+    ctx.db.raid_participant().iter().find(|p| {
+        p.player_identity == identity
+            && ctx.db.raid_instance().id().find(p.raid_id).is_some_and(|r| r.ended_at == 0)
+    })
+}
+
+/// Mirror each running raid's current boss HP and phase into its `RaidInstance`.
+/// A dead boss pays out and brings on the next one, and the raid ends after the last.
+fn tick_raids(ctx: &ReducerContext) {
+    let raids: Vec<RaidInstance> = ctx.db.raid_instance().iter()
+        .filter(|r| r.ended_at == 0)
+        .collect();
+    for raid in raids {
+        let Some(dungeon) = ctx.db.active_dungeon().iter().find(|d| d.raid_id == Some(raid.id)) else {
+            // The arena is gone (wiped or abandoned)
+            end_raid(ctx, raid.id);
+            continue;
+        };
+        expire_raid_disconnects(ctx, raid.id, dungeon.id);
+        let bosses = encounter_bosses(ctx, raid_encounters::DEFAULT_ENCOUNTER);
+        let index = (raid.boss_index as usize).min(bosses.len() - 1);
+        let boss_type = bosses[index].clone();
+        let boss = ctx.db.dungeon_enemy().iter()
+            .find(|e| e.dungeon_id == dungeon.id && e.enemy_type == boss_type && e.is_boss);
+        match boss {
+            Some(boss) if boss.is_alive => {
+                if boss.hp != raid.boss_hp || boss.boss_phase != raid.boss_phase {
+                    ctx.db.raid_instance().id().update(RaidInstance {
+                        boss_hp: boss.hp,
+                        boss_max_hp: boss.max_hp,
+                        boss_phase: boss.boss_phase,
+                        ..raid
+                    });
+                }
+            }
+            _ => {
+                let final_boss = index + 1 >= bosses.len();
+                reward_raid_boss(ctx, &dungeon, raid.id, &boss_type, final_boss);
+                if final_boss {
+                    clear_raid(ctx, dungeon, raid);
+                } else {
+                    next_raid_boss(ctx, &dungeon, raid, &bosses[index + 1]);
+                }
             }
-        } else {
-            e.ai_state = "kite".to_string();
         }
-    } else {
-        // Too far, approach
-        e.ai_state = "chase".to_string();
-        e.x += nx * speed * 0.5;
-        e.y += ny * speed * 0.5;
     }
 }
 
-/// Raid Boss AI: 3-phase boss fight
-/// Phase 1 (100-60% HP): Attack highest threat, tank check
-/// Phase 2 (60-30% HP): Teleport center, spawn adds every 6s
-/// Phase 3 (<30% HP): Enrage (+50% ATK), raid-wide AoE every 4s
-fn ai_raid_boss(e: &mut DungeonEnemy, target: &PlayerPosition, _dx: f32, _dy: f32, dist: f32, nx: f32, ny: f32, dt: f32, ctx: &ReducerContext, all_positions: &[PlayerPosition]) {
-    let speed = 40.0 * dt * 60.0; // Slow but menacing
+/// Bring the next boss of the encounter into the arena
+fn next_raid_boss(ctx: &ReducerContext, dungeon: &ActiveDungeon, raid: RaidInstance, boss_type: &str) {
+    let mut rng = dungeon_rng(ctx, dungeon.id);
+    let tiles = ctx.db.room_tile_map().iter()
+        .find(|m| m.dungeon_id == dungeon.id && m.room_index == 0)
+        .map(|m| m.tiles)
+        .unwrap_or_else(room_layout::open_room);
+    spawn_enemies_for_room(ctx, dungeon.id, 0, dungeon.depth, &[boss_type], &mut rng, &tiles);
+    store_dungeon_rng(ctx, dungeon.id, &rng);
+
+    let (boss_hp, _boss_atk, _boss_def) = get_enemy_stats(ctx, boss_type, dungeon.depth);
+    log::info!("Raid {} moves on to {}", raid.id, boss_type);
+    ctx.db.raid_instance().id().update(RaidInstance {
+        boss_index: raid.boss_index + 1,
+        boss_hp,
+        boss_max_hp: boss_hp,
+        boss_phase: 1,
+        ..raid
+    });
+}
 
-    e.facing_angle = ny.atan2(nx);
-    e.state_timer -= dt;
+/// Pay out a raid boss to every member. While a member hasn't cleared the raid today
+/// each boss gives a boss-grade piece, and the last boss also guarantees a legendary
+/// for their class; after the daily clear bosses give a single regular piece.
+/// Members past the weekly cap for a boss get nothing from it.
+fn reward_raid_boss(ctx: &ReducerContext, dungeon: &ActiveDungeon, raid_id: u64, boss_type: &str, final_boss: bool) {
+    let now = ctx.timestamp.to_duration_since_unix_epoch()
+        .unwrap_or_default().as_millis() as u64;
+    let today = (now / MS_PER_DAY) as u32;
+    let week = raid_week(now);
 
-    // Calculate current phase based on HP percentage
-    let hp_pct = e.hp as f32 / e.max_hp as f32;
-    let new_phase = if hp_pct > 0.6 { 1 } else if hp_pct > 0.3 { 2 } else { 3 };
-
-    // Phase transition
-    if new_phase != e.boss_phase {
-        e.boss_phase = new_phase;
-        e.state_timer = 0.5; // Brief pause during transition
-        match new_phase {
-            2 => {
-                // Teleport to center
-                e.x = 270.0;
-                e.y = 360.0;
-                e.ai_state = "phase2".to_string();
-            }
-            3 => {
-                // Enrage
-                e.atk = (e.atk as f32 * 1.5) as i32;
-                e.ai_state = "enrage".to_string();
-            }
-            _ => {}
+    let mut rng = dungeon_rng(ctx, dungeon.id);
+    let drop_table = items::get_drop_table(boss_type);
+    let members: Vec<Identity> = ctx.db.raid_participant().iter()
+        .filter(|p| p.raid_id == raid_id)
+        .map(|p| p.player_identity)
+        .collect();
+    for identity in members {
+        let Some(player) = ctx.db.player().identity().find(identity) else { continue };
+
+        let lockout = ctx.db.raid_lockout().iter()
+            .find(|l| l.identity == identity && l.boss_type == boss_type && l.week == week);
+        let clears = lockout.as_ref().map(|l| l.clears).unwrap_or(0);
+        if clears >= RAID_WEEKLY_LOOT_CLEARS {
+            log::info!("{:?} is locked out of {} loot until the weekly reset", identity, boss_type);
+            continue;
         }
-    }
-
-    match e.boss_phase {
-        1 => {
-            // Phase 1: Chase and attack highest threat
-            if dist <= ENEMY_ATTACK_RANGE + 15.0 {
-                if e.state_timer <= 0.0 {
-                    e.state_timer = 1.0;
-                    e.ai_state = "attack".to_string();
-                    // Deal damage to target
-                    if let Some(player) = ctx.db.player().identity().find(target.identity) {
-                        let damage = (e.atk - player.def / 2).max(1);
-                        ctx.db.player().identity().update(Player {
-                            hp: (player.hp - damage).max(0),
-                            ..player
-                        });
-                    }
-                }
-            } else {
-                e.ai_state = "chase".to_string();
-                e.x += nx * speed;
-                e.y += ny * speed;
+        match lockout {
+            Some(l) => {
+                ctx.db.raid_lockout().id().update(RaidLockout { clears: clears + 1, ..l });
             }
-        }
-        2 => {
-            // Phase 2: Spawn adds every 6 seconds, chase between spawns
-            if e.state_timer <= 0.0 {
-                e.state_timer = 6.0;
-                e.ai_state = "summon".to_string();
-                // Spawn 2 skeleton adds around the boss
-                for i in 0..2 {
-                    let angle = (i as f32) * std::f32::consts::PI;
-                    let (add_hp, add_atk) = get_enemy_stats("skeleton", 1);
-                    ctx.db.dungeon_enemy().insert(DungeonEnemy {
-                        id: 0,
-                        dungeon_id: e.dungeon_id,
-                        room_index: e.room_index,
-                        enemy_type: "skeleton".to_string(),
-                        x: e.x + angle.cos() * 50.0,
-                        y: e.y + angle.sin() * 50.0,
-                        hp: add_hp,
-                        max_hp: add_hp,
-                        atk: add_atk,
-                        is_alive: true,
-                        ai_state: "chase".to_string(),
-                        state_timer: 0.0,
-                        target_x: e.x,
-                        target_y: e.y,
-                        facing_angle: angle,
-                        pack_id: None,
-                        current_target: None,
-                        is_taunted: false,
-                        taunted_by: None,
-                        taunt_timer: 0.0,
-                        is_boss: false,
-                        boss_phase: 0,
-                    });
-                }
-            } else {
-                // Chase between summons
-                if dist > ENEMY_ATTACK_RANGE + 10.0 {
-                    e.x += nx * speed * 0.7;
-                    e.y += ny * speed * 0.7;
-                } else if e.ai_state != "summon" {
-                    e.ai_state = "attack".to_string();
-                    // Attack
-                    if let Some(player) = ctx.db.player().identity().find(target.identity) {
-                        let damage = (e.atk - player.def / 2).max(1);
-                        ctx.db.player().identity().update(Player {
-                            hp: (player.hp - damage).max(0),
-                            ..player
-                        });
-                    }
-                }
+            None => {
+                ctx.db.raid_lockout().insert(RaidLockout {
+                    id: 0,
+                    identity,
+                    boss_type: boss_type.to_string(),
+                    week,
+                    clears: 1,
+                });
             }
         }
-        3 => {
-            // Phase 3: Enraged, raid-wide AoE every 4 seconds
-            if e.state_timer <= 0.0 {
-                e.state_timer = 4.0;
-                e.ai_state = "aoe".to_string();
-                // Deal AoE damage to ALL players in dungeon
-                for pos in all_positions.iter() {
-                    if pos.dungeon_id != e.dungeon_id {
-                        continue;
-                    }
-                    if let Some(player) = ctx.db.player().identity().find(pos.identity) {
-                        let aoe_damage = (e.atk / 3).max(5); // Reduced damage but hits everyone
-                        ctx.db.player().identity().update(Player {
-                            hp: (player.hp - aoe_damage).max(0),
-                            ..player
-                        });
-                    }
-                }
+
+        let class = Some(player.player_class.as_str());
+        let first_today = ctx.db.daily_raid_clear().identity().find(identity)
+            .is_none_or(|c| c.last_clear_day < today);
+        let slot = items::pick_slot(&mut rng, &drop_table);
+        grant_item(ctx, identity, items::generate_gear(&mut rng, slot, player.level, first_today, class, boss_type));
+        if first_today && final_boss {
+            let slot = items::pick_slot(&mut rng, &drop_table);
+            let item = items::generate_legendary(&mut rng, slot, player.level, class, boss_type);
+            log::info!("{:?} earned {} from the daily raid clear", identity, item.name);
+            grant_item(ctx, identity, item);
+
+            let clear = DailyRaidClear { identity, last_clear_day: today };
+            if ctx.db.daily_raid_clear().identity().find(identity).is_some() {
+                ctx.db.daily_raid_clear().identity().update(clear);
             } else {
-                // Aggressive chase and attack
-                e.ai_state = "enrage".to_string();
-                if dist > ENEMY_ATTACK_RANGE {
-                    e.x += nx * speed * 1.5; // Faster when enraged
-                    e.y += ny * speed * 1.5;
-                } else {
-                    // Fast attacks
-                    if let Some(player) = ctx.db.player().identity().find(target.identity) {
-                        let damage = (e.atk - player.def / 2).max(1);
-                        ctx.db.player().identity().update(Player {
-                            hp: (player.hp - damage).max(0),
-                            ..player
-                        });
-                    }
-                }
+                ctx.db.daily_raid_clear().insert(clear);
             }
         }
-        _ => {}
     }
-}
 
-// ─── Helper Functions ──────────────────────────────────────────────────────────
+    store_dungeon_rng(ctx, dungeon.id, &rng);
+}
 
-/// Schedule the next enemy AI tick (50ms = 20Hz for smooth multiplayer sync)
-fn schedule_enemy_tick(ctx: &ReducerContext) {
-    ctx.db.enemy_tick_schedule().insert(EnemyTickSchedule {
-        scheduled_id: 0,
-        scheduled_at: ScheduleAt::Interval(TimeDuration::from_micros(50_000)),
+/// The last boss is down: everyone goes on cooldown and is healed up. The arena
+/// stays up until the party leaves through `complete_dungeon`.
+fn clear_raid(ctx: &ReducerContext, dungeon: ActiveDungeon, raid: RaidInstance) {
+    let now = ctx.timestamp.to_duration_since_unix_epoch()
+        .unwrap_or_default().as_millis() as u64;
+    let raid_id = raid.id;
+    ctx.db.raid_instance().id().update(RaidInstance {
+        boss_hp: 0,
+        ..raid
     });
-}
 
-/// Spawn enemies for a given room
-fn spawn_enemies_for_room(ctx: &ReducerContext, dungeon_id: u64, room_index: u32, depth: u32, seed: u64) {
-    let room_seed = seed.wrapping_add(room_index as u64 * 1337);
-    let mut pack_id_counter: u64 = seed.wrapping_add(room_index as u64);
-
-    // Fixed 4-room structure:
-    // Room 0: Basic (Training) - slimes, skeletons
-    // Room 1: Tactical (Chamber) - archers, chargers + mini-boss (Shield Knight)
-    // Room 2: Complex (Gauntlet) - necromancers, bombers, wolf packs
-    // Room 3: Raid (Arena) - raid boss only (requires 2+ players)
-    let enemy_types: Vec<&str> = match room_index {
-        0 => vec!["slime", "slime", "skeleton", "bat"],
-        1 => vec!["archer", "charger", "skeleton", "shield_knight"], // mini-boss: shield_knight
-        2 => vec!["wolf", "wolf", "necromancer", "bomber"],
-        3 => vec!["raid_boss"], // Raid boss room
-        _ => vec!["slime", "skeleton"],
-    };
+    let members: Vec<Identity> = ctx.db.raid_participant().iter()
+        .filter(|p| p.raid_id == raid_id)
+        .map(|p| p.player_identity)
+        .collect();
+    for identity in members {
+        set_raid_cooldown(ctx, identity, now + RAID_CLEAR_COOLDOWN_MS);
+        if let Some(player) = ctx.db.player().identity().find(identity) {
+            ctx.db.player().identity().update(Player {
+                hp: player.max_hp,
+                ..player
+            });
+        }
+    }
 
-    let enemy_count = enemy_types.len();
+    log::info!("Raid {} cleared in dungeon {} ({} wipes)", raid_id, dungeon.id, ctx.db.raid_instance().id().find(raid_id).map(|r| r.wipe_count).unwrap_or(0));
+    end_raid(ctx, raid_id);
+}
 
-    for i in 0..enemy_count {
-        let et = enemy_types[i];
-        let (hp, atk) = get_enemy_stats(et, depth);
+/// Put a server-rolled item straight into a player's inventory
+fn grant_item(ctx: &ReducerContext, identity: Identity, item: Item) {
+    ctx.db.inventory_item().insert(InventoryItem {
+        id: 0,
+        owner_identity: identity,
+        item_data_json: String::new(),
+        equipped_slot: None,
+        card_data_json: None,
+        item: Some(item),
+    });
+}
 
-        // Spread enemies around the room (raid boss centered)
-        let angle = if et == "raid_boss" {
-            0.0
-        } else {
-            (i as f32 / enemy_count as f32) * std::f32::consts::TAU
-        };
-        let radius = if et == "raid_boss" {
-            0.0 // Center of room
-        } else {
-            150.0 + (room_seed.wrapping_add(i as u64) % 80) as f32
-        };
-        let x = 270.0 + angle.cos() * radius; // Room center
-        let y = 360.0 + angle.sin() * radius;
-
-        // Initial AI state depends on enemy type
-        let (initial_state, pack_id) = match et {
-            "charger" => ("idle".to_string(), None),
-            "wolf" => {
-                pack_id_counter += 1;
-                ("orbit".to_string(), Some(pack_id_counter))
-            }
-            "bomber" => ("chase".to_string(), None),
-            "necromancer" => ("flee".to_string(), None),
-            "shield_knight" => ("advance".to_string(), None),
-            "archer" => ("kite".to_string(), None),
-            _ => ("chase".to_string(), None), // skeleton, slime, bat
-        };
+/// Raid week number; weeks roll over Monday 00:00 UTC (the epoch was a Thursday)
+fn raid_week(now_ms: u64) -> u32 {
+    ((now_ms / MS_PER_DAY + 3) / 7) as u32
+}
 
-        let is_boss = et == "boss" || et == "raid_boss";
-        ctx.db.dungeon_enemy().insert(DungeonEnemy {
-            id: 0, // auto_inc
-            dungeon_id,
-            room_index,
-            enemy_type: et.to_string(),
-            x,
-            y,
-            hp,
-            max_hp: hp,
-            atk,
-            is_alive: true,
-            ai_state: initial_state,
-            state_timer: 0.0,
-            target_x: x,
-            target_y: y,
-            facing_angle: angle,
-            pack_id,
-            // Threat system fields
-            current_target: None,
-            is_taunted: false,
-            taunted_by: None,
-            taunt_timer: 0.0,
-            // Boss fields
-            is_boss,
-            boss_phase: if is_boss { 1 } else { 0 },
-        });
+/// Arm the weekly reset if it isn't already scheduled
+fn schedule_raid_reset(ctx: &ReducerContext) {
+    if ctx.db.raid_reset_schedule().iter().count() == 0 {
+        insert_raid_reset(ctx);
     }
 }
 
-/// Get base stats for an enemy type, scaled by dungeon depth
-fn get_enemy_stats(enemy_type: &str, depth: u32) -> (i32, i32) {
-    let scale = 1.0 + (depth as f32 - 1.0) * 0.15;
-    let (base_hp, base_atk) = match enemy_type {
-        "skeleton" => (60, 12),
-        "slime" => (40, 8),
-        "charger" => (40, 20),
-        "necromancer" => (60, 5),
-        "bat" => (15, 6),
-        "wolf" => (20, 8),
-        "bomber" => (25, 30),
-        "shield_knight" => (70, 12),
-        "archer" => (35, 10),
-        "boss" => (300, 18),
-        "raid_boss" => (800, 25), // 3-phase raid boss
-        _ => (20, 5),
-    };
-    ((base_hp as f32 * scale) as i32, (base_atk as f32 * scale) as i32)
+/// Schedule the reset for the start of next raid week, unless one is already pending
+fn insert_raid_reset(ctx: &ReducerContext) {
+    let pending = ctx.db.raid_reset_schedule().iter()
+        .any(|r| matches!(r.scheduled_at, ScheduleAt::Time(at) if at > ctx.timestamp));
+    if pending {
+        return;
+    }
+    let now = ctx.timestamp.to_duration_since_unix_epoch()
+        .unwrap_or_default().as_millis() as u64;
+    let next_reset_ms = ((raid_week(now) as u64 + 1) * 7 - 3) * MS_PER_DAY;
+    ctx.db.raid_reset_schedule().insert(RaidResetSchedule {
+        scheduled_id: 0,
+        scheduled_at: ScheduleAt::Time(Timestamp::from_micros_since_unix_epoch(next_reset_ms as i64 * 1000)),
+    });
 }
 
-/// Get movement speed for enemy type (base pixels per tick at 60fps equivalent)
-fn get_enemy_speed(enemy_type: &str) -> f32 {
-    match enemy_type {
-        "charger" => ENEMY_MOVE_SPEED * 2.5,
-        "bat" => ENEMY_MOVE_SPEED * 1.5,
-        "wolf" => ENEMY_MOVE_SPEED * 1.8,
-        "necromancer" => ENEMY_MOVE_SPEED * 0.5,
-        "bomber" => ENEMY_MOVE_SPEED * 0.8,
-        "shield_knight" => ENEMY_MOVE_SPEED * 0.7,
-        "archer" => ENEMY_MOVE_SPEED * 0.6,
-        _ => ENEMY_MOVE_SPEED,
+/// Weekly reset: drop lockouts from past weeks and schedule the next reset
+#[reducer]
+pub fn reset_raid_lockouts(ctx: &ReducerContext, _arg: RaidResetSchedule) -> Result<(), String> {
+    // Only the scheduler may run the reset
+    if ctx.sender != ctx.identity() {
+        return Err("Raid resets are scheduled by the server".into());
+    }
+    let now = ctx.timestamp.to_duration_since_unix_epoch()
+        .unwrap_or_default().as_millis() as u64;
+    let week = raid_week(now);
+    let stale: Vec<u64> = ctx.db.raid_lockout().iter()
+        .filter(|l| l.week < week)
+        .map(|l| l.id)
+        .collect();
+    let count = stale.len();
+    for id in stale {
+        ctx.db.raid_lockout().id().delete(id);
     }
+    insert_raid_reset(ctx);
+    log::info!("Weekly raid reset (week {}): cleared {} lockouts", week, count);
+    Ok(())
 }
 
-/// Get XP reward for killing an enemy type
-fn get_enemy_xp(enemy_type: &str) -> u64 {
-    match enemy_type {
-        "skeleton" => 15,
-        "slime" => 10,
-        "charger" => 25,
-        "necromancer" => 50,
-        "bat" => 8,
-        "wolf" => 12,
-        "bomber" => 20,
-        "shield_knight" => 35,
-        "archer" => 18,
-        "boss" => 100,
-        _ => 10,
+/// Close a raid: stamp the instance and drop its participants
+fn end_raid(ctx: &ReducerContext, raid_id: u64) {
+    let now = ctx.timestamp.to_duration_since_unix_epoch()
+        .unwrap_or_default().as_millis() as u64;
+    if let Some(raid) = ctx.db.raid_instance().id().find(raid_id) {
+        ctx.db.raid_instance().id().update(RaidInstance {
+            ended_at: now,
+            ..raid
+        });
+    }
+
+    let participants: Vec<u64> = ctx.db.raid_participant().iter()
+        .filter(|p| p.raid_id == raid_id)
+        .map(|p| p.id)
+        .collect();
+    for id in participants {
+        ctx.db.raid_participant().id().delete(id);
     }
 }
 
-/// Drop loot when an enemy dies (takes individual fields to avoid borrow issues)
-fn drop_loot_for_dead_enemy(
-    ctx: &ReducerContext,
-    enemy_type: &str,
-    dungeon_id: u64,
-    room_index: u32,
-    x: f32,
-    y: f32,
-    atk: i32,
-    max_hp: i32,
-) {
-    // Determine rarity based on enemy type
-    // Boss/raid_boss: 5% legendary, 25% epic, 50% rare
-    // Shield_knight (mini-boss): 10% epic, 40% rare
-    // Others: standard rates
-    let is_boss = enemy_type == "boss" || enemy_type == "raid_boss";
-    let is_miniboss = enemy_type == "shield_knight";
-
-    let rarity = if is_boss {
-        let roll: f32 = (ctx.timestamp.to_duration_since_unix_epoch().unwrap_or_default().as_micros() % 100) as f32 / 100.0;
-        if roll < 0.05 { "legendary" }
-        else if roll < 0.30 { "epic" }
-        else if roll < 0.80 { "rare" }
-        else { "uncommon" }
-    } else if is_miniboss {
-        let roll: f32 = (ctx.timestamp.to_duration_since_unix_epoch().unwrap_or_default().as_micros() % 100) as f32 / 100.0;
-        if roll < 0.10 { "epic" }
-        else if roll < 0.50 { "rare" }
-        else { "uncommon" }
+fn set_raid_cooldown(ctx: &ReducerContext, identity: Identity, until: u64) {
+    let cooldown = RaidCooldown { identity, cooldown_until: until };
+    if ctx.db.raid_cooldown().identity().find(identity).is_some() {
+        ctx.db.raid_cooldown().identity().update(cooldown);
     } else {
-        // Regular enemies: 1% legendary for class gear
-        let roll: f32 = (ctx.timestamp.to_duration_since_unix_epoch().unwrap_or_default().as_micros() % 100) as f32 / 100.0;
-        if roll < 0.01 { "legendary" }
-        else {
-            match enemy_type {
-                "necromancer" => "rare",
-                "charger" => "uncommon",
-                _ => "common",
-            }
-        }
-    };
-
-    // For legendary drops, pick a random participant's class for class-specific gear
-    let class_tag = if rarity == "legendary" {
-        // Get all participants in this dungeon and pick random class
-        let participants: Vec<Identity> = ctx.db.dungeon_participant().iter()
-            .filter(|p| p.dungeon_id == dungeon_id)
-            .map(|p| p.player_identity)
-            .collect();
+        ctx.db.raid_cooldown().insert(cooldown);
+    }
+}
 
-        if !participants.is_empty() {
-            let idx = (ctx.timestamp.to_duration_since_unix_epoch().unwrap_or_default().as_micros() as usize) % participants.len();
-            if let Some(player) = ctx.db.player().identity().find(participants[idx]) {
-                format!(",\"classReq\":\"{}\"", player.player_class)
-            } else {
-                String::new()
-            }
-        } else {
-            String::new()
-        }
-    } else {
-        String::new()
-    };
+/// Drop combat events older than COMBAT_EVENT_TTL_MS
+fn prune_combat_events(ctx: &ReducerContext) {
+    let now = ctx.timestamp.to_duration_since_unix_epoch()
+        .unwrap_or_default().as_millis() as u64;
+    let expired: Vec<u64> = ctx.db.combat_event().iter()
+        .filter(|ev| ev.created_at + COMBAT_EVENT_TTL_MS <= now)
+        .map(|ev| ev.id)
+        .collect();
+    for id in expired {
+        ctx.db.combat_event().id().delete(id);
+    }
+}
 
-    let item_json = format!(
-        r#"{{"type":"drop","source":"{}","atk_bonus":{},"def_bonus":{},"rarity":"{}"{}}}"#,
-        enemy_type,
-        atk / 2,
-        max_hp / 10,
-        rarity,
-        class_tag,
-    );
+/// Total % damage bonus from socketed cards against an enemy type
+fn card_bonus_vs(gear: &[Item], enemy_type: &str) -> i32 {
+    gear.iter()
+        .filter_map(|item| item.card.as_ref())
+        .map(|card| items::card_effect(&card.card_type))
+        .filter(|effect| effect.bonus_vs.contains(&enemy_type))
+        .map(|effect| effect.bonus_pct)
+        .sum()
+}
 
-    ctx.db.loot_drop().insert(LootDrop {
+/// Roll an enemy's card drop straight into the killer's card inventory
+fn roll_card_drop(ctx: &ReducerContext, rng: &mut Rng, killer: Identity, enemy_type: &str) {
+    if !rng.chance(items::card_drop_chance(enemy_type)) {
+        return;
+    }
+    ctx.db.player_card().insert(PlayerCard {
         id: 0,
-        dungeon_id,
-        room_index,
-        x,
-        y,
-        item_data_json: item_json,
-        rarity: rarity.to_string(),
-        picked_up: false,
+        owner_identity: killer,
+        card_type: enemy_type.to_string(),
     });
+    log::info!("{:?} found a {} card", killer, enemy_type);
 }
 
-/// Check if player should level up, returns (new_level, new_max_hp, new_atk, new_def)
-fn check_level_up(level: u32, xp: u64, max_hp: i32, atk: i32, def: i32) -> (u32, i32, i32, i32) {
-    let mut lvl = level;
-    let mut hp = max_hp;
-    let mut a = atk;
-    let mut d = def;
-
-    // Keep leveling up while XP exceeds threshold
-    while xp >= lvl as u64 * BASE_XP_PER_LEVEL {
-        lvl += 1;
-        hp += 10;
-        a += 2;
-        d += 1;
+/// Recompute and store a player's derived stats (after equip/unequip/level-up)
+fn recompute_player_stats(ctx: &ReducerContext, identity: Identity) {
+    if let Some(player) = ctx.db.player().identity().find(identity) {
+        ctx.db.player().identity().update(with_derived_stats(ctx, player));
     }
-
-    (lvl, hp, a, d)
 }
 
 /// Clean up all enemies and loot for a dungeon
@@ -2339,6 +5036,83 @@ fn cleanup_dungeon(ctx: &ReducerContext, dungeon_id: u64) {
     for id in messages {
         ctx.db.player_message().id().delete(id);
     }
+
+    // Delete downed markers
+    let downed: Vec<Identity> = ctx.db.downed_player().iter()
+        .filter(|d| d.dungeon_id == dungeon_id)
+        .map(|d| d.identity)
+        .collect();
+    for identity in downed {
+        ctx.db.downed_player().identity().delete(identity);
+    }
+
+    // Delete status effects
+    let effects: Vec<u64> = ctx.db.status_effect().iter()
+        .filter(|fx| fx.dungeon_id == dungeon_id)
+        .map(|fx| fx.id)
+        .collect();
+    for id in effects {
+        ctx.db.status_effect().id().delete(id);
+    }
+
+    // Delete combat events
+    let events: Vec<u64> = ctx.db.combat_event().iter()
+        .filter(|ev| ev.dungeon_id == dungeon_id)
+        .map(|ev| ev.id)
+        .collect();
+    for id in events {
+        ctx.db.combat_event().id().delete(id);
+    }
+
+    // Delete boss mechanic timers and telegraphed zones
+    let timers: Vec<u64> = ctx.db.boss_mechanic_timer().iter()
+        .filter(|t| t.dungeon_id == dungeon_id)
+        .map(|t| t.id)
+        .collect();
+    for id in timers {
+        ctx.db.boss_mechanic_timer().id().delete(id);
+    }
+    let zones: Vec<u64> = ctx.db.telegraph_zone().iter()
+        .filter(|z| z.dungeon_id == dungeon_id)
+        .map(|z| z.id)
+        .collect();
+    for id in zones {
+        ctx.db.telegraph_zone().id().delete(id);
+    }
+
+    // Delete projectiles in flight
+    let projectiles: Vec<u64> = ctx.db.projectile().iter()
+        .filter(|p| p.dungeon_id == dungeon_id)
+        .map(|p| p.id)
+        .collect();
+    for id in projectiles {
+        ctx.db.projectile().id().delete(id);
+    }
+
+    // Delete the room graph
+    let rooms: Vec<u64> = ctx.db.dungeon_room().iter()
+        .filter(|r| r.dungeon_id == dungeon_id)
+        .map(|r| r.id)
+        .collect();
+    for id in rooms {
+        ctx.db.dungeon_room().id().delete(id);
+    }
+    let connections: Vec<u64> = ctx.db.room_connection().iter()
+        .filter(|c| c.dungeon_id == dungeon_id)
+        .map(|c| c.id)
+        .collect();
+    for id in connections {
+        ctx.db.room_connection().id().delete(id);
+    }
+
+    // Delete room layouts
+    let layouts: Vec<u64> = ctx.db.room_tile_map().iter()
+        .filter(|m| m.dungeon_id == dungeon_id)
+        .map(|m| m.id)
+        .collect();
+    for id in layouts {
+        ctx.db.room_tile_map().id().delete(id);
+    }
 }
 
 // ─── Game Mode Reducers ─────────────────────────────────────────────────────────
@@ -2397,6 +5171,7 @@ pub fn enter_open_world(ctx: &ReducerContext) -> Result<(), String> {
             id: 0,
             created_at: timestamp,
             player_count: 1,
+            rng_state: ctx.timestamp.to_duration_since_unix_epoch().unwrap_or_default().as_micros() as u64,
         });
 
         // Spawn enemies for all rooms in the new instance
@@ -2411,6 +5186,7 @@ pub fn enter_open_world(ctx: &ReducerContext) -> Result<(), String> {
     };
 
     // Spawn player at town center (5, 5)
+    start_movement_grace(ctx, ctx.sender);
     ctx.db.open_world_player().insert(OpenWorldPlayer {
         identity: ctx.sender,
         instance_id,
@@ -2484,6 +5260,7 @@ pub fn leave_open_world(ctx: &ReducerContext) -> Result<(), String> {
 }
 
 /// Update player position in Open World
+#[allow(clippy::too_many_arguments)]
 #[reducer]
 pub fn update_open_world_position(
     ctx: &ReducerContext,
@@ -2501,10 +5278,22 @@ pub fn update_open_world_position(
         .ok_or("Not in Open World")?;
 
     // Validate room bounds
-    if room_x < 0 || room_x >= OPEN_WORLD_SIZE || room_y < 0 || room_y >= OPEN_WORLD_SIZE {
+    if !(0..OPEN_WORLD_SIZE).contains(&room_x) || !(0..OPEN_WORLD_SIZE).contains(&room_y) {
         return Err("Invalid room coordinates".into());
     }
 
+    // Measure the move in the old room's frame so walking through an edge into the
+    // next room is a short step, and hopping across the map is a long one
+    let (x, y) = clamp_to_room(ctx, ctx.sender, x, y);
+    let to = (
+        x + (room_x - ow_player.room_x) as f32 * ROOM_W,
+        y + (room_y - ow_player.room_y) as f32 * ROOM_H,
+    );
+    let (vx, vy) = validate_move(ctx, ctx.sender, (ow_player.x, ow_player.y), to);
+    let (room_dx, room_dy) = ((vx / ROOM_W).floor() as i32, (vy / ROOM_H).floor() as i32);
+    let (room_x, room_y) = (ow_player.room_x + room_dx, ow_player.room_y + room_dy);
+    let (x, y) = (vx - room_dx as f32 * ROOM_W, vy - room_dy as f32 * ROOM_H);
+
     ctx.db.open_world_player().identity().update(OpenWorldPlayer {
         room_x,
         room_y,
@@ -2548,9 +5337,37 @@ pub fn attack_open_world(ctx: &ReducerContext, enemy_id: u64) -> Result<(), Stri
         return Err("Target out of range".into());
     }
 
-    // Calculate damage
-    let damage = player.atk.max(1);
-    let new_hp = enemy.hp - damage;
+    let gear = equipped_items(ctx, ctx.sender);
+    start_swing(ctx, 0, &gear)?;
+
+    // Calculate damage, including socketed card bonuses vs this enemy type
+    let mut damage = player.atk.max(1);
+    let card_bonus = card_bonus_vs(&gear, &enemy.enemy_type);
+    if card_bonus > 0 {
+        damage = damage * (100 + card_bonus) / 100;
+    }
+
+    // Open world enemies run the simple chase AI, so shields are always raised
+    let definition = enemy_definition(ctx, &enemy.enemy_type);
+    let defender = Defender {
+        def: enemy.def,
+        resistances: &definition.resistances,
+        x: enemy.x,
+        y: enemy.y,
+        facing_angle: enemy.facing_angle,
+        shield_raised: definition.ai == AiArchetype::ShieldKnight,
+        dodge: definition.dodge,
+        block: definition.block,
+    };
+
+    let now = ctx.timestamp.to_duration_since_unix_epoch()
+        .unwrap_or_default().as_millis() as u64;
+    let mut rng = load_open_world_rng(ctx, enemy.instance_id);
+    let damage_type = weapon_damage_type(&gear);
+    let hit = combat::resolve_damage(&mut rng, damage, damage_type, &player_attacker(&player, ow_player.x, ow_player.y), &defender);
+    store_open_world_rng(ctx, enemy.instance_id, &rng);
+    log_combat_event(ctx, 0, Combatant::Player(ctx.sender), Combatant::OpenWorldEnemy(enemy_id), &hit, damage_type, (enemy.x, enemy.y));
+    let new_hp = enemy.hp - hit.amount;
 
     // Calculate XP with level scaling
     let enemy_level = get_enemy_level_for_room(enemy.room_x, enemy.room_y);
@@ -2565,15 +5382,15 @@ pub fn attack_open_world(ctx: &ReducerContext, enemy_id: u64) -> Result<(), Stri
 
     if new_hp <= 0 {
         // Enemy dies
-        let base_xp = get_enemy_xp(&enemy.enemy_type);
+        let base_xp = get_enemy_xp(ctx, &enemy.enemy_type);
         let scaled_xp = (base_xp as f32 * xp_mult) as u64;
 
         // Set respawn timer
         let is_hotspot = is_hotspot_room(enemy.room_x, enemy.room_y);
         let respawn_delay = if is_hotspot { OPEN_WORLD_HOTSPOT_RESPAWN_MS } else { OPEN_WORLD_BASE_RESPAWN_MS };
-        let respawn_at = ctx.timestamp.to_duration_since_unix_epoch()
-            .unwrap_or_default().as_millis() as u64 + respawn_delay;
+        let respawn_at = now + respawn_delay;
 
+        roll_card_drop(ctx, &mut rng, ctx.sender, &enemy.enemy_type);
         ctx.db.open_world_enemy().id().update(OpenWorldEnemy {
             hp: 0,
             is_alive: false,
@@ -2583,17 +5400,12 @@ pub fn attack_open_world(ctx: &ReducerContext, enemy_id: u64) -> Result<(), Stri
 
         // Award XP
         let new_xp = player.xp + scaled_xp;
-        let (new_level, new_max_hp, new_atk, new_def) = check_level_up(
-            player.level, new_xp, player.max_hp, player.atk, player.def,
-        );
-        ctx.db.player().identity().update(Player {
+        let new_level = check_level_up(player.level, new_xp);
+        ctx.db.player().identity().update(with_derived_stats(ctx, Player {
             xp: new_xp,
             level: new_level,
-            max_hp: new_max_hp,
-            atk: new_atk,
-            def: new_def,
             ..player
-        });
+        }));
 
         log::info!("Open World enemy {} killed, +{}xp (scaled)", enemy_id, scaled_xp);
     } else {
@@ -2613,11 +5425,11 @@ pub fn queue_dungeon(ctx: &ReducerContext, dungeon_tier: u32, difficulty: u32) -
         return Err("Player not found".into());
     }
 
-    if dungeon_tier < 1 || dungeon_tier > 3 {
+    if !(1..=3).contains(&dungeon_tier) {
         return Err("Invalid dungeon tier (1-3)".into());
     }
 
-    if difficulty < 1 || difficulty > 5 {
+    if !(1..=5).contains(&difficulty) {
         return Err("Invalid difficulty (1-5 stars)".into());
     }
 
@@ -2654,10 +5466,14 @@ pub fn start_dungeon_solo(ctx: &ReducerContext, dungeon_tier: u32, difficulty: u
     let player = ctx.db.player().identity().find(ctx.sender)
         .ok_or("Player not found")?;
 
-    if dungeon_tier < 1 || dungeon_tier > 3 {
+    if !(1..=3).contains(&dungeon_tier) {
         return Err("Invalid dungeon tier (1-3)".into());
     }
 
+    if !(1..=5).contains(&difficulty) {
+        return Err("Invalid difficulty (1-5 stars)".into());
+    }
+
     // Cancel any existing queue
     if ctx.db.dungeon_queue().identity().find(ctx.sender).is_some() {
         ctx.db.dungeon_queue().identity().delete(ctx.sender);
@@ -2677,6 +5493,11 @@ pub fn start_dungeon_solo(ctx: &ReducerContext, dungeon_tier: u32, difficulty: u
         current_room: 0,
         total_rooms: 1,  // Single room for tiered dungeons
         seed,
+        tier: dungeon_tier,
+        difficulty,
+        started_at: seed / 1000,
+        rng_state: seed,
+        raid_id: None,
     });
 
     ctx.db.dungeon_participant().insert(DungeonParticipant {
@@ -2686,7 +5507,10 @@ pub fn start_dungeon_solo(ctx: &ReducerContext, dungeon_tier: u32, difficulty: u
     });
 
     // Spawn enemies for the tier's room (room_index = tier - 1)
-    spawn_enemies_for_tier(ctx, dungeon.id, dungeon_tier, stat_mult, seed);
+    let mut rng = Rng::new(seed);
+    let tiles = create_room_layout(ctx, &mut rng, dungeon.id, 0, &[], ROOM_BASE_FEATURES + dungeon_tier);
+    spawn_enemies_for_tier(ctx, dungeon.id, dungeon_tier, stat_mult, &mut rng, &tiles);
+    store_dungeon_rng(ctx, dungeon.id, &rng);
 
     // Start enemy AI tick
     if ctx.db.enemy_tick_schedule().iter().count() == 0 {
@@ -2694,6 +5518,7 @@ pub fn start_dungeon_solo(ctx: &ReducerContext, dungeon_tier: u32, difficulty: u
     }
 
     // Initialize player position
+    start_movement_grace(ctx, ctx.sender);
     ctx.db.player_position().insert(PlayerPosition {
         identity: ctx.sender,
         dungeon_id: dungeon.id,
@@ -2718,7 +5543,7 @@ pub fn start_dungeon_solo(ctx: &ReducerContext, dungeon_tier: u32, difficulty: u
         });
     }
 
-    log::info!("Started solo dungeon tier {} difficulty {} for {:?}", dungeon_tier, difficulty, ctx.sender);
+    log::info!("Started solo dungeon tier {} difficulty {} for {:?} (seed {})", dungeon_tier, difficulty, ctx.sender, seed);
     Ok(())
 }
 
@@ -2801,6 +5626,13 @@ pub fn tick_open_world(ctx: &ReducerContext, _arg: OpenWorldTickSchedule) {
     // Collect all open world players for AI targeting
     let players: Vec<OpenWorldPlayer> = ctx.db.open_world_player().iter().collect();
 
+    // Open world rooms have no obstacles; flow fields are still shared per target and room
+    let open_field = room_layout::open_field();
+    let mut flow_fields: std::collections::HashMap<(u64, i32, i32, Identity), FlowField> = std::collections::HashMap::new();
+
+    // Each shard's RNG stream, written back after the tick
+    let mut rngs: std::collections::HashMap<u64, Rng> = std::collections::HashMap::new();
+
     // Process alive enemies - chase and attack players
     for enemy in ctx.db.open_world_enemy().iter() {
         if !enemy.is_alive {
@@ -2833,12 +5665,15 @@ pub fn tick_open_world(ctx: &ReducerContext, _arg: OpenWorldTickSchedule) {
             e.facing_angle = ny.atan2(nx);
 
             // Use same speed calculation as dungeon enemies
-            let speed = get_enemy_speed(&e.enemy_type) * dt * 60.0;
+            let speed = get_enemy_speed(ctx, &e.enemy_type) * dt * 60.0;
 
             // Chase if not in attack range (use same range as dungeon)
             if dist > ENEMY_ATTACK_RANGE {
-                e.x += nx * speed;
-                e.y += ny * speed;
+                let flow = flow_fields.entry((e.instance_id, e.room_x, e.room_y, target.identity))
+                    .or_insert_with(|| FlowField::toward(&open_field, target.x, target.y));
+                let (mx, my) = flow.direction(e.x, e.y).unwrap_or((nx, ny));
+                e.x += mx * speed;
+                e.y += my * speed;
                 // Clamp to room bounds
                 e.x = e.x.clamp(20.0, ROOM_W - 20.0);
                 e.y = e.y.clamp(20.0, ROOM_H - 20.0);
@@ -2852,14 +5687,11 @@ pub fn tick_open_world(ctx: &ReducerContext, _arg: OpenWorldTickSchedule) {
                     e.ai_state = "attack".to_string();
 
                     // Deal damage to player
-                    if let Some(player) = ctx.db.player().identity().find(target.identity) {
-                        let damage = (e.atk - player.def / 2).max(1);
-                        let new_hp = player.hp - damage;
-                        ctx.db.player().identity().update(Player {
-                            hp: new_hp.max(0),
-                            ..player
-                        });
-                    }
+                    let definition = enemy_definition(ctx, &e.enemy_type);
+                    let attacker = Attacker { x: e.x, y: e.y, crit_chance: definition.crit_chance, crit_damage: definition.crit_damage };
+                    let rng = rngs.entry(e.instance_id)
+                        .or_insert_with(|| load_open_world_rng(ctx, e.instance_id));
+                    hit_player(ctx, rng, 0, Combatant::OpenWorldEnemy(e.id), &attacker, target.identity, (target.x, target.y), e.atk, DamageType::Physical);
                 }
             }
 
@@ -2867,6 +5699,10 @@ pub fn tick_open_world(ctx: &ReducerContext, _arg: OpenWorldTickSchedule) {
         }
     }
 
+    for (instance_id, rng) in rngs {
+        store_open_world_rng(ctx, instance_id, &rng);
+    }
+
     // Respawn dead enemies whose timer has expired
     let dead_enemies: Vec<OpenWorldEnemy> = ctx.db.open_world_enemy().iter()
         .filter(|e| !e.is_alive && e.respawn_at > 0 && e.respawn_at <= now)
@@ -2875,12 +5711,13 @@ pub fn tick_open_world(ctx: &ReducerContext, _arg: OpenWorldTickSchedule) {
     for enemy in dead_enemies {
         // Get appropriate level for the room
         let level = get_enemy_level_for_room(enemy.room_x, enemy.room_y);
-        let (hp, atk) = get_enemy_stats(&enemy.enemy_type, level);
+        let (hp, atk, def) = get_enemy_stats(ctx, &enemy.enemy_type, level);
 
         ctx.db.open_world_enemy().id().update(OpenWorldEnemy {
             hp,
             max_hp: hp,
             atk,
+            def,
             is_alive: true,
             respawn_at: 0,
             ai_state: "chase".to_string(),
@@ -2951,7 +5788,7 @@ fn spawn_open_world_enemies(ctx: &ReducerContext, instance_id: u64) {
 
             for spawn_idx in 0..num_spawns {
                 let enemy_type = get_enemy_type_for_zone(level);
-                let (hp, atk) = get_enemy_stats(&enemy_type, level);
+                let (hp, atk, def) = get_enemy_stats(ctx, &enemy_type, level);
 
                 // Distribute spawn points around the room
                 let angle = (spawn_idx as f32 / num_spawns as f32) * std::f32::consts::TAU;
@@ -2978,6 +5815,7 @@ fn spawn_open_world_enemies(ctx: &ReducerContext, instance_id: u64) {
                     target_x: x,
                     target_y: y,
                     facing_angle: angle,
+                    def,
                 });
             }
         }
@@ -2998,7 +5836,7 @@ fn cleanup_open_world_instance(ctx: &ReducerContext, instance_id: u64) {
     ctx.db.open_world_instance().id().delete(instance_id);
 }
 
-fn spawn_enemies_for_tier(ctx: &ReducerContext, dungeon_id: u64, tier: u32, stat_mult: f32, _seed: u64) {
+fn spawn_enemies_for_tier(ctx: &ReducerContext, dungeon_id: u64, tier: u32, stat_mult: f32, rng: &mut Rng, tiles: &[Tile]) {
     // Tier 1: slimes, skeletons (Training Grounds)
     // Tier 2: archers, chargers, shield_knight (Tactical Chamber)
     // Tier 3: wolves, necromancer, bomber (The Gauntlet)
@@ -3010,24 +5848,22 @@ fn spawn_enemies_for_tier(ctx: &ReducerContext, dungeon_id: u64, tier: u32, stat
     };
 
     for (i, et) in enemy_types.iter().enumerate() {
-        let (base_hp, base_atk) = get_enemy_stats(et, tier);
+        let (base_hp, base_atk, base_def) = get_enemy_stats(ctx, et, tier);
         let hp = (base_hp as f32 * stat_mult) as i32;
         let atk = (base_atk as f32 * stat_mult) as i32;
+        let def = (base_def as f32 * stat_mult) as i32;
 
         let angle = (i as f32 / enemy_types.len() as f32) * std::f32::consts::TAU;
-        let radius = 150.0 + (i as f32 * 20.0);
-        let x = ROOM_W / 2.0 + angle.cos() * radius;
-        let y = ROOM_H / 2.0 + angle.sin() * radius;
-
-        let (initial_state, pack_id) = match *et {
-            "charger" => ("idle".to_string(), None),
-            "wolf" => ("orbit".to_string(), Some(dungeon_id)),
-            "bomber" => ("chase".to_string(), None),
-            "necromancer" => ("flee".to_string(), None),
-            "shield_knight" => ("advance".to_string(), None),
-            "archer" => ("kite".to_string(), None),
-            _ => ("chase".to_string(), None),
-        };
+        let radius = 150.0 + rng.range_u32(0, 79) as f32;
+        let (x, y) = room_layout::nearest_walkable(
+            tiles,
+            ROOM_W / 2.0 + angle.cos() * radius,
+            ROOM_H / 2.0 + angle.sin() * radius,
+        );
+
+        let ai = enemy_definition(ctx, et).ai;
+        let pack_id = (ai == AiArchetype::Wolf).then_some(dungeon_id);
+        let initial_state = ai.initial_state().to_string();
 
         ctx.db.dungeon_enemy().insert(DungeonEnemy {
             id: 0,
@@ -3052,6 +5888,9 @@ fn spawn_enemies_for_tier(ctx: &ReducerContext, dungeon_id: u64, tier: u32, stat
             taunt_timer: 0.0,
             is_boss: false,
             boss_phase: 0,
+            summoner_id: None,
+            def,
+            is_priority: false,
         });
     }
 }
@@ -3064,7 +5903,7 @@ fn process_dungeon_queues(ctx: &ReducerContext, now: u64) {
     let mut groups: std::collections::HashMap<(u32, u32), Vec<DungeonQueue>> = std::collections::HashMap::new();
     for q in queued {
         groups.entry((q.dungeon_tier, q.difficulty))
-            .or_insert_with(Vec::new)
+            .or_default()
             .push(q);
     }
 
@@ -3086,6 +5925,11 @@ fn process_dungeon_queues(ctx: &ReducerContext, now: u64) {
                 current_room: 0,
                 total_rooms: 1,
                 seed,
+                tier,
+                difficulty,
+                started_at: now,
+                rng_state: seed,
+                raid_id: None,
             });
 
             // Add all players as participants
@@ -3098,6 +5942,7 @@ fn process_dungeon_queues(ctx: &ReducerContext, now: u64) {
 
                 // Initialize position
                 if let Some(player) = ctx.db.player().identity().find(p.identity) {
+                    start_movement_grace(ctx, p.identity);
                     ctx.db.player_position().insert(PlayerPosition {
                         identity: p.identity,
                         dungeon_id: dungeon.id,
@@ -3129,14 +5974,17 @@ fn process_dungeon_queues(ctx: &ReducerContext, now: u64) {
 
             // Spawn enemies with loot bonus for party size
             let loot_bonus = 1.0 + (players.len() as f32 - 1.0) * 0.1; // +10% per extra player
-            spawn_enemies_for_tier(ctx, dungeon.id, tier, stat_mult * loot_bonus, seed);
+            let mut rng = Rng::new(seed);
+            let tiles = create_room_layout(ctx, &mut rng, dungeon.id, 0, &[], ROOM_BASE_FEATURES + tier);
+            spawn_enemies_for_tier(ctx, dungeon.id, tier, stat_mult * loot_bonus, &mut rng, &tiles);
+            store_dungeon_rng(ctx, dungeon.id, &rng);
 
             // Start enemy AI tick
             if ctx.db.enemy_tick_schedule().iter().count() == 0 {
                 schedule_enemy_tick(ctx);
             }
 
-            log::info!("Started co-op dungeon tier {} with {} players", tier, players.len());
+            log::info!("Started co-op dungeon tier {} with {} players (seed {})", tier, players.len(), seed);
         }
     }
 }
@@ -3144,18 +5992,18 @@ fn process_dungeon_queues(ctx: &ReducerContext, now: u64) {
 fn process_raid_queues(ctx: &ReducerContext, now: u64) {
     // Need exactly: 1 tank, 1 healer, 2 dps
     let tanks: Vec<RaidQueue> = ctx.db.raid_queue().iter()
-        .filter(|q| q.player_class == "tank")
+        .filter(|q| q.player_class == "tank" && raid_eligible(ctx, q.identity, now))
         .collect();
     let healers: Vec<RaidQueue> = ctx.db.raid_queue().iter()
-        .filter(|q| q.player_class == "healer")
+        .filter(|q| q.player_class == "healer" && raid_eligible(ctx, q.identity, now))
         .collect();
     let dps: Vec<RaidQueue> = ctx.db.raid_queue().iter()
-        .filter(|q| q.player_class == "dps")
+        .filter(|q| q.player_class == "dps" && raid_eligible(ctx, q.identity, now))
         .collect();
 
-    if tanks.len() >= 1 && healers.len() >= 1 && dps.len() >= 2 {
+    if !tanks.is_empty() && !healers.is_empty() && dps.len() >= 2 {
         // Form raid party
-        let party = vec![
+        let party = [
             tanks[0].identity,
             healers[0].identity,
             dps[0].identity,
@@ -3163,7 +6011,8 @@ fn process_raid_queues(ctx: &ReducerContext, now: u64) {
         ];
 
         // Create raid instance
-        let (boss_hp, _boss_atk) = get_enemy_stats("raid_boss", 1);
+        let bosses = encounter_bosses(ctx, raid_encounters::DEFAULT_ENCOUNTER);
+        let (boss_hp, _boss_atk, _boss_def) = get_enemy_stats(ctx, &bosses[0], 1);
         let raid = ctx.db.raid_instance().insert(RaidInstance {
             id: 0,
             started_at: now,
@@ -3171,33 +6020,99 @@ fn process_raid_queues(ctx: &ReducerContext, now: u64) {
             boss_max_hp: boss_hp,
             boss_phase: 1,
             wipe_count: 0,
+            ended_at: 0,
+            boss_index: 0,
         });
 
-        // Add participants
-        for pid in &party {
-            if let Some(player) = ctx.db.player().identity().find(*pid) {
-                ctx.db.raid_participant().insert(RaidParticipant {
-                    id: 0,
-                    raid_id: raid.id,
-                    player_identity: *pid,
-                    player_class: player.player_class.clone(),
-                    disconnected_at: None,
-                });
+        // The fight runs in a single-room arena dungeon led by the tank
+        let seed = now;
+        let dungeon = ctx.db.active_dungeon().insert(ActiveDungeon {
+            id: 0,
+            owner_identity: party[0],
+            depth: 1,
+            current_room: 0,
+            total_rooms: 1,
+            seed,
+            tier: 0,
+            difficulty: 1,
+            started_at: now,
+            rng_state: seed,
+            raid_id: Some(raid.id),
+        });
+        let mut rng = Rng::new(seed);
+        let tiles = create_room_layout(ctx, &mut rng, dungeon.id, 0, &[], RAID_ARENA_FEATURES);
+        spawn_enemies_for_room(ctx, dungeon.id, 0, 1, &[bosses[0].as_str()], &mut rng, &tiles);
+        store_dungeon_rng(ctx, dungeon.id, &rng);
+
+        // Add participants, lined up at the south end of the arena facing the boss
+        for (slot, pid) in party.iter().enumerate() {
+            add_raid_member(ctx, raid.id, dungeon.id, *pid, slot, &tiles);
+        }
 
-                // Remove from queue
-                ctx.db.raid_queue().identity().delete(*pid);
+        // Start enemy AI tick
+        if ctx.db.enemy_tick_schedule().iter().count() == 0 {
+            schedule_enemy_tick(ctx);
+        }
+        // Modules published before lockouts existed never armed the reset in init
+        schedule_raid_reset(ctx);
 
-                // Update game mode
-                if let Some(gm) = ctx.db.player_game_mode().identity().find(*pid) {
-                    ctx.db.player_game_mode().identity().update(PlayerGameMode {
-                        mode: "raid".to_string(),
-                        instance_id: Some(raid.id),
-                        ..gm
-                    });
-                }
+        log::info!("Started raid {} in arena dungeon {} with party of 4", raid.id, dungeon.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEED: f32 = PLAYER_BASE_MOVE_SPEED * MOVE_SPEED_TOLERANCE;
+
+    /// Send `updates` moves `interval_ms` apart, each asking for `step` pixels,
+    /// and return the total distance the budget let through
+    fn travel(updates: u32, interval_ms: u64, step: f32) -> f32 {
+        let mut budget = MOVE_LATENCY_MS as f32;
+        let mut total = 0.0;
+        for _ in 0..updates {
+            let (allowed, left) = spend_travel_budget(budget, interval_ms, SPEED, step);
+            total += step.min(allowed);
+            budget = left;
+        }
+        total
+    }
+
+    #[test]
+    fn rapid_updates_cannot_beat_the_speed_cap() {
+        // 100 updates 10ms apart is one second of travel
+        let cap = SPEED + SPEED * MOVE_LATENCY_MS as f32 / 1000.0;
+        let total = travel(100, 10, 50.0);
+        assert!(total <= cap + 0.01, "travelled {total}px in 1s, cap {cap}px");
+    }
+
+    #[test]
+    fn full_speed_moves_are_never_clamped() {
+        for interval_ms in [10, 50, 100, 250] {
+            let step = SPEED * interval_ms as f32 / 1000.0;
+            let mut budget = 0.0;
+            for _ in 0..50 {
+                let (allowed, left) = spend_travel_budget(budget, interval_ms, SPEED, step);
+                assert!(step <= allowed + 0.01, "{interval_ms}ms updates clamped");
+                budget = left;
             }
         }
+    }
+
+    #[test]
+    fn late_packets_can_use_the_carried_budget() {
+        // A 70ms gap followed by a 30ms one still covers 100ms of travel
+        let (_, budget) = spend_travel_budget(0.0, 70, SPEED, SPEED * 0.05);
+        let (allowed, _) = spend_travel_budget(budget, 30, SPEED, SPEED * 0.05);
+        assert!(allowed >= SPEED * 0.05);
+    }
 
-        log::info!("Started raid with party of 4");
+    #[test]
+    fn idle_time_is_not_banked() {
+        let (_, budget) = spend_travel_budget(MOVE_LATENCY_MS as f32, 5000, SPEED, 0.0);
+        assert_eq!(budget, MOVE_LATENCY_MS as f32);
+        let (allowed, _) = spend_travel_budget(0.0, 5000, SPEED, 10_000.0);
+        assert!(allowed <= SPEED * MOVE_MAX_WINDOW_MS as f32 / 1000.0 + 0.01);
     }
 }
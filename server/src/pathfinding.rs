@@ -0,0 +1,243 @@
+//! Grid pathfinding over room tile maps.
+//! A flow field is a BFS distance map from a target's cell; every enemy chasing that
+//! target in the same room reads its next step from the same field each tick.
+
+use std::collections::VecDeque;
+
+use crate::room_layout::{self, Tile, ROOM_COLS, ROOM_ROWS};
+
+const UNREACHABLE: u32 = u32::MAX;
+/// Spacing of line-of-sight samples, in pixels
+const LOS_STEP: f32 = 9.0;
+
+/// 8-way neighbor offsets (orthogonal first)
+const DIRS: [(i32, i32); 8] = [(1, 0), (-1, 0), (0, 1), (0, -1), (1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+pub struct FlowField {
+    goal: (f32, f32),
+    walkable: Vec<bool>,
+    dist: Vec<u32>,
+}
+
+impl FlowField {
+    /// Build a field leading to the given position over the walkable tiles
+    pub fn toward(tiles: &[Tile], goal_x: f32, goal_y: f32) -> Self {
+        let walkable: Vec<bool> = tiles.iter().map(|t| t.is_walkable()).collect();
+        let mut dist = vec![UNREACHABLE; walkable.len()];
+        let mut queue = VecDeque::new();
+        if let Some((col, row)) = room_layout::cell_at(goal_x, goal_y) {
+            // Seed the goal even if the target stands on a blocked edge
+            dist[idx(col, row)] = 0;
+            queue.push_back((col, row));
+        }
+        while let Some((col, row)) = queue.pop_front() {
+            let d = dist[idx(col, row)];
+            for (nc, nr) in room_layout::neighbors(col, row) {
+                let i = idx(nc, nr);
+                if walkable[i] && dist[i] == UNREACHABLE {
+                    dist[i] = d + 1;
+                    queue.push_back((nc, nr));
+                }
+            }
+        }
+        FlowField { goal: (goal_x, goal_y), walkable, dist }
+    }
+
+    /// Unit direction to move from (x, y) toward the goal. Heads straight for the
+    /// goal when the line is clear, otherwise steps to the neighboring cell with the
+    /// lowest distance. None if the goal can't be reached.
+    pub fn direction(&self, x: f32, y: f32) -> Option<(f32, f32)> {
+        if self.line_clear((x, y), self.goal) {
+            return normalize(self.goal.0 - x, self.goal.1 - y);
+        }
+        let (col, row) = room_layout::cell_at(x, y)?;
+        let mut best: Option<((u32, u32), u32)> = None;
+        for (dc, dr) in DIRS {
+            let (nc, nr) = (col as i32 + dc, row as i32 + dr);
+            if !self.open(nc, nr) {
+                continue;
+            }
+            // No corner cutting past blocked tiles
+            if dc != 0 && dr != 0 && (!self.open(col as i32 + dc, row as i32) || !self.open(col as i32, row as i32 + dr)) {
+                continue;
+            }
+            let d = self.dist[idx(nc as u32, nr as u32)];
+            if d != UNREACHABLE && best.is_none_or(|(_, bd)| d < bd) {
+                best = Some(((nc as u32, nr as u32), d));
+            }
+        }
+        let ((nc, nr), _) = best?;
+        let (tx, ty) = room_layout::cell_center(nc, nr);
+        normalize(tx - x, ty - y)
+    }
+
+    fn open(&self, col: i32, row: i32) -> bool {
+        col >= 0 && row >= 0 && col < ROOM_COLS as i32 && row < ROOM_ROWS as i32
+            && self.walkable[idx(col as u32, row as u32)]
+    }
+
+    /// True if a straight move between two points crosses only walkable tiles
+    pub fn line_clear(&self, from: (f32, f32), to: (f32, f32)) -> bool {
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let steps = ((dx * dx + dy * dy).sqrt() / LOS_STEP).ceil() as u32;
+        (0..=steps).all(|i| {
+            let t = if steps == 0 { 0.0 } else { i as f32 / steps as f32 };
+            match room_layout::cell_at(from.0 + dx * t, from.1 + dy * t) {
+                Some((col, row)) => self.walkable[idx(col, row)],
+                None => false,
+            }
+        })
+    }
+}
+
+/// Unit direction of the first step toward the reachable cell (within `max_steps`)
+/// that is furthest from `threat`. None if no cell is further away than the
+/// current one.
+pub fn retreat_step(tiles: &[Tile], from: (f32, f32), threat: (f32, f32), max_steps: u32) -> Option<(f32, f32)> {
+    let start = room_layout::cell_at(from.0, from.1)?;
+    let threat_dist = |col: u32, row: u32| {
+        let (cx, cy) = room_layout::cell_center(col, row);
+        (cx - threat.0).powi(2) + (cy - threat.1).powi(2)
+    };
+
+    // BFS outward from the enemy, remembering each cell's parent
+    let mut parent: Vec<Option<(u32, u32)>> = vec![None; tiles.len()];
+    let mut steps = vec![UNREACHABLE; tiles.len()];
+    let mut queue = VecDeque::new();
+    steps[idx(start.0, start.1)] = 0;
+    queue.push_back(start);
+    let mut best = (start, threat_dist(start.0, start.1));
+    while let Some((col, row)) = queue.pop_front() {
+        let s = steps[idx(col, row)];
+        let d = threat_dist(col, row);
+        if d > best.1 {
+            best = ((col, row), d);
+        }
+        if s >= max_steps {
+            continue;
+        }
+        for (nc, nr) in room_layout::neighbors(col, row) {
+            let i = idx(nc, nr);
+            if tiles[i].is_walkable() && steps[i] == UNREACHABLE {
+                steps[i] = s + 1;
+                parent[i] = Some((col, row));
+                queue.push_back((nc, nr));
+            }
+        }
+    }
+    if best.0 == start {
+        return None;
+    }
+
+    // Walk back to the first cell after the start
+    let mut cell = best.0;
+    while let Some(prev) = parent[idx(cell.0, cell.1)] {
+        if prev == start {
+            break;
+        }
+        cell = prev;
+    }
+    let (tx, ty) = room_layout::cell_center(cell.0, cell.1);
+    normalize(tx - from.0, ty - from.1)
+}
+
+fn normalize(dx: f32, dy: f32) -> Option<(f32, f32)> {
+    let len = (dx * dx + dy * dy).sqrt();
+    (len > 0.1).then(|| (dx / len, dy / len))
+}
+
+fn idx(col: u32, row: u32) -> usize {
+    (row * ROOM_COLS + col) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::room_layout::cell_center;
+
+    /// Open room split by a wall down column 7, passable only at row 17
+    fn walled_room() -> Vec<Tile> {
+        let mut tiles = room_layout::open_room();
+        for row in 1..ROOM_ROWS - 1 {
+            if row != 17 {
+                tiles[idx(7, row)] = Tile::Wall;
+            }
+        }
+        tiles
+    }
+
+    #[test]
+    fn heads_straight_for_a_visible_goal() {
+        let tiles = room_layout::open_room();
+        let (gx, gy) = cell_center(10, 5);
+        let (x, y) = cell_center(3, 5);
+        let field = FlowField::toward(&tiles, gx, gy);
+        let (dx, dy) = field.direction(x, y).unwrap();
+        assert!((dx - 1.0).abs() < 1e-4 && dy.abs() < 1e-4);
+    }
+
+    #[test]
+    fn routes_around_walls() {
+        let tiles = walled_room();
+        let (gx, gy) = cell_center(11, 5);
+        let field = FlowField::toward(&tiles, gx, gy);
+
+        // Following the field walks through the gap and arrives at the goal
+        let (mut x, mut y) = cell_center(3, 5);
+        for _ in 0..400 {
+            let Some((dx, dy)) = field.direction(x, y) else {
+                break;
+            };
+            (x, y) = (x + dx * 4.0, y + dy * 4.0);
+            assert!(room_layout::is_walkable(&tiles, x, y), "walked into a wall at ({x}, {y})");
+            if (x - gx).abs() < 4.0 && (y - gy).abs() < 4.0 {
+                return;
+            }
+        }
+        panic!("never reached the goal, stopped at ({x}, {y})");
+    }
+
+    #[test]
+    fn no_direction_to_an_unreachable_goal() {
+        let mut tiles = room_layout::open_room();
+        for (c, r) in room_layout::neighbors(10, 5) {
+            tiles[idx(c, r)] = Tile::Wall;
+        }
+        let (gx, gy) = cell_center(10, 5);
+        let (x, y) = cell_center(3, 5);
+        assert_eq!(FlowField::toward(&tiles, gx, gy).direction(x, y), None);
+    }
+
+    #[test]
+    fn retreats_away_from_the_threat() {
+        let tiles = room_layout::open_room();
+        let from = cell_center(7, 10);
+        let threat = cell_center(3, 10);
+        let (dx, _) = retreat_step(&tiles, from, threat, 6).unwrap();
+        assert!(dx > 0.0);
+    }
+
+    #[test]
+    fn retreat_goes_around_walls() {
+        let tiles = walled_room();
+        // Backed against the wall: the only way further from the threat is through the gap
+        let from = cell_center(6, 15);
+        let threat = cell_center(2, 10);
+        let (dx, dy) = retreat_step(&tiles, from, threat, 8).unwrap();
+        let next = (from.0 + dx * 4.0, from.1 + dy * 4.0);
+        assert!(room_layout::is_walkable(&tiles, next.0, next.1));
+        assert!(dy > 0.0);
+    }
+
+    #[test]
+    fn no_retreat_when_cornered() {
+        let mut tiles = room_layout::open_room();
+        for (c, r) in room_layout::neighbors(5, 5) {
+            tiles[idx(c, r)] = Tile::Pillar;
+        }
+        let from = cell_center(5, 5);
+        let threat = cell_center(9, 9);
+        assert_eq!(retreat_step(&tiles, from, threat, 6), None);
+        assert_eq!(retreat_step(&room_layout::open_room(), from, threat, 0), None);
+    }
+}
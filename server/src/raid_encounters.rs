@@ -0,0 +1,100 @@
+//! Built-in raid encounters and their boss mechanics. They seed the `raid_encounter`
+//! and `raid_mechanic` tables on init; after that the tables are the source of truth
+//! and `tick_boss_mechanics` interprets whatever rows a boss has.
+
+use spacetimedb::SpacetimeType;
+
+/// What a boss mechanic does when it goes off.
+/// New variants go at the end so stored rows keep their tags.
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MechanicKind {
+    GroundAoe,    // Zones under `count` players; everyone inside when they resolve is hit
+    TankBuster,   // Heavy hit on the boss's target plus a Vulnerable stack (max `count`)
+    Spread,       // Zone on every player; each one hits everyone within `radius` of its owner
+    Stack,        // Zone on one player; the damage is split between everyone inside
+    Adds,         // Summon `count` adds of `add_type`
+    PriorityAdds, // Like Adds, but the boss mends while they live
+    OrbRing,      // Ring of `count` orbs fired outward
+    Pulse,        // Zone of `radius` around the boss (0 = the whole room)
+    SoftEnrage,   // Boss ATK grows by `damage_pct` percent per use
+    HardEnrage,   // Raid-wide hit for `damage_pct` percent of ATK per use
+    Cleave,       // Cone of `radius` and `arc` half-angle toward the boss's target
+}
+
+impl MechanicKind {
+    /// Boss `ai_state` while the mechanic goes off, for client animations
+    pub fn ai_state(self) -> &'static str {
+        match self {
+            MechanicKind::Adds | MechanicKind::PriorityAdds => "summon",
+            MechanicKind::Pulse | MechanicKind::HardEnrage => "aoe",
+            MechanicKind::SoftEnrage => "enrage",
+            MechanicKind::TankBuster | MechanicKind::Cleave => "attack",
+            MechanicKind::GroundAoe | MechanicKind::Spread | MechanicKind::Stack | MechanicKind::OrbRing => "cast",
+        }
+    }
+}
+
+/// Bosses of a raid, fought one after another in the arena
+pub struct EncounterTemplate {
+    pub name: &'static str,
+    pub bosses: &'static [&'static str],
+}
+
+/// Compile-time boss mechanic. A mechanic arms once the boss is at or below `hp_pct`
+/// (100 = from the pull), first goes off `start` seconds later and then repeats every
+/// `interval` seconds (0 = once).
+pub struct MechanicTemplate {
+    pub boss_type: &'static str,
+    pub name: &'static str,
+    pub kind: MechanicKind,
+    pub hp_pct: u32,
+    pub start: f32,
+    pub interval: f32,
+    pub damage_pct: i32, // Percent of boss ATK
+    pub radius: f32,
+    pub arc: f32,        // Cone half-angle in radians
+    pub telegraph: f32,  // Seconds a zone shows before it resolves
+    pub count: u32,
+    pub add_type: &'static str,
+}
+
+const BASE: MechanicTemplate = MechanicTemplate {
+    boss_type: "",
+    name: "",
+    kind: MechanicKind::Pulse,
+    hp_pct: 100,
+    start: 0.0,
+    interval: 0.0,
+    damage_pct: 0,
+    radius: 0.0,
+    arc: 0.0,
+    telegraph: 0.0,
+    count: 0,
+    add_type: "",
+};
+
+/// Encounter new raids are formed for
+pub const DEFAULT_ENCOUNTER: &str = "sunken_crypt";
+
+pub const ENCOUNTERS: &[EncounterTemplate] = &[
+    EncounterTemplate { name: "sunken_crypt", bosses: &["bone_colossus", "raid_boss"] },
+];
+
+pub const MECHANICS: &[MechanicTemplate] = &[
+    // Bone Colossus: tank swaps, spread/stack checks and menders that must die first
+    MechanicTemplate { boss_type: "bone_colossus", name: "Bone Crush", kind: MechanicKind::TankBuster, start: 6.0, interval: 10.0, damage_pct: 200, count: 4, ..BASE },
+    MechanicTemplate { boss_type: "bone_colossus", name: "Bone Sweep", kind: MechanicKind::Cleave, start: 4.0, interval: 9.0, damage_pct: 150, radius: 120.0, arc: 0.8, telegraph: 1.5, ..BASE },
+    MechanicTemplate { boss_type: "bone_colossus", name: "Marrow Shards", kind: MechanicKind::Spread, start: 12.0, interval: 20.0, damage_pct: 80, radius: 70.0, telegraph: 3.0, ..BASE },
+    MechanicTemplate { boss_type: "bone_colossus", name: "Grave Weight", kind: MechanicKind::Stack, start: 22.0, interval: 20.0, damage_pct: 400, radius: 80.0, telegraph: 4.0, ..BASE },
+    MechanicTemplate { boss_type: "bone_colossus", name: "Bone Menders", kind: MechanicKind::PriorityAdds, hp_pct: 50, interval: 25.0, count: 2, add_type: "skeleton", ..BASE },
+    MechanicTemplate { boss_type: "bone_colossus", name: "Brittle Rage", kind: MechanicKind::SoftEnrage, start: 150.0, interval: 15.0, damage_pct: 10, ..BASE },
+    MechanicTemplate { boss_type: "bone_colossus", name: "Collapse", kind: MechanicKind::HardEnrage, start: 240.0, interval: 2.0, damage_pct: 1000, ..BASE },
+    // Raid boss: three phases (adds and orb rings at 60%, enraged deathwaves to run out of at 30%)
+    MechanicTemplate { boss_type: "raid_boss", name: "Crushing Blow", kind: MechanicKind::TankBuster, start: 8.0, interval: 12.0, damage_pct: 250, count: 3, ..BASE },
+    MechanicTemplate { boss_type: "raid_boss", name: "Grave Eruption", kind: MechanicKind::GroundAoe, start: 5.0, interval: 10.0, damage_pct: 120, radius: 60.0, telegraph: 2.0, count: 2, ..BASE },
+    MechanicTemplate { boss_type: "raid_boss", name: "Raise Dead", kind: MechanicKind::Adds, hp_pct: 60, interval: 6.0, count: 2, add_type: "skeleton", ..BASE },
+    MechanicTemplate { boss_type: "raid_boss", name: "Soul Nova", kind: MechanicKind::OrbRing, hp_pct: 60, start: 3.0, interval: 6.0, damage_pct: 50, count: 12, ..BASE },
+    MechanicTemplate { boss_type: "raid_boss", name: "Frenzy", kind: MechanicKind::SoftEnrage, hp_pct: 30, damage_pct: 50, ..BASE },
+    MechanicTemplate { boss_type: "raid_boss", name: "Deathwave", kind: MechanicKind::Pulse, hp_pct: 30, start: 4.0, interval: 4.0, damage_pct: 33, radius: 150.0, telegraph: 1.5, ..BASE },
+    MechanicTemplate { boss_type: "raid_boss", name: "Annihilate", kind: MechanicKind::HardEnrage, start: 360.0, interval: 2.0, damage_pct: 1000, ..BASE },
+];
@@ -0,0 +1,142 @@
+//! Small deterministic PRNG for server-side rolls.
+//! SplitMix64: fast, tiny state, and the same seed always yields the same sequence.
+//! Dungeons persist their stream in `ActiveDungeon.rng_state`, so a run can be
+//! replayed from its seed.
+
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Current state, for persisting the stream between reducer calls
+    pub fn state(&self) -> u64 {
+        self.state
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform float in [0, 1)
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Uniform integer in [lo, hi] (inclusive)
+    pub fn range_u32(&mut self, lo: u32, hi: u32) -> u32 {
+        if hi <= lo {
+            return lo;
+        }
+        lo + (self.next_u64() % (hi - lo + 1) as u64) as u32
+    }
+
+    /// True with probability `p` (0.0 - 1.0)
+    pub fn chance(&mut self, p: f32) -> bool {
+        self.next_f32() < p
+    }
+
+    /// Pick a random index in [0, len)
+    pub fn index(&mut self, len: usize) -> usize {
+        if len == 0 {
+            return 0;
+        }
+        (self.next_u64() % len as u64) as usize
+    }
+
+    /// Pick an index proportionally to the given weights. Zero (or negative) weights
+    /// are never picked unless every weight is.
+    pub fn weighted_index(&mut self, weights: &[f32]) -> usize {
+        pick_weighted(weights, self.next_f32())
+    }
+}
+
+/// Index that `roll` (in [0, 1)) lands on when the positive weights are laid end to end
+fn pick_weighted(weights: &[f32], roll: f32) -> usize {
+    let total: f32 = weights.iter().filter(|w| **w > 0.0).sum();
+    let mut roll = roll * total;
+    let mut last = 0;
+    for (i, &w) in weights.iter().enumerate() {
+        if w <= 0.0 {
+            continue;
+        }
+        roll -= w;
+        if roll <= 0.0 {
+            return i;
+        }
+        last = i;
+    }
+    // Float rounding can leave a sliver of the roll over
+    last
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_reference_splitmix64_sequence() {
+        let mut rng = Rng::new(1234567);
+        let expected = [
+            6457827717110365317,
+            3203168211198807973,
+            9817491932198370423,
+            4593380528125082431,
+            16408922859458223821,
+        ];
+        for value in expected {
+            assert_eq!(rng.next_u64(), value);
+        }
+    }
+
+    #[test]
+    fn same_seed_gives_same_rolls() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        for _ in 0..100 {
+            assert_eq!(a.range_u32(1, 6), b.range_u32(1, 6));
+            assert_eq!(a.next_f32(), b.next_f32());
+        }
+        assert_eq!(a.state(), b.state());
+    }
+
+    #[test]
+    fn resumes_from_persisted_state() {
+        let mut original = Rng::new(7);
+        original.next_u64();
+        let mut resumed = Rng::new(original.state());
+        assert_eq!(original.next_u64(), resumed.next_u64());
+    }
+
+    #[test]
+    fn rolls_stay_in_range() {
+        let mut rng = Rng::new(99);
+        for _ in 0..1000 {
+            let f = rng.next_f32();
+            assert!((0.0..1.0).contains(&f));
+            assert!((3..=5).contains(&rng.range_u32(3, 5)));
+            assert!(rng.index(4) < 4);
+        }
+        assert_eq!(rng.range_u32(8, 8), 8);
+        assert_eq!(rng.weighted_index(&[0.0, 1.0, 0.0]), 1);
+    }
+
+    #[test]
+    fn zero_weights_are_never_picked() {
+        assert_eq!(pick_weighted(&[0.0, 3.0, 1.0], 0.0), 1);
+        assert_eq!(pick_weighted(&[0.0, 3.0, 1.0, 0.0], 0.999_999), 2);
+        assert_eq!(pick_weighted(&[1.0, 0.0], 0.999_999), 0);
+        let mut rng = Rng::new(5);
+        for _ in 0..1000 {
+            let i = rng.weighted_index(&[0.0, 3.0, 0.0, 1.0, 0.0]);
+            assert!(i == 1 || i == 3);
+        }
+    }
+}
@@ -0,0 +1,384 @@
+//! Seeded tile maps for dungeon rooms: border walls, doors, pillars, wall segments and pits.
+//! Layouts are stored in the `room_tile_map` table so the client renders the same room
+//! the server collides against.
+
+use spacetimedb::SpacetimeType;
+
+use crate::rng::Rng;
+use crate::TILE_SIZE;
+
+/// Room grid size in tiles (matches client ROOM_W=15, ROOM_H=20)
+pub const ROOM_COLS: u32 = 15;
+pub const ROOM_ROWS: u32 = 20;
+
+/// Tiles kept clear around the room center (player spawn) and each door
+const CLEAR_RADIUS: i32 = 2;
+/// Step size when sweeping movement along a segment
+const SWEEP_STEP: f32 = TILE_SIZE / 4.0;
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tile {
+    Floor,
+    Wall,
+    Pillar,
+    Pit,
+    Door,
+}
+
+impl Tile {
+    /// Entities can stand on this tile
+    pub fn is_walkable(self) -> bool {
+        matches!(self, Tile::Floor | Tile::Door)
+    }
+
+    /// Solid tiles stop charges and projectiles (pits only block walking)
+    pub fn is_solid(self) -> bool {
+        matches!(self, Tile::Wall | Tile::Pillar)
+    }
+}
+
+/// Wall a door sits in
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Side {
+    /// Door tile at the center of this wall
+    pub fn door_tile(self) -> (u32, u32) {
+        match self {
+            Side::North => (ROOM_COLS / 2, 0),
+            Side::South => (ROOM_COLS / 2, ROOM_ROWS - 1),
+            Side::East => (ROOM_COLS - 1, ROOM_ROWS / 2),
+            Side::West => (0, ROOM_ROWS / 2),
+        }
+    }
+
+    pub fn opposite(self) -> Side {
+        match self {
+            Side::North => Side::South,
+            Side::South => Side::North,
+            Side::East => Side::West,
+            Side::West => Side::East,
+        }
+    }
+}
+
+/// Obstacle shapes stamped into the room interior
+enum Feature {
+    Pillar,
+    PillarBlock,
+    WallSegment { len: u32, vertical: bool },
+    Pit { w: u32, h: u32 },
+}
+
+// ─── Generation ────────────────────────────────────────────────────────────────
+
+/// Generate a room with doors on the given sides. `features` is the number of
+/// obstacle groups to try placing; arenas pass a small number.
+pub fn generate(rng: &mut Rng, doors: &[Side], features: u32) -> Vec<Tile> {
+    let mut tiles = vec![Tile::Floor; (ROOM_COLS * ROOM_ROWS) as usize];
+    for row in 0..ROOM_ROWS {
+        for col in 0..ROOM_COLS {
+            if row == 0 || col == 0 || row == ROOM_ROWS - 1 || col == ROOM_COLS - 1 {
+                tiles[idx(col, row)] = Tile::Wall;
+            }
+        }
+    }
+    for side in doors {
+        let (col, row) = side.door_tile();
+        tiles[idx(col, row)] = Tile::Door;
+    }
+
+    let mut keep_clear = vec![(ROOM_COLS / 2, ROOM_ROWS / 2)];
+    keep_clear.extend(doors.iter().map(|s| s.door_tile()));
+
+    // Mirrored layouts read better; decide once per room
+    let mirror = rng.chance(0.5);
+    for _ in 0..features {
+        let feature = match rng.weighted_index(&[30.0, 20.0, 30.0, 20.0]) {
+            0 => Feature::Pillar,
+            1 => Feature::PillarBlock,
+            2 => Feature::WallSegment { len: rng.range_u32(3, 5), vertical: rng.chance(0.5) },
+            _ => Feature::Pit { w: rng.range_u32(2, 3), h: rng.range_u32(1, 2) },
+        };
+        let col = rng.range_u32(1, ROOM_COLS - 2);
+        let row = rng.range_u32(1, ROOM_ROWS - 2);
+
+        let mut cells = stamp(&feature, col, row);
+        if mirror {
+            // Cells past the east wall have no mirror; try_place rejects the feature anyway
+            let mirrored: Vec<(u32, u32, Tile)> = cells.iter()
+                .filter_map(|&(c, r, t)| (ROOM_COLS - 1).checked_sub(c).map(|mc| (mc, r, t)))
+                .collect();
+            cells.extend(mirrored);
+        }
+        try_place(&mut tiles, &cells, &keep_clear);
+    }
+    tiles
+}
+
+/// Plain walled room with no doors or obstacles
+pub fn open_room() -> Vec<Tile> {
+    generate(&mut Rng::new(0), &[], 0)
+}
+
+/// Grid with no walls at all, used for open world rooms
+pub fn open_field() -> Vec<Tile> {
+    vec![Tile::Floor; (ROOM_COLS * ROOM_ROWS) as usize]
+}
+
+/// Tiles covered by a feature anchored at (col, row)
+fn stamp(feature: &Feature, col: u32, row: u32) -> Vec<(u32, u32, Tile)> {
+    let rect = |w: u32, h: u32, tile: Tile| {
+        let mut cells = Vec::new();
+        for dr in 0..h {
+            for dc in 0..w {
+                cells.push((col + dc, row + dr, tile));
+            }
+        }
+        cells
+    };
+    match *feature {
+        Feature::Pillar => rect(1, 1, Tile::Pillar),
+        Feature::PillarBlock => rect(2, 2, Tile::Pillar),
+        Feature::WallSegment { len, vertical: true } => rect(1, len, Tile::Wall),
+        Feature::WallSegment { len, vertical: false } => rect(len, 1, Tile::Wall),
+        Feature::Pit { w, h } => rect(w, h, Tile::Pit),
+    }
+}
+
+/// Place a feature if it stays inside the room, off the clear zones, and leaves
+/// every walkable tile reachable from the room center
+fn try_place(tiles: &mut [Tile], cells: &[(u32, u32, Tile)], keep_clear: &[(u32, u32)]) -> bool {
+    for &(c, r, _) in cells {
+        if c == 0 || r == 0 || c >= ROOM_COLS - 1 || r >= ROOM_ROWS - 1 {
+            return false;
+        }
+        let near_clear = keep_clear.iter().any(|&(kc, kr)| {
+            (c as i32 - kc as i32).abs() <= CLEAR_RADIUS && (r as i32 - kr as i32).abs() <= CLEAR_RADIUS
+        });
+        if near_clear {
+            return false;
+        }
+    }
+
+    let before = tiles.to_vec();
+    for &(c, r, t) in cells {
+        tiles[idx(c, r)] = t;
+    }
+    if !fully_connected(tiles) {
+        tiles.copy_from_slice(&before);
+        return false;
+    }
+    true
+}
+
+/// True if every walkable tile can be reached from the room center
+fn fully_connected(tiles: &[Tile]) -> bool {
+    let start = (ROOM_COLS / 2, ROOM_ROWS / 2);
+    let mut seen = vec![false; tiles.len()];
+    let mut stack = vec![start];
+    seen[idx(start.0, start.1)] = true;
+    let mut reached = 0;
+    while let Some((c, r)) = stack.pop() {
+        reached += 1;
+        for (nc, nr) in neighbors(c, r) {
+            let i = idx(nc, nr);
+            if !seen[i] && tiles[i].is_walkable() {
+                seen[i] = true;
+                stack.push((nc, nr));
+            }
+        }
+    }
+    reached == tiles.iter().filter(|t| t.is_walkable()).count()
+}
+
+/// 4-connected neighbors inside the grid
+pub fn neighbors(col: u32, row: u32) -> impl Iterator<Item = (u32, u32)> {
+    let (c, r) = (col as i32, row as i32);
+    [(c + 1, r), (c - 1, r), (c, r + 1), (c, r - 1)]
+        .into_iter()
+        .filter(|&(nc, nr)| nc >= 0 && nr >= 0 && nc < ROOM_COLS as i32 && nr < ROOM_ROWS as i32)
+        .map(|(nc, nr)| (nc as u32, nr as u32))
+}
+
+fn idx(col: u32, row: u32) -> usize {
+    (row * ROOM_COLS + col) as usize
+}
+
+// ─── Queries ───────────────────────────────────────────────────────────────────
+
+/// Grid cell containing a pixel position, if inside the room
+pub fn cell_at(x: f32, y: f32) -> Option<(u32, u32)> {
+    if x < 0.0 || y < 0.0 {
+        return None;
+    }
+    let (col, row) = ((x / TILE_SIZE) as u32, (y / TILE_SIZE) as u32);
+    (col < ROOM_COLS && row < ROOM_ROWS).then_some((col, row))
+}
+
+/// Pixel center of a grid cell
+pub fn cell_center(col: u32, row: u32) -> (f32, f32) {
+    ((col as f32 + 0.5) * TILE_SIZE, (row as f32 + 0.5) * TILE_SIZE)
+}
+
+/// Tile at a pixel position; outside the room counts as wall
+pub fn tile_at(tiles: &[Tile], x: f32, y: f32) -> Tile {
+    match cell_at(x, y) {
+        Some((col, row)) => tiles.get(idx(col, row)).copied().unwrap_or(Tile::Wall),
+        None => Tile::Wall,
+    }
+}
+
+pub fn is_walkable(tiles: &[Tile], x: f32, y: f32) -> bool {
+    tile_at(tiles, x, y).is_walkable()
+}
+
+/// Resolve a move against the tile map: take the full move if possible, otherwise
+/// slide along one axis, otherwise stay put
+pub fn slide_move(tiles: &[Tile], from: (f32, f32), to: (f32, f32)) -> (f32, f32) {
+    if is_walkable(tiles, to.0, to.1) {
+        to
+    } else if is_walkable(tiles, to.0, from.1) {
+        (to.0, from.1)
+    } else if is_walkable(tiles, from.0, to.1) {
+        (from.0, to.1)
+    } else {
+        from
+    }
+}
+
+/// Furthest walkable point along a straight line from `from` toward `to`
+pub fn sweep(tiles: &[Tile], from: (f32, f32), to: (f32, f32)) -> (f32, f32) {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let dist = (dx * dx + dy * dy).sqrt();
+    let steps = (dist / SWEEP_STEP).ceil() as u32;
+    let mut last = from;
+    for i in 1..=steps {
+        let t = i as f32 / steps as f32;
+        let p = (from.0 + dx * t, from.1 + dy * t);
+        if !is_walkable(tiles, p.0, p.1) {
+            break;
+        }
+        last = p;
+    }
+    last
+}
+
+/// Closest walkable cell center to a position (searching outward ring by ring)
+pub fn nearest_walkable(tiles: &[Tile], x: f32, y: f32) -> (f32, f32) {
+    if is_walkable(tiles, x, y) {
+        return (x, y);
+    }
+    let col = (x / TILE_SIZE).clamp(0.0, (ROOM_COLS - 1) as f32) as i32;
+    let row = (y / TILE_SIZE).clamp(0.0, (ROOM_ROWS - 1) as f32) as i32;
+    for radius in 1..ROOM_ROWS as i32 {
+        for dr in -radius..=radius {
+            for dc in -radius..=radius {
+                if dr.abs() != radius && dc.abs() != radius {
+                    continue;
+                }
+                let (c, r) = (col + dc, row + dr);
+                if c < 0 || r < 0 || c >= ROOM_COLS as i32 || r >= ROOM_ROWS as i32 {
+                    continue;
+                }
+                if tiles[idx(c as u32, r as u32)].is_walkable() {
+                    return cell_center(c as u32, r as u32);
+                }
+            }
+        }
+    }
+    cell_center(ROOM_COLS / 2, ROOM_ROWS / 2)
+}
+
+/// Random walkable cell center, falling back to the room center
+pub fn random_walkable(tiles: &[Tile], rng: &mut Rng) -> (f32, f32) {
+    for _ in 0..32 {
+        let col = rng.range_u32(1, ROOM_COLS - 2);
+        let row = rng.range_u32(1, ROOM_ROWS - 2);
+        if tiles[idx(col, row)] == Tile::Floor {
+            return cell_center(col, row);
+        }
+    }
+    cell_center(ROOM_COLS / 2, ROOM_ROWS / 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_SIDES: [Side; 4] = [Side::North, Side::South, Side::East, Side::West];
+
+    /// Cells reachable from the room center by walking
+    fn reachable(tiles: &[Tile]) -> Vec<bool> {
+        let start = (ROOM_COLS / 2, ROOM_ROWS / 2);
+        let mut seen = vec![false; tiles.len()];
+        let mut stack = vec![start];
+        seen[idx(start.0, start.1)] = true;
+        while let Some((c, r)) = stack.pop() {
+            for (nc, nr) in neighbors(c, r) {
+                if !seen[idx(nc, nr)] && tiles[idx(nc, nr)].is_walkable() {
+                    seen[idx(nc, nr)] = true;
+                    stack.push((nc, nr));
+                }
+            }
+        }
+        seen
+    }
+
+    #[test]
+    fn every_door_is_reachable_from_the_center() {
+        for seed in 0..200 {
+            let tiles = generate(&mut Rng::new(seed), &ALL_SIDES, 12);
+            let seen = reachable(&tiles);
+            for side in ALL_SIDES {
+                let (col, row) = side.door_tile();
+                assert_eq!(tiles[idx(col, row)], Tile::Door, "seed {seed} {side:?}");
+                assert!(seen[idx(col, row)], "seed {seed}: {side:?} door cut off");
+            }
+        }
+    }
+
+    #[test]
+    fn every_walkable_tile_is_reachable() {
+        for seed in 0..200 {
+            let tiles = generate(&mut Rng::new(seed), &[Side::North, Side::East], 12);
+            let seen = reachable(&tiles);
+            for (i, tile) in tiles.iter().enumerate() {
+                assert!(!tile.is_walkable() || seen[i], "seed {seed}: tile {i} cut off");
+            }
+        }
+    }
+
+    #[test]
+    fn same_seed_gives_same_layout() {
+        let a = generate(&mut Rng::new(31), &ALL_SIDES, 8);
+        let b = generate(&mut Rng::new(31), &ALL_SIDES, 8);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn border_is_wall_except_doors() {
+        let tiles = generate(&mut Rng::new(5), &[Side::West], 8);
+        for row in 0..ROOM_ROWS {
+            for col in 0..ROOM_COLS {
+                let border = row == 0 || col == 0 || row == ROOM_ROWS - 1 || col == ROOM_COLS - 1;
+                if border && (col, row) != Side::West.door_tile() {
+                    assert_eq!(tiles[idx(col, row)], Tile::Wall);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn slide_move_keeps_the_free_axis() {
+        let tiles = open_room();
+        let from = cell_center(1, 5);
+        let (x, y) = slide_move(&tiles, from, (from.0 - TILE_SIZE, from.1 + 4.0));
+        assert_eq!((x, y), (from.0, from.1 + 4.0));
+    }
+}